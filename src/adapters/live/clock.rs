@@ -1,5 +1,8 @@
 //! Live clock using the system clock.
 
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
 use chrono::{DateTime, Utc};
 
 use crate::ports::clock::Clock;
@@ -11,6 +14,11 @@ impl Clock for LiveClock {
     fn now(&self) -> DateTime<Utc> {
         Utc::now()
     }
+
+    fn monotonic(&self) -> Duration {
+        static ORIGIN: OnceLock<Instant> = OnceLock::new();
+        ORIGIN.get_or_init(Instant::now).elapsed()
+    }
 }
 
 #[cfg(test)]
@@ -27,4 +35,13 @@ mod tests {
         assert!(now >= before);
         assert!(now <= after);
     }
+
+    #[test]
+    fn monotonic_never_goes_backwards() {
+        let clock = LiveClock;
+        let first = clock.monotonic();
+        let second = clock.monotonic();
+
+        assert!(second >= first);
+    }
 }
@@ -0,0 +1,24 @@
+//! Live adapter for the `Environment` port.
+
+use crate::ports::Environment;
+
+/// Live environment that reads the current process environment.
+pub struct LiveEnvironment;
+
+impl Environment for LiveEnvironment {
+    fn var(&self, name: &str) -> Option<String> {
+        std::env::var(name).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_existing_variable() {
+        let env = LiveEnvironment;
+        assert!(env.var("PATH").is_some());
+        assert_eq!(env.var("SPECK_TEST_SURELY_UNSET_VARIABLE"), None);
+    }
+}
@@ -2,7 +2,7 @@
 
 use std::path::Path;
 
-use crate::ports::filesystem::FileSystem;
+use crate::ports::filesystem::{AsyncFileSystem, FileMetadata, FileSystem, FsFuture};
 
 /// Live filesystem adapter backed by real disk I/O.
 pub struct LiveFileSystem;
@@ -20,12 +20,41 @@ impl FileSystem for LiveFileSystem {
         path: &Path,
         contents: &str,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        if let Some(parent) = path.parent() {
-            std::fs::create_dir_all(parent)?;
-        }
         Ok(std::fs::write(path, contents)?)
     }
 
+    fn create_dir_all(&self, path: &Path) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        Ok(std::fs::create_dir_all(path)?)
+    }
+
+    fn rename(
+        &self,
+        from: &Path,
+        to: &Path,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        Ok(std::fs::rename(from, to)?)
+    }
+
+    fn remove_file(&self, path: &Path) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        Ok(std::fs::remove_file(path)?)
+    }
+
+    fn remove_dir(&self, path: &Path) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        Ok(std::fs::remove_dir(path)?)
+    }
+
+    fn metadata(
+        &self,
+        path: &Path,
+    ) -> Result<FileMetadata, Box<dyn std::error::Error + Send + Sync>> {
+        let meta = std::fs::metadata(path)?;
+        Ok(FileMetadata {
+            len: meta.len(),
+            modified: meta.modified()?.into(),
+            is_dir: meta.is_dir(),
+        })
+    }
+
     fn exists(&self, path: &Path) -> bool {
         path.exists()
     }
@@ -45,3 +74,34 @@ impl FileSystem for LiveFileSystem {
         Ok(entries)
     }
 }
+
+/// Live asynchronous filesystem adapter backed by `tokio::fs`.
+pub struct LiveAsyncFileSystem;
+
+impl AsyncFileSystem for LiveAsyncFileSystem {
+    fn read_to_string<'a>(&'a self, path: &'a Path) -> FsFuture<'a, String> {
+        Box::pin(async move { Ok(tokio::fs::read_to_string(path).await?) })
+    }
+
+    fn write<'a>(&'a self, path: &'a Path, contents: &'a str) -> FsFuture<'a, ()> {
+        Box::pin(async move { Ok(tokio::fs::write(path, contents).await?) })
+    }
+
+    fn create_dir_all<'a>(&'a self, path: &'a Path) -> FsFuture<'a, ()> {
+        Box::pin(async move { Ok(tokio::fs::create_dir_all(path).await?) })
+    }
+
+    fn list_dir<'a>(&'a self, path: &'a Path) -> FsFuture<'a, Vec<String>> {
+        Box::pin(async move {
+            let mut entries = Vec::new();
+            let mut dir = tokio::fs::read_dir(path).await?;
+            while let Some(entry) = dir.next_entry().await? {
+                if let Some(name) = entry.file_name().to_str() {
+                    entries.push(name.to_string());
+                }
+            }
+            entries.sort();
+            Ok(entries)
+        })
+    }
+}
@@ -3,6 +3,8 @@
 use std::path::Path;
 use std::process::Command;
 
+use chrono::{DateTime, Utc};
+
 use crate::ports::git::GitRepo;
 
 /// Live git adapter that shells out to the `git` CLI.
@@ -27,6 +29,25 @@ impl GitRepo for LiveGitRepo {
         Ok(String::from_utf8_lossy(&output.stdout).into_owned())
     }
 
+    fn last_modified(
+        &self,
+        path: &Path,
+    ) -> Result<(DateTime<Utc>, String), Box<dyn std::error::Error + Send + Sync>> {
+        let path_str = path.to_string_lossy();
+        let output = Command::new("git")
+            .args(["log", "-1", "--format=%aI%x00%an", "--", &path_str])
+            .output()?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(format!("git log failed: {stderr}").into());
+        }
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let (timestamp, author) =
+            stdout.trim().split_once('\0').ok_or_else(|| format!("no commits touch {path_str}"))?;
+        let timestamp = DateTime::parse_from_rfc3339(timestamp)?.with_timezone(&Utc);
+        Ok((timestamp, author.to_string()))
+    }
+
     fn list_files(
         &self,
         path: &Path,
@@ -44,6 +65,59 @@ impl GitRepo for LiveGitRepo {
             .collect();
         Ok(files)
     }
+
+    fn fetch_remote_commit(
+        &self,
+        remote_ref: &str,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let (remote, branch) = remote_ref
+            .split_once('/')
+            .ok_or_else(|| format!("expected <remote>/<branch>, got '{remote_ref}'"))?;
+        let output = Command::new("git").args(["fetch", "--quiet", remote, branch]).output()?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(format!("git fetch {remote} {branch} failed: {stderr}").into());
+        }
+        let output = Command::new("git")
+            .args(["rev-parse", "--verify", &format!("{remote_ref}^{{commit}}")])
+            .output()?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(format!("git rev-parse {remote_ref} failed: {stderr}").into());
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    fn list_files_at(
+        &self,
+        commit: &str,
+    ) -> Result<Vec<String>, Box<dyn std::error::Error + Send + Sync>> {
+        let output = Command::new("git").args(["ls-tree", "-r", "--name-only", commit]).output()?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(format!("git ls-tree {commit} failed: {stderr}").into());
+        }
+        let files = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter(|s| !s.is_empty())
+            .map(String::from)
+            .collect();
+        Ok(files)
+    }
+
+    fn read_file_at(
+        &self,
+        commit: &str,
+        path: &Path,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let spec = format!("{commit}:{}", path.to_string_lossy());
+        let output = Command::new("git").args(["show", &spec]).output()?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(format!("git show {spec} failed: {stderr}").into());
+        }
+        Ok(String::from_utf8(output.stdout)?)
+    }
 }
 
 #[cfg(test)]
@@ -68,6 +142,15 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn gets_last_modified() {
+        let git = LiveGitRepo;
+        let (_, author) = git.last_modified(Path::new("Cargo.toml")).unwrap();
+
+        assert!(!author.is_empty());
+        assert!(git.last_modified(Path::new("no/such/path.rs")).is_err());
+    }
+
     #[test]
     fn lists_files() {
         let git = LiveGitRepo;
@@ -78,4 +161,22 @@ mod tests {
         assert!(!files.is_empty());
         assert!(files.iter().any(|f| f.contains("main.rs") || f.contains("lib.rs")));
     }
+
+    #[test]
+    fn reads_files_at_commit() {
+        let git = LiveGitRepo;
+        let head = git.current_commit().unwrap();
+
+        let files = git.list_files_at(&head).unwrap();
+        assert!(files.iter().any(|f| f == "Cargo.toml"));
+        let manifest = git.read_file_at(&head, Path::new("Cargo.toml")).unwrap();
+        assert!(manifest.contains("[package]"));
+        assert!(git.read_file_at(&head, Path::new("no/such/path.rs")).is_err());
+    }
+
+    #[test]
+    fn rejects_remote_ref_without_branch() {
+        let err = LiveGitRepo.fetch_remote_commit("origin").unwrap_err();
+        assert!(err.to_string().contains("<remote>/<branch>"), "{err}");
+    }
 }
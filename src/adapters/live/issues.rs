@@ -2,7 +2,7 @@
 //!
 //! Shells out to the `bd` CLI for issue management.
 
-use crate::ports::{Issue, IssueTracker};
+use crate::ports::{Issue, IssueStatus, IssueTracker};
 use std::process::Command;
 
 /// Represents a bd CLI issue in JSON output.
@@ -13,6 +13,8 @@ struct BdIssue {
     #[serde(default)]
     description: Option<String>,
     status: String,
+    #[serde(default)]
+    labels: Vec<String>,
 }
 
 impl From<BdIssue> for Issue {
@@ -22,10 +24,20 @@ impl From<BdIssue> for Issue {
             title: bd.title,
             body: bd.description.unwrap_or_default(),
             status: bd.status,
+            labels: bd.labels,
         }
     }
 }
 
+/// Translates a normalized status into the `bd` CLI's status vocabulary.
+fn bd_status(status: IssueStatus) -> &'static str {
+    match status {
+        IssueStatus::Open => "open",
+        IssueStatus::InProgress => "in_progress",
+        IssueStatus::Closed => "closed",
+    }
+}
+
 /// Live issue tracker that shells out to the `bd` CLI.
 pub struct LiveIssueTracker;
 
@@ -34,10 +46,16 @@ impl IssueTracker for LiveIssueTracker {
         &self,
         title: &str,
         body: &str,
+        labels: &[String],
     ) -> Result<Issue, Box<dyn std::error::Error + Send + Sync>> {
         // Create the issue and capture the ID
+        let mut create_args = vec!["create", title, "-d", body, "--silent"];
+        let joined_labels = labels.join(",");
+        if !labels.is_empty() {
+            create_args.extend(["--labels", &joined_labels]);
+        }
         let create_output = Command::new("bd")
-            .args(["create", title, "-d", body, "--silent"])
+            .args(&create_args)
             .output()
             .map_err(|e| format!("Failed to run bd: {e}. Is bd installed?"))?;
 
@@ -73,10 +91,11 @@ impl IssueTracker for LiveIssueTracker {
         id: &str,
         title: Option<&str>,
         body: Option<&str>,
-        status: Option<&str>,
+        status: Option<IssueStatus>,
+        labels: &[String],
     ) -> Result<Issue, Box<dyn std::error::Error + Send + Sync>> {
         // Handle status=closed via bd close
-        if status == Some("closed") {
+        if status == Some(IssueStatus::Closed) {
             let close_output = Command::new("bd")
                 .args(["close", id])
                 .output()
@@ -98,6 +117,14 @@ impl IssueTracker for LiveIssueTracker {
             update_args.push("-d".to_string());
             update_args.push(b.to_string());
         }
+        if let Some(s) = status.filter(|s| *s != IssueStatus::Closed) {
+            update_args.push("--status".to_string());
+            update_args.push(bd_status(s).to_string());
+        }
+        for label in labels {
+            update_args.push("--add-label".to_string());
+            update_args.push(label.clone());
+        }
 
         if update_args.len() > 2 {
             let update_output = Command::new("bd")
@@ -193,12 +220,21 @@ mod tests {
             title: "Test issue".to_string(),
             description: Some("A description".to_string()),
             status: "open".to_string(),
+            labels: vec!["area:sync".to_string()],
         };
         let issue: Issue = bd.into();
         assert_eq!(issue.id, "speck-1");
         assert_eq!(issue.title, "Test issue");
         assert_eq!(issue.body, "A description");
         assert_eq!(issue.status, "open");
+        assert_eq!(issue.labels, ["area:sync"]);
+    }
+
+    #[test]
+    fn bd_status_maps_normalized_statuses() {
+        assert_eq!(bd_status(IssueStatus::Open), "open");
+        assert_eq!(bd_status(IssueStatus::InProgress), "in_progress");
+        assert_eq!(bd_status(IssueStatus::Closed), "closed");
     }
 
     #[test]
@@ -208,6 +244,7 @@ mod tests {
             title: "No body".to_string(),
             description: None,
             status: "closed".to_string(),
+            labels: vec![],
         };
         let issue: Issue = bd.into();
         assert_eq!(issue.body, "");
@@ -5,12 +5,20 @@ use std::env;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 
-use crate::ports::llm::{CompletionFuture, CompletionRequest, CompletionResponse, LlmClient};
+use crate::ports::llm::{
+    CompletionFuture, CompletionRequest, CompletionResponse, EmbeddingFuture, LlmClient,
+};
 
 const ANTHROPIC_API_URL: &str = "https://api.anthropic.com/v1/messages";
 const ANTHROPIC_VERSION: &str = "2023-06-01";
+const VOYAGE_API_URL: &str = "https://api.voyageai.com/v1/embeddings";
+const VOYAGE_DEFAULT_MODEL: &str = "voyage-3";
 
 /// Live LLM client that calls the Anthropic Claude API.
+///
+/// The messages API has no embedding endpoint, so [`LlmClient::embed`] calls
+/// the Voyage AI embeddings API instead. It needs `VOYAGE_API_KEY`; the model
+/// defaults to `voyage-3` and can be overridden with `SPECK_EMBED_MODEL`.
 pub struct LiveLlmClient {
     client: Client,
 }
@@ -76,6 +84,32 @@ struct AnthropicErrorDetail {
     message: String,
 }
 
+/// Request body sent to the Voyage AI embeddings API.
+#[derive(Serialize)]
+struct VoyageRequest<'a> {
+    model: &'a str,
+    input: &'a [String],
+}
+
+/// Top-level response from the Voyage AI embeddings API.
+#[derive(Deserialize)]
+struct VoyageResponse {
+    data: Vec<VoyageEmbedding>,
+}
+
+/// A single embedding in the Voyage AI response.
+#[derive(Deserialize)]
+struct VoyageEmbedding {
+    embedding: Vec<f32>,
+    index: usize,
+}
+
+/// Error response from the Voyage AI API.
+#[derive(Deserialize)]
+struct VoyageError {
+    detail: String,
+}
+
 impl LlmClient for LiveLlmClient {
     fn complete(&self, request: &CompletionRequest) -> CompletionFuture<'_> {
         let model = request.model.clone();
@@ -135,4 +169,50 @@ impl LlmClient for LiveLlmClient {
             })
         })
     }
+
+    fn embed(&self, texts: &[String]) -> EmbeddingFuture<'_> {
+        let texts = texts.to_vec();
+
+        Box::pin(async move {
+            let api_key = env::var("VOYAGE_API_KEY").map_err(|_| {
+                Box::<dyn std::error::Error + Send + Sync>::from(
+                    "VOYAGE_API_KEY environment variable not set",
+                )
+            })?;
+            let model =
+                env::var("SPECK_EMBED_MODEL").unwrap_or_else(|_| VOYAGE_DEFAULT_MODEL.to_string());
+
+            let response = self
+                .client
+                .post(VOYAGE_API_URL)
+                .bearer_auth(&api_key)
+                .json(&VoyageRequest { model: &model, input: &texts })
+                .send()
+                .await
+                .map_err(|e| -> Box<dyn std::error::Error + Send + Sync> {
+                    format!("Voyage AI embeddings request failed: {e}").into()
+                })?;
+
+            let status = response.status();
+            let response_text =
+                response.text().await.map_err(|e| -> Box<dyn std::error::Error + Send + Sync> {
+                    format!("Failed to read Voyage AI response: {e}").into()
+                })?;
+
+            if !status.is_success() {
+                let msg = serde_json::from_str::<VoyageError>(&response_text)
+                    .map(|e| e.detail)
+                    .unwrap_or(response_text);
+                return Err(format!("Voyage AI error ({}): {msg}", status.as_u16()).into());
+            }
+
+            let mut api_response: VoyageResponse = serde_json::from_str(&response_text).map_err(
+                |e| -> Box<dyn std::error::Error + Send + Sync> {
+                    format!("Failed to parse Voyage AI response: {e}").into()
+                },
+            )?;
+            api_response.data.sort_by_key(|e| e.index);
+            Ok(api_response.data.into_iter().map(|e| e.embedding).collect())
+        })
+    }
 }
@@ -1,6 +1,7 @@
 //! Live adapters for real external interactions.
 
 pub mod clock;
+pub mod env;
 pub mod filesystem;
 pub mod git;
 pub mod id_gen;
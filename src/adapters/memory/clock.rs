@@ -0,0 +1,31 @@
+//! Fixed clock for tests.
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+
+use crate::ports::clock::Clock;
+
+/// Clock that always reports the same instant and never sleeps.
+///
+/// Its monotonic reading is always zero, so no time ever elapses.
+pub struct FixedClock(pub DateTime<Utc>);
+
+impl Default for FixedClock {
+    /// Fixed at the Unix epoch.
+    fn default() -> Self {
+        Self(DateTime::UNIX_EPOCH)
+    }
+}
+
+impl Clock for FixedClock {
+    fn now(&self) -> DateTime<Utc> {
+        self.0
+    }
+
+    fn monotonic(&self) -> Duration {
+        Duration::ZERO
+    }
+
+    fn sleep(&self, _duration: Duration) {}
+}
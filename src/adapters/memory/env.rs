@@ -0,0 +1,26 @@
+//! In-memory environment for tests.
+
+use std::collections::HashMap;
+
+use crate::ports::Environment;
+
+/// Environment backed by a map; empty unless variables are added.
+#[derive(Default)]
+pub struct MemEnvironment {
+    vars: HashMap<String, String>,
+}
+
+impl MemEnvironment {
+    /// Returns this environment with `name` set to `value`.
+    #[must_use]
+    pub fn with_var(mut self, name: &str, value: &str) -> Self {
+        self.vars.insert(name.to_string(), value.to_string());
+        self
+    }
+}
+
+impl Environment for MemEnvironment {
+    fn var(&self, name: &str) -> Option<String> {
+        self.vars.get(name).cloned()
+    }
+}
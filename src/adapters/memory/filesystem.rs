@@ -0,0 +1,167 @@
+//! In-memory filesystem for tests.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use crate::ports::{AsyncFileSystem, FileMetadata, FileSystem, FsFuture};
+
+/// Filesystem that keeps file contents in memory.
+///
+/// A path exists as a directory when it was created with `create_dir_all`
+/// or any file lives beneath it. Clones share the same contents, so one
+/// clone can back the synchronous port and another the asynchronous one.
+#[derive(Clone, Default)]
+pub struct MemFileSystem {
+    files: Arc<Mutex<HashMap<PathBuf, String>>>,
+    dirs: Arc<Mutex<HashSet<PathBuf>>>,
+    /// Writes to paths ending with this suffix fail (for fault injection).
+    fail_writes_to: Option<String>,
+}
+
+impl MemFileSystem {
+    /// Creates an empty in-memory filesystem.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a filesystem where writes to paths ending in `suffix` fail.
+    #[must_use]
+    pub fn failing_writes_to(suffix: &str) -> Self {
+        Self { fail_writes_to: Some(suffix.to_string()), ..Self::new() }
+    }
+
+    /// Returns a shared handle to the stored files, so tests can seed or
+    /// inspect them after the filesystem has moved into a context.
+    #[must_use]
+    pub fn files(&self) -> Arc<Mutex<HashMap<PathBuf, String>>> {
+        Arc::clone(&self.files)
+    }
+
+    /// Returns a shared handle to the directories created explicitly.
+    #[must_use]
+    pub fn dirs(&self) -> Arc<Mutex<HashSet<PathBuf>>> {
+        Arc::clone(&self.dirs)
+    }
+}
+
+impl FileSystem for MemFileSystem {
+    fn read_to_string(
+        &self,
+        path: &Path,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let files = self.files.lock().unwrap();
+        files.get(path).cloned().ok_or_else(|| format!("File not found: {}", path.display()).into())
+    }
+
+    fn write(
+        &self,
+        path: &Path,
+        contents: &str,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        if self.fail_writes_to.as_deref().is_some_and(|s| path.ends_with(s)) {
+            return Err(format!("injected write failure: {}", path.display()).into());
+        }
+        let mut files = self.files.lock().unwrap();
+        files.insert(path.to_path_buf(), contents.to_string());
+        Ok(())
+    }
+
+    fn create_dir_all(&self, path: &Path) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        if self.files.lock().unwrap().keys().any(|f| path.starts_with(f)) {
+            return Err(format!("Not a directory: {}", path.display()).into());
+        }
+        let mut dirs = self.dirs.lock().unwrap();
+        dirs.extend(path.ancestors().filter(|a| !a.as_os_str().is_empty()).map(Path::to_path_buf));
+        Ok(())
+    }
+
+    fn rename(
+        &self,
+        from: &Path,
+        to: &Path,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let mut files = self.files.lock().unwrap();
+        let contents =
+            files.remove(from).ok_or_else(|| format!("File not found: {}", from.display()))?;
+        files.insert(to.to_path_buf(), contents);
+        Ok(())
+    }
+
+    fn remove_file(&self, path: &Path) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let mut files = self.files.lock().unwrap();
+        files
+            .remove(path)
+            .map(|_| ())
+            .ok_or_else(|| format!("File not found: {}", path.display()).into())
+    }
+
+    fn remove_dir(&self, path: &Path) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        // Only refuse non-empty directories.
+        let files = self.files.lock().unwrap();
+        if files.keys().any(|k| k.starts_with(path)) {
+            return Err(format!("Directory not empty: {}", path.display()).into());
+        }
+        self.dirs.lock().unwrap().remove(path);
+        Ok(())
+    }
+
+    fn metadata(
+        &self,
+        path: &Path,
+    ) -> Result<FileMetadata, Box<dyn std::error::Error + Send + Sync>> {
+        let len = self.files.lock().unwrap().get(path).map(String::len);
+        let is_dir = len.is_none() && self.exists(path);
+        if len.is_none() && !is_dir {
+            return Err(format!("File not found: {}", path.display()).into());
+        }
+        Ok(FileMetadata {
+            len: len.unwrap_or_default() as u64,
+            modified: chrono::Utc::now(),
+            is_dir,
+        })
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        let files = self.files.lock().unwrap();
+        // Check exact path or if any file is "under" this directory.
+        files.contains_key(path)
+            || files.keys().any(|k| k.starts_with(path) && k != path)
+            || self.dirs.lock().unwrap().contains(path)
+    }
+
+    fn list_dir(
+        &self,
+        path: &Path,
+    ) -> Result<Vec<String>, Box<dyn std::error::Error + Send + Sync>> {
+        // Files directly in `path`, plus the first component of anything deeper.
+        let files = self.files.lock().unwrap();
+        let dirs = self.dirs.lock().unwrap();
+        let names: BTreeSet<String> = files
+            .keys()
+            .chain(dirs.iter())
+            .filter_map(|k| k.strip_prefix(path).ok()?.components().next())
+            .map(|c| c.as_os_str().to_string_lossy().into_owned())
+            .collect();
+        Ok(names.into_iter().collect())
+    }
+}
+
+impl AsyncFileSystem for MemFileSystem {
+    fn read_to_string<'a>(&'a self, path: &'a Path) -> FsFuture<'a, String> {
+        Box::pin(async move { FileSystem::read_to_string(self, path) })
+    }
+
+    fn write<'a>(&'a self, path: &'a Path, contents: &'a str) -> FsFuture<'a, ()> {
+        Box::pin(async move { FileSystem::write(self, path, contents) })
+    }
+
+    fn create_dir_all<'a>(&'a self, path: &'a Path) -> FsFuture<'a, ()> {
+        Box::pin(async move { FileSystem::create_dir_all(self, path) })
+    }
+
+    fn list_dir<'a>(&'a self, path: &'a Path) -> FsFuture<'a, Vec<String>> {
+        Box::pin(async move { FileSystem::list_dir(self, path) })
+    }
+}
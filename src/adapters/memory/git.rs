@@ -0,0 +1,56 @@
+//! No-op git repository for tests.
+
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+
+use crate::ports::GitRepo;
+
+/// Git repository with a fixed HEAD, a clean working tree, no files, and no remotes.
+pub struct NoopGitRepo;
+
+impl GitRepo for NoopGitRepo {
+    fn current_commit(&self) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        Ok("0000000".to_string())
+    }
+
+    fn diff(&self) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(String::new())
+    }
+
+    fn last_modified(
+        &self,
+        path: &Path,
+    ) -> Result<(DateTime<Utc>, String), Box<dyn std::error::Error + Send + Sync>> {
+        Err(format!("no commit touches {}", path.display()).into())
+    }
+
+    fn list_files(
+        &self,
+        _path: &Path,
+    ) -> Result<Vec<String>, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(vec![])
+    }
+
+    fn fetch_remote_commit(
+        &self,
+        remote_ref: &str,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        Err(format!("no remote ref {remote_ref}").into())
+    }
+
+    fn list_files_at(
+        &self,
+        _commit: &str,
+    ) -> Result<Vec<String>, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(vec![])
+    }
+
+    fn read_file_at(
+        &self,
+        commit: &str,
+        path: &Path,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        Err(format!("no file {} at {commit}", path.display()).into())
+    }
+}
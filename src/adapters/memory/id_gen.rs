@@ -0,0 +1,17 @@
+//! Sequential ID generator for tests.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::ports::IdGenerator;
+
+/// ID generator producing `test-id-0`, `test-id-1`, and so on.
+#[derive(Default)]
+pub struct SequentialIdGenerator {
+    next: AtomicUsize,
+}
+
+impl IdGenerator for SequentialIdGenerator {
+    fn generate_id(&self) -> String {
+        format!("test-id-{}", self.next.fetch_add(1, Ordering::SeqCst))
+    }
+}
@@ -0,0 +1,112 @@
+//! In-memory issue tracker for tests.
+
+use std::sync::Mutex;
+
+use crate::ports::{Issue, IssueStatus, IssueTracker};
+
+/// Issue tracker that keeps issues in memory, numbering them from `MEM-1`.
+#[derive(Default)]
+pub struct MemIssueTracker {
+    issues: Mutex<Vec<Issue>>,
+    without_labels: bool,
+}
+
+impl MemIssueTracker {
+    /// A tracker that reports no label support.
+    ///
+    /// It fails any call that passes labels, so tests catch callers that
+    /// send them anyway.
+    #[must_use]
+    pub fn without_labels() -> Self {
+        Self { without_labels: true, ..Self::default() }
+    }
+
+    fn check_labels(&self, labels: &[String]) -> Result<(), String> {
+        if self.without_labels && !labels.is_empty() {
+            return Err(format!("tracker does not support labels: {}", labels.join(", ")));
+        }
+        Ok(())
+    }
+}
+
+impl IssueTracker for MemIssueTracker {
+    fn supports_labels(&self) -> bool {
+        !self.without_labels
+    }
+
+    fn create_issue(
+        &self,
+        title: &str,
+        body: &str,
+        labels: &[String],
+    ) -> Result<Issue, Box<dyn std::error::Error + Send + Sync>> {
+        self.check_labels(labels)?;
+        let mut issues = self.issues.lock().unwrap();
+        let issue = Issue {
+            id: format!("MEM-{}", issues.len() + 1),
+            title: title.to_string(),
+            body: body.to_string(),
+            status: "open".to_string(),
+            labels: labels.to_vec(),
+        };
+        issues.push(issue.clone());
+        Ok(issue)
+    }
+
+    fn update_issue(
+        &self,
+        id: &str,
+        title: Option<&str>,
+        body: Option<&str>,
+        status: Option<IssueStatus>,
+        labels: &[String],
+    ) -> Result<Issue, Box<dyn std::error::Error + Send + Sync>> {
+        self.check_labels(labels)?;
+        let mut issues = self.issues.lock().unwrap();
+        let issue = issues
+            .iter_mut()
+            .find(|issue| issue.id == id)
+            .ok_or_else(|| format!("issue not found: {id}"))?;
+        if let Some(title) = title {
+            issue.title = title.to_string();
+        }
+        if let Some(body) = body {
+            issue.body = body.to_string();
+        }
+        if let Some(status) = status {
+            issue.status = match status {
+                IssueStatus::Open => "open",
+                IssueStatus::InProgress => "in_progress",
+                IssueStatus::Closed => "closed",
+            }
+            .to_string();
+        }
+        for label in labels {
+            if !issue.labels.contains(label) {
+                issue.labels.push(label.clone());
+            }
+        }
+        Ok(issue.clone())
+    }
+
+    fn list_issues(
+        &self,
+        status: Option<&str>,
+    ) -> Result<Vec<Issue>, Box<dyn std::error::Error + Send + Sync>> {
+        let issues = self.issues.lock().unwrap();
+        Ok(issues
+            .iter()
+            .filter(|issue| status.is_none_or(|s| issue.status == s))
+            .cloned()
+            .collect())
+    }
+
+    fn get_issue(&self, id: &str) -> Result<Issue, Box<dyn std::error::Error + Send + Sync>> {
+        let issues = self.issues.lock().unwrap();
+        issues
+            .iter()
+            .find(|issue| issue.id == id)
+            .cloned()
+            .ok_or_else(|| format!("issue not found: {id}").into())
+    }
+}
@@ -0,0 +1,15 @@
+//! No-op LLM client for tests.
+
+use crate::ports::{CompletionFuture, CompletionRequest, LlmClient};
+
+/// LLM client that rejects every completion.
+///
+/// Tests that exercise the LLM should swap in a client with canned
+/// responses; failing loudly here keeps accidental calls from passing.
+pub struct NoopLlmClient;
+
+impl LlmClient for NoopLlmClient {
+    fn complete(&self, _request: &CompletionRequest) -> CompletionFuture<'_> {
+        Box::pin(async { Err("no LLM client configured in the testing context".into()) })
+    }
+}
@@ -0,0 +1,14 @@
+//! In-memory and no-op adapters for tests.
+//!
+//! These back [`ServiceContext::testing`](crate::context::ServiceContext::testing):
+//! every port does something harmless and deterministic, so tests only need
+//! to swap in the adapters they actually exercise.
+
+pub mod clock;
+pub mod env;
+pub mod filesystem;
+pub mod git;
+pub mod id_gen;
+pub mod issues;
+pub mod llm;
+pub mod shell;
@@ -0,0 +1,12 @@
+//! No-op shell executor for tests.
+
+use crate::ports::{ShellExecutor, ShellOutput};
+
+/// Shell executor where every command succeeds with no output.
+pub struct NoopShellExecutor;
+
+impl ShellExecutor for NoopShellExecutor {
+    fn run(&self, _command: &str) -> Result<ShellOutput, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(ShellOutput { exit_code: 0, stdout: String::new(), stderr: String::new() })
+    }
+}
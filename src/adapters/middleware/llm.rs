@@ -0,0 +1,286 @@
+//! Hook-based and caching middleware for the `LlmClient` port.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use crate::ports::{
+    CompletionFuture, CompletionRequest, CompletionResponse, EmbeddingFuture, LlmClient,
+};
+
+/// Hook invoked with each request before it is sent to the inner client.
+pub type BeforeHook = Box<dyn Fn(&CompletionRequest) + Send + Sync>;
+
+/// Hook invoked with each successful response returned by the inner client.
+pub type AfterHook = Box<dyn Fn(&CompletionResponse) + Send + Sync>;
+
+/// Wraps an inner LLM client, running `before`/`after` hooks around each completion.
+///
+/// Use this to compose cross-cutting concerns (prompt logging, token
+/// accounting, redaction auditing) without touching each call site.
+pub struct HookedLlmClient {
+    inner: Box<dyn LlmClient>,
+    before: Vec<BeforeHook>,
+    after: Vec<AfterHook>,
+}
+
+impl HookedLlmClient {
+    /// Start building a hooked client around the given implementation.
+    #[must_use]
+    pub fn builder(inner: Box<dyn LlmClient>) -> HookedLlmClientBuilder {
+        HookedLlmClientBuilder { inner, before: Vec::new(), after: Vec::new() }
+    }
+}
+
+impl LlmClient for HookedLlmClient {
+    fn complete(&self, request: &CompletionRequest) -> CompletionFuture<'_> {
+        for hook in &self.before {
+            hook(request);
+        }
+        let request = request.clone();
+
+        Box::pin(async move {
+            let result = self.inner.complete(&request).await;
+            if let Ok(response) = &result {
+                for hook in &self.after {
+                    hook(response);
+                }
+            }
+            result
+        })
+    }
+
+    fn embed(&self, texts: &[String]) -> EmbeddingFuture<'_> {
+        self.inner.embed(texts)
+    }
+}
+
+/// Builder for [`HookedLlmClient`].
+pub struct HookedLlmClientBuilder {
+    inner: Box<dyn LlmClient>,
+    before: Vec<BeforeHook>,
+    after: Vec<AfterHook>,
+}
+
+impl HookedLlmClientBuilder {
+    /// Add a hook that runs before each request. Hooks run in insertion order.
+    #[must_use]
+    pub fn before(mut self, hook: impl Fn(&CompletionRequest) + Send + Sync + 'static) -> Self {
+        self.before.push(Box::new(hook));
+        self
+    }
+
+    /// Add a hook that runs after each successful response. Hooks run in insertion order.
+    #[must_use]
+    pub fn after(mut self, hook: impl Fn(&CompletionResponse) + Send + Sync + 'static) -> Self {
+        self.after.push(Box::new(hook));
+        self
+    }
+
+    /// Finish building the hooked client.
+    #[must_use]
+    pub fn build(self) -> HookedLlmClient {
+        HookedLlmClient { inner: self.inner, before: self.before, after: self.after }
+    }
+}
+
+/// Wraps an inner LLM client, reusing earlier responses to identical requests.
+///
+/// Direct calls are keyed on the request alone. Planning passes embed
+/// codebase state in their prompts, so they should go through
+/// [`at_commit`](Self::at_commit) with the `CodebaseMap::commit_hash` they
+/// analyzed: entries cached at one commit then miss once the codebase moves.
+/// Only successful responses are cached; [`entries`](Self::entries) and
+/// [`with_entries`](Self::with_entries) let callers persist them across runs.
+pub struct CachingLlmClient<'a> {
+    inner: Box<dyn LlmClient + 'a>,
+    entries: Mutex<HashMap<String, CompletionResponse>>,
+}
+
+impl<'a> CachingLlmClient<'a> {
+    /// Wrap `inner` with an empty cache.
+    #[must_use]
+    pub fn new(inner: Box<dyn LlmClient + 'a>) -> Self {
+        Self::with_entries(inner, HashMap::new())
+    }
+
+    /// Wrap `inner`, starting from `entries` saved from an earlier client.
+    #[must_use]
+    pub fn with_entries(
+        inner: Box<dyn LlmClient + 'a>,
+        entries: HashMap<String, CompletionResponse>,
+    ) -> Self {
+        Self { inner, entries: Mutex::new(entries) }
+    }
+
+    /// A copy of every cached response, keyed as the cache keys them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a thread panicked while holding the cache lock.
+    #[must_use]
+    pub fn entries(&self) -> HashMap<String, CompletionResponse> {
+        self.entries.lock().unwrap().clone()
+    }
+
+    /// A view of this client whose cache keys also include `commit`.
+    #[must_use]
+    pub fn at_commit(&self, commit: &str) -> CommitScopedLlmClient<'_> {
+        CommitScopedLlmClient { client: self, commit: commit.to_string() }
+    }
+
+    fn complete_keyed(
+        &self,
+        commit: Option<&str>,
+        request: &CompletionRequest,
+    ) -> CompletionFuture<'_> {
+        let key = serde_json::json!({
+            "commit": commit,
+            "model": request.model,
+            "max_tokens": request.max_tokens,
+            "prompt": request.prompt,
+        })
+        .to_string();
+        let request = request.clone();
+
+        Box::pin(async move {
+            if let Some(hit) = self.entries.lock().unwrap().get(&key) {
+                return Ok(hit.clone());
+            }
+            let response = self.inner.complete(&request).await?;
+            self.entries.lock().unwrap().insert(key, response.clone());
+            Ok(response)
+        })
+    }
+}
+
+impl LlmClient for CachingLlmClient<'_> {
+    fn complete(&self, request: &CompletionRequest) -> CompletionFuture<'_> {
+        self.complete_keyed(None, request)
+    }
+
+    fn embed(&self, texts: &[String]) -> EmbeddingFuture<'_> {
+        self.inner.embed(texts)
+    }
+}
+
+/// A [`CachingLlmClient`] whose cache keys are scoped to one codebase commit.
+pub struct CommitScopedLlmClient<'a> {
+    client: &'a CachingLlmClient<'a>,
+    commit: String,
+}
+
+impl LlmClient for CommitScopedLlmClient<'_> {
+    fn complete(&self, request: &CompletionRequest) -> CompletionFuture<'_> {
+        self.client.complete_keyed(Some(&self.commit), request)
+    }
+
+    fn embed(&self, texts: &[String]) -> EmbeddingFuture<'_> {
+        self.client.embed(texts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::replaying::llm::ReplayingLlmClient;
+    use crate::cassette::format::{Cassette, Interaction};
+    use crate::cassette::replayer::CassetteReplayer;
+    use chrono::Utc;
+    use serde_json::json;
+    use std::sync::{Arc, Mutex};
+
+    fn recorded_client(output: serde_json::Value) -> Box<dyn LlmClient> {
+        let cassette = Cassette {
+            name: "hooks".into(),
+            recorded_at: Utc::now(),
+            commit: "abc".into(),
+            interactions: vec![Interaction {
+                seq: 0,
+                port: "llm".into(),
+                method: "complete".into(),
+                input: json!({"prompt": "hi"}),
+                output,
+            }],
+        };
+        let replayer = Arc::new(Mutex::new(CassetteReplayer::new(&cassette)));
+        Box::new(ReplayingLlmClient::new(replayer))
+    }
+
+    fn request() -> CompletionRequest {
+        CompletionRequest {
+            model: "claude-sonnet-4-20250514".into(),
+            prompt: "hi".into(),
+            max_tokens: 16,
+        }
+    }
+
+    #[tokio::test]
+    async fn hooks_fire_around_recorded_completion() {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let before_events = Arc::clone(&events);
+        let after_events = Arc::clone(&events);
+
+        let client = HookedLlmClient::builder(recorded_client(json!({
+            "Ok": {"text": "hello", "prompt_tokens": 3, "completion_tokens": 5}
+        })))
+        .before(move |req| before_events.lock().unwrap().push(format!("before:{}", req.prompt)))
+        .after(move |resp| {
+            after_events
+                .lock()
+                .unwrap()
+                .push(format!("after:{}:{}", resp.text, resp.completion_tokens));
+        })
+        .build();
+
+        let response = client.complete(&request()).await.unwrap();
+
+        assert_eq!(response.text, "hello");
+        assert_eq!(*events.lock().unwrap(), vec!["before:hi", "after:hello:5"]);
+    }
+
+    #[tokio::test]
+    async fn after_hook_skipped_on_error() {
+        let after_calls = Arc::new(Mutex::new(0));
+        let counter = Arc::clone(&after_calls);
+
+        let client = HookedLlmClient::builder(recorded_client(json!({"Err": "rate limited"})))
+            .after(move |_| *counter.lock().unwrap() += 1)
+            .build();
+
+        let result = client.complete(&request()).await;
+
+        assert!(result.is_err());
+        assert_eq!(*after_calls.lock().unwrap(), 0);
+    }
+
+    /// LLM client that answers every request and counts how often it is called.
+    struct CountingLlm {
+        calls: Arc<Mutex<usize>>,
+    }
+
+    impl LlmClient for CountingLlm {
+        fn complete(&self, _request: &CompletionRequest) -> CompletionFuture<'_> {
+            *self.calls.lock().unwrap() += 1;
+            Box::pin(async {
+                Ok(CompletionResponse { text: "ok".into(), prompt_tokens: 1, completion_tokens: 1 })
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn cache_keys_planning_calls_on_commit() {
+        let calls = Arc::new(Mutex::new(0));
+        let client = CachingLlmClient::new(Box::new(CountingLlm { calls: Arc::clone(&calls) }));
+
+        client.at_commit("abc123").complete(&request()).await.unwrap();
+        client.at_commit("abc123").complete(&request()).await.unwrap();
+        assert_eq!(*calls.lock().unwrap(), 1, "identical commit should hit the cache");
+
+        client.at_commit("def456").complete(&request()).await.unwrap();
+        assert_eq!(*calls.lock().unwrap(), 2, "a new commit should miss the cache");
+
+        client.complete(&request()).await.unwrap();
+        client.complete(&request()).await.unwrap();
+        assert_eq!(*calls.lock().unwrap(), 3, "unscoped calls key on the request alone");
+    }
+}
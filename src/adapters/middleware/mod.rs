@@ -0,0 +1,3 @@
+//! Middleware adapters that decorate port implementations with hooks.
+
+pub mod llm;
@@ -1,5 +1,8 @@
 //! Adapter implementations for port traits.
 
 pub mod live;
+#[cfg(test)]
+pub mod memory;
+pub mod middleware;
 pub mod recording;
 pub mod replaying;
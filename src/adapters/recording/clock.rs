@@ -27,6 +27,16 @@ impl Clock for RecordingClock {
         record_interaction(&self.recorder, "clock", "now", &(), &result);
         result
     }
+
+    fn monotonic(&self) -> std::time::Duration {
+        let result = self.inner.monotonic();
+        record_interaction(&self.recorder, "clock", "monotonic", &(), &result);
+        result
+    }
+
+    fn sleep(&self, duration: std::time::Duration) {
+        self.inner.sleep(duration);
+    }
 }
 
 #[cfg(test)]
@@ -40,6 +50,10 @@ mod tests {
         fn now(&self) -> DateTime<Utc> {
             Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap()
         }
+
+        fn monotonic(&self) -> std::time::Duration {
+            std::time::Duration::ZERO
+        }
     }
 
     #[test]
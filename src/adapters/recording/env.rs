@@ -0,0 +1,34 @@
+//! Recording adapter for the `Environment` port.
+
+use std::sync::{Arc, Mutex};
+
+use super::record_interaction;
+use crate::cassette::recorder::CassetteRecorder;
+use crate::ports::Environment;
+
+/// Records environment lookups while delegating to an inner implementation.
+pub struct RecordingEnvironment {
+    inner: Box<dyn Environment>,
+    recorder: Arc<Mutex<CassetteRecorder>>,
+}
+
+impl RecordingEnvironment {
+    /// Creates a new recording environment wrapping the given implementation.
+    pub fn new(inner: Box<dyn Environment>, recorder: Arc<Mutex<CassetteRecorder>>) -> Self {
+        Self { inner, recorder }
+    }
+}
+
+impl Environment for RecordingEnvironment {
+    fn var(&self, name: &str) -> Option<String> {
+        let result = self.inner.var(name);
+        record_interaction(
+            &self.recorder,
+            "env",
+            "var",
+            &serde_json::json!({ "name": name }),
+            &result,
+        );
+        result
+    }
+}
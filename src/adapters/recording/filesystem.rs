@@ -7,7 +7,7 @@ use serde::Serialize;
 
 use super::{record_interaction, record_result};
 use crate::cassette::recorder::CassetteRecorder;
-use crate::ports::FileSystem;
+use crate::ports::{AsyncFileSystem, FileMetadata, FileSystem, FsFuture};
 
 /// Records filesystem interactions while delegating to an inner implementation.
 pub struct RecordingFileSystem {
@@ -27,6 +27,12 @@ struct PathInput<'a> {
     path: &'a str,
 }
 
+#[derive(Serialize)]
+struct RenameInput<'a> {
+    from: &'a str,
+    to: &'a str,
+}
+
 #[derive(Serialize)]
 struct WriteInput<'a> {
     path: &'a str,
@@ -55,6 +61,49 @@ impl FileSystem for RecordingFileSystem {
         result
     }
 
+    fn create_dir_all(&self, path: &Path) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let result = self.inner.create_dir_all(path);
+        let input = PathInput { path: &path.display().to_string() };
+        record_result(&self.recorder, "fs", "create_dir_all", &input, &result);
+        result
+    }
+
+    fn rename(
+        &self,
+        from: &Path,
+        to: &Path,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let result = self.inner.rename(from, to);
+        let input =
+            RenameInput { from: &from.display().to_string(), to: &to.display().to_string() };
+        record_result(&self.recorder, "fs", "rename", &input, &result);
+        result
+    }
+
+    fn remove_file(&self, path: &Path) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let result = self.inner.remove_file(path);
+        let input = PathInput { path: &path.display().to_string() };
+        record_result(&self.recorder, "fs", "remove_file", &input, &result);
+        result
+    }
+
+    fn remove_dir(&self, path: &Path) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let result = self.inner.remove_dir(path);
+        let input = PathInput { path: &path.display().to_string() };
+        record_result(&self.recorder, "fs", "remove_dir", &input, &result);
+        result
+    }
+
+    fn metadata(
+        &self,
+        path: &Path,
+    ) -> Result<FileMetadata, Box<dyn std::error::Error + Send + Sync>> {
+        let result = self.inner.metadata(path);
+        let input = PathInput { path: &path.display().to_string() };
+        record_result(&self.recorder, "fs", "metadata", &input, &result);
+        result
+    }
+
     fn exists(&self, path: &Path) -> bool {
         let result = self.inner.exists(path);
         let input = PathInput { path: &path.display().to_string() };
@@ -73,6 +122,60 @@ impl FileSystem for RecordingFileSystem {
     }
 }
 
+/// Records asynchronous filesystem interactions under the `fs` port.
+///
+/// Inputs and outputs match [`RecordingFileSystem`], so the recorded
+/// interactions are interchangeable with synchronous ones.
+pub struct RecordingAsyncFileSystem {
+    inner: Box<dyn AsyncFileSystem>,
+    recorder: Arc<Mutex<CassetteRecorder>>,
+}
+
+impl RecordingAsyncFileSystem {
+    /// Creates a new recording async filesystem wrapping the given implementation.
+    pub fn new(inner: Box<dyn AsyncFileSystem>, recorder: Arc<Mutex<CassetteRecorder>>) -> Self {
+        Self { inner, recorder }
+    }
+}
+
+impl AsyncFileSystem for RecordingAsyncFileSystem {
+    fn read_to_string<'a>(&'a self, path: &'a Path) -> FsFuture<'a, String> {
+        Box::pin(async move {
+            let result = self.inner.read_to_string(path).await;
+            let input = PathInput { path: &path.display().to_string() };
+            record_result(&self.recorder, "fs", "read_to_string", &input, &result);
+            result
+        })
+    }
+
+    fn write<'a>(&'a self, path: &'a Path, contents: &'a str) -> FsFuture<'a, ()> {
+        Box::pin(async move {
+            let result = self.inner.write(path, contents).await;
+            let input = WriteInput { path: &path.display().to_string(), contents };
+            record_result(&self.recorder, "fs", "write", &input, &result);
+            result
+        })
+    }
+
+    fn create_dir_all<'a>(&'a self, path: &'a Path) -> FsFuture<'a, ()> {
+        Box::pin(async move {
+            let result = self.inner.create_dir_all(path).await;
+            let input = PathInput { path: &path.display().to_string() };
+            record_result(&self.recorder, "fs", "create_dir_all", &input, &result);
+            result
+        })
+    }
+
+    fn list_dir<'a>(&'a self, path: &'a Path) -> FsFuture<'a, Vec<String>> {
+        Box::pin(async move {
+            let result = self.inner.list_dir(path).await;
+            let input = PathInput { path: &path.display().to_string() };
+            record_result(&self.recorder, "fs", "list_dir", &input, &result);
+            result
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -95,6 +198,39 @@ mod tests {
             Ok(())
         }
 
+        fn rename(
+            &self,
+            _from: &Path,
+            _to: &Path,
+        ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+            Ok(())
+        }
+
+        fn remove_file(
+            &self,
+            _path: &Path,
+        ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+            Ok(())
+        }
+
+        fn create_dir_all(
+            &self,
+            _path: &Path,
+        ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+            Ok(())
+        }
+
+        fn remove_dir(&self, _path: &Path) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+            Ok(())
+        }
+
+        fn metadata(
+            &self,
+            _path: &Path,
+        ) -> Result<FileMetadata, Box<dyn std::error::Error + Send + Sync>> {
+            Ok(FileMetadata { len: 12, modified: chrono::Utc::now(), is_dir: false })
+        }
+
         fn exists(&self, _path: &Path) -> bool {
             true
         }
@@ -3,6 +3,7 @@
 use std::path::Path;
 use std::sync::{Arc, Mutex};
 
+use chrono::{DateTime, Utc};
 use serde::Serialize;
 
 use super::record_result;
@@ -27,6 +28,22 @@ struct PathInput<'a> {
     path: &'a str,
 }
 
+#[derive(Serialize)]
+struct RefInput<'a> {
+    reference: &'a str,
+}
+
+#[derive(Serialize)]
+struct CommitInput<'a> {
+    commit: &'a str,
+}
+
+#[derive(Serialize)]
+struct CommitPathInput<'a> {
+    commit: &'a str,
+    path: &'a str,
+}
+
 impl GitRepo for RecordingGitRepo {
     fn current_commit(&self) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
         let result = self.inner.current_commit();
@@ -40,6 +57,16 @@ impl GitRepo for RecordingGitRepo {
         result
     }
 
+    fn last_modified(
+        &self,
+        path: &Path,
+    ) -> Result<(DateTime<Utc>, String), Box<dyn std::error::Error + Send + Sync>> {
+        let result = self.inner.last_modified(path);
+        let input = PathInput { path: &path.display().to_string() };
+        record_result(&self.recorder, "git", "last_modified", &input, &result);
+        result
+    }
+
     fn list_files(
         &self,
         path: &Path,
@@ -49,6 +76,36 @@ impl GitRepo for RecordingGitRepo {
         record_result(&self.recorder, "git", "list_files", &input, &result);
         result
     }
+
+    fn fetch_remote_commit(
+        &self,
+        remote_ref: &str,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let result = self.inner.fetch_remote_commit(remote_ref);
+        let input = RefInput { reference: remote_ref };
+        record_result(&self.recorder, "git", "fetch_remote_commit", &input, &result);
+        result
+    }
+
+    fn list_files_at(
+        &self,
+        commit: &str,
+    ) -> Result<Vec<String>, Box<dyn std::error::Error + Send + Sync>> {
+        let result = self.inner.list_files_at(commit);
+        record_result(&self.recorder, "git", "list_files_at", &CommitInput { commit }, &result);
+        result
+    }
+
+    fn read_file_at(
+        &self,
+        commit: &str,
+        path: &Path,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let result = self.inner.read_file_at(commit, path);
+        let input = CommitPathInput { commit, path: &path.display().to_string() };
+        record_result(&self.recorder, "git", "read_file_at", &input, &result);
+        result
+    }
 }
 
 #[cfg(test)]
@@ -66,12 +123,41 @@ mod tests {
             Ok(String::new())
         }
 
+        fn last_modified(
+            &self,
+            _path: &Path,
+        ) -> Result<(DateTime<Utc>, String), Box<dyn std::error::Error + Send + Sync>> {
+            Ok((Utc::now(), "Fake Author".into()))
+        }
+
         fn list_files(
             &self,
             _path: &Path,
         ) -> Result<Vec<String>, Box<dyn std::error::Error + Send + Sync>> {
             Ok(vec!["file.rs".into()])
         }
+
+        fn fetch_remote_commit(
+            &self,
+            _remote_ref: &str,
+        ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+            Ok("def456".into())
+        }
+
+        fn list_files_at(
+            &self,
+            _commit: &str,
+        ) -> Result<Vec<String>, Box<dyn std::error::Error + Send + Sync>> {
+            Ok(vec!["file.rs".into()])
+        }
+
+        fn read_file_at(
+            &self,
+            _commit: &str,
+            _path: &Path,
+        ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+            Ok("fn main() {}\n".into())
+        }
     }
 
     #[test]
@@ -6,7 +6,7 @@ use serde::Serialize;
 
 use super::record_result;
 use crate::cassette::recorder::CassetteRecorder;
-use crate::ports::{Issue, IssueTracker};
+use crate::ports::{Issue, IssueStatus, IssueTracker};
 
 /// Records issue tracker interactions while delegating to an inner implementation.
 pub struct RecordingIssueTracker {
@@ -25,6 +25,8 @@ impl RecordingIssueTracker {
 struct CreateIssueInput<'a> {
     title: &'a str,
     body: &'a str,
+    #[serde(skip_serializing_if = "<[String]>::is_empty")]
+    labels: &'a [String],
 }
 
 #[derive(Serialize)]
@@ -32,7 +34,9 @@ struct UpdateIssueInput<'a> {
     id: &'a str,
     title: Option<&'a str>,
     body: Option<&'a str>,
-    status: Option<&'a str>,
+    status: Option<IssueStatus>,
+    #[serde(skip_serializing_if = "<[String]>::is_empty")]
+    labels: &'a [String],
 }
 
 #[derive(Serialize)]
@@ -46,13 +50,18 @@ struct GetIssueInput<'a> {
 }
 
 impl IssueTracker for RecordingIssueTracker {
+    fn supports_labels(&self) -> bool {
+        self.inner.supports_labels()
+    }
+
     fn create_issue(
         &self,
         title: &str,
         body: &str,
+        labels: &[String],
     ) -> Result<Issue, Box<dyn std::error::Error + Send + Sync>> {
-        let result = self.inner.create_issue(title, body);
-        let input = CreateIssueInput { title, body };
+        let result = self.inner.create_issue(title, body, labels);
+        let input = CreateIssueInput { title, body, labels };
         record_result(&self.recorder, "issues", "create_issue", &input, &result);
         result
     }
@@ -62,10 +71,11 @@ impl IssueTracker for RecordingIssueTracker {
         id: &str,
         title: Option<&str>,
         body: Option<&str>,
-        status: Option<&str>,
+        status: Option<IssueStatus>,
+        labels: &[String],
     ) -> Result<Issue, Box<dyn std::error::Error + Send + Sync>> {
-        let result = self.inner.update_issue(id, title, body, status);
-        let input = UpdateIssueInput { id, title, body, status };
+        let result = self.inner.update_issue(id, title, body, status, labels);
+        let input = UpdateIssueInput { id, title, body, status, labels };
         record_result(&self.recorder, "issues", "update_issue", &input, &result);
         result
     }
@@ -99,12 +109,14 @@ mod tests {
             &self,
             title: &str,
             body: &str,
+            labels: &[String],
         ) -> Result<Issue, Box<dyn std::error::Error + Send + Sync>> {
             Ok(Issue {
                 id: "fake-1".into(),
                 title: title.into(),
                 body: body.into(),
                 status: "open".into(),
+                labels: labels.to_vec(),
             })
         }
 
@@ -113,13 +125,15 @@ mod tests {
             id: &str,
             title: Option<&str>,
             _body: Option<&str>,
-            _status: Option<&str>,
+            _status: Option<IssueStatus>,
+            _labels: &[String],
         ) -> Result<Issue, Box<dyn std::error::Error + Send + Sync>> {
             Ok(Issue {
                 id: id.into(),
                 title: title.unwrap_or("updated").into(),
                 body: String::new(),
                 status: "open".into(),
+                labels: vec![],
             })
         }
 
@@ -136,6 +150,7 @@ mod tests {
                 title: "Fake issue".into(),
                 body: String::new(),
                 status: "open".into(),
+                labels: vec![],
             })
         }
     }
@@ -152,7 +167,7 @@ mod tests {
         {
             let tracker =
                 RecordingIssueTracker::new(Box::new(FakeIssueTracker), Arc::clone(&recorder));
-            let _ = tracker.create_issue("Test Issue", "Test body");
+            let _ = tracker.create_issue("Test Issue", "Test body", &[]);
         }
 
         let recorder = Arc::try_unwrap(recorder).unwrap().into_inner().unwrap();
@@ -165,4 +180,25 @@ mod tests {
 
         let _ = std::fs::remove_dir_all(&dir);
     }
+
+    #[test]
+    fn records_update_issue_status_in_normalized_form() {
+        let dir = std::env::temp_dir().join("speck_rec_issues_status_test");
+        std::fs::create_dir_all(&dir).unwrap();
+        let cassette_path = dir.join("issues.cassette.yaml");
+
+        let recorder = Arc::new(Mutex::new(CassetteRecorder::new(&cassette_path, "test", "abc")));
+        {
+            let tracker =
+                RecordingIssueTracker::new(Box::new(FakeIssueTracker), Arc::clone(&recorder));
+            let _ = tracker.update_issue("fake-1", None, None, Some(IssueStatus::InProgress), &[]);
+        }
+
+        let recorder = Arc::try_unwrap(recorder).unwrap().into_inner().unwrap();
+        recorder.finish().unwrap();
+
+        let content = std::fs::read_to_string(&cassette_path).unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+        assert!(content.contains("status: in_progress"), "{content}");
+    }
 }
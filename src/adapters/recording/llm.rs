@@ -2,9 +2,11 @@
 
 use std::sync::{Arc, Mutex};
 
+use serde::Serialize;
+
 use super::record_result;
 use crate::cassette::recorder::CassetteRecorder;
-use crate::ports::{CompletionFuture, CompletionRequest, LlmClient};
+use crate::ports::{CompletionFuture, CompletionRequest, EmbeddingFuture, LlmClient};
 
 /// Records LLM interactions while delegating to an inner implementation.
 pub struct RecordingLlmClient {
@@ -35,6 +37,20 @@ impl LlmClient for RecordingLlmClient {
             result
         })
     }
+
+    fn embed(&self, texts: &[String]) -> EmbeddingFuture<'_> {
+        let texts = texts.to_vec();
+        Box::pin(async move {
+            let result = self.inner.embed(&texts).await;
+            record_result(&self.recorder, "llm", "embed", &EmbedInput { texts: &texts }, &result);
+            result
+        })
+    }
+}
+
+#[derive(Serialize)]
+struct EmbedInput<'a> {
+    texts: &'a [String],
 }
 
 // Note: Testing the async LlmClient recording is more complex and would
@@ -1,6 +1,7 @@
 //! Recording adapters that capture interactions to cassettes.
 
 pub mod clock;
+pub mod env;
 pub mod filesystem;
 pub mod git;
 pub mod id_gen;
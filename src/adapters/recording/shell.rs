@@ -6,18 +6,29 @@ use serde::Serialize;
 
 use super::record_result;
 use crate::cassette::recorder::CassetteRecorder;
+use crate::cassette::shell_output::{RecordedShellOutput, ShellRecordMode};
 use crate::ports::{ShellExecutor, ShellOutput};
 
 /// Records shell interactions while delegating to an inner implementation.
 pub struct RecordingShellExecutor {
     inner: Box<dyn ShellExecutor>,
     recorder: Arc<Mutex<CassetteRecorder>>,
+    mode: ShellRecordMode,
 }
 
 impl RecordingShellExecutor {
     /// Creates a new recording shell executor wrapping the given implementation.
+    ///
+    /// Output is recorded in full; see [`with_mode`](Self::with_mode).
     pub fn new(inner: Box<dyn ShellExecutor>, recorder: Arc<Mutex<CassetteRecorder>>) -> Self {
-        Self { inner, recorder }
+        Self { inner, recorder, mode: ShellRecordMode::Full }
+    }
+
+    /// Sets how much of each run's output is recorded.
+    #[must_use]
+    pub fn with_mode(mut self, mode: ShellRecordMode) -> Self {
+        self.mode = mode;
+        self
     }
 }
 
@@ -26,11 +37,38 @@ struct CommandInput<'a> {
     command: &'a str,
 }
 
+impl RecordingShellExecutor {
+    /// Records `result` for `command` as a `run` interaction in `mode`.
+    fn record(
+        &self,
+        command: &str,
+        result: &Result<ShellOutput, Box<dyn std::error::Error + Send + Sync>>,
+        mode: ShellRecordMode,
+    ) {
+        let input = CommandInput { command };
+        match mode {
+            ShellRecordMode::Full => record_result(&self.recorder, "shell", "run", &input, result),
+            ShellRecordMode::Minimal => {
+                let minimal = result.as_ref().map(RecordedShellOutput::minimal);
+                record_result(&self.recorder, "shell", "run", &input, &minimal);
+            }
+        }
+    }
+}
+
 impl ShellExecutor for RecordingShellExecutor {
     fn run(&self, command: &str) -> Result<ShellOutput, Box<dyn std::error::Error + Send + Sync>> {
         let result = self.inner.run(command);
-        let input = CommandInput { command };
-        record_result(&self.recorder, "shell", "run", &input, &result);
+        self.record(command, &result, self.mode);
+        result
+    }
+
+    fn run_inspected(
+        &self,
+        command: &str,
+    ) -> Result<ShellOutput, Box<dyn std::error::Error + Send + Sync>> {
+        let result = self.inner.run_inspected(command);
+        self.record(command, &result, ShellRecordMode::Full);
         result
     }
 }
@@ -76,4 +114,63 @@ mod tests {
 
         let _ = std::fs::remove_dir_all(&dir);
     }
+
+    /// Shell that prints a long log and exits 1 for commands containing "fail".
+    struct VerboseShellExecutor;
+
+    impl ShellExecutor for VerboseShellExecutor {
+        fn run(
+            &self,
+            command: &str,
+        ) -> Result<ShellOutput, Box<dyn std::error::Error + Send + Sync>> {
+            let exit_code = i32::from(command.contains("fail"));
+            let stdout =
+                format!("running 500 tests\n{}test result: done\n", "test ok\n".repeat(500));
+            Ok(ShellOutput { exit_code, stdout, stderr: String::new() })
+        }
+    }
+
+    /// Records a passing and a failing run in `mode`, returning the cassette path.
+    fn record_runs(dir: &std::path::Path, mode: ShellRecordMode) -> std::path::PathBuf {
+        let path = dir.join(format!("{mode:?}.cassette.yaml"));
+        let recorder = Arc::new(Mutex::new(CassetteRecorder::new(&path, "test", "abc")));
+        {
+            let shell =
+                RecordingShellExecutor::new(Box::new(VerboseShellExecutor), Arc::clone(&recorder))
+                    .with_mode(mode);
+            shell.run("cargo test").unwrap();
+            shell.run("cargo test fail").unwrap();
+        }
+        let recorder = Arc::try_unwrap(recorder).unwrap().into_inner().unwrap();
+        recorder.finish().unwrap();
+        path
+    }
+
+    #[test]
+    fn minimal_recording_is_smaller_and_replays_exit_codes() {
+        use crate::adapters::replaying::shell::ReplayingShellExecutor;
+        use crate::cassette::config::CassetteConfig;
+
+        let dir = std::env::temp_dir().join("speck_rec_shell_minimal_test");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let full = record_runs(&dir, ShellRecordMode::Full);
+        let minimal = record_runs(&dir, ShellRecordMode::Minimal);
+        let size = |path: &std::path::Path| std::fs::metadata(path).unwrap().len();
+        let (full_size, minimal_size) = (size(&full), size(&minimal));
+
+        let replayer = CassetteConfig::load_monolithic(&minimal).unwrap();
+        let shell = ReplayingShellExecutor::new(Arc::new(Mutex::new(replayer)));
+        let passed = shell.run("cargo test").unwrap();
+        let failed = shell.run("cargo test fail").unwrap();
+
+        let _ = std::fs::remove_dir_all(&dir);
+        assert!(minimal_size * 4 < full_size, "{minimal_size} vs {full_size} bytes");
+        assert_eq!(passed.exit_code, 0);
+        assert_eq!(failed.exit_code, 1);
+        assert!(passed.stdout.starts_with("running 500 tests\n"));
+        assert!(passed.stdout.contains(" bytes elided ...]"));
+        assert!(passed.stdout.ends_with("test result: done\n"));
+    }
 }
@@ -32,4 +32,11 @@ impl Clock for ReplayingClock {
         let output = next_output(self.replayer.as_ref(), "clock", "now");
         serde_json::from_value(output).expect("failed to deserialize clock output from cassette")
     }
+
+    fn monotonic(&self) -> std::time::Duration {
+        let output = next_output(self.replayer.as_ref(), "clock", "monotonic");
+        serde_json::from_value(output).expect("failed to deserialize clock output from cassette")
+    }
+
+    fn sleep(&self, _duration: std::time::Duration) {}
 }
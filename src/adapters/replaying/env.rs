@@ -0,0 +1,33 @@
+//! Replaying adapter for the `Environment` port.
+
+use std::sync::{Arc, Mutex};
+
+use super::next_output;
+use crate::cassette::replayer::CassetteReplayer;
+use crate::ports::Environment;
+
+/// Serves recorded environment lookups from a cassette.
+pub struct ReplayingEnvironment {
+    replayer: Option<Arc<Mutex<CassetteReplayer>>>,
+}
+
+impl ReplayingEnvironment {
+    /// Create a replaying environment backed by the given replayer.
+    #[must_use]
+    pub fn new(replayer: Arc<Mutex<CassetteReplayer>>) -> Self {
+        Self { replayer: Some(replayer) }
+    }
+
+    /// Create a replaying environment with no cassette. Panics when called.
+    #[must_use]
+    pub fn unconfigured() -> Self {
+        Self { replayer: None }
+    }
+}
+
+impl Environment for ReplayingEnvironment {
+    fn var(&self, _name: &str) -> Option<String> {
+        let output = next_output(self.replayer.as_ref(), "env", "var");
+        serde_json::from_value(output).expect("failed to deserialize env output from cassette")
+    }
+}
@@ -5,7 +5,7 @@ use std::sync::{Arc, Mutex};
 
 use super::{next_output, replay_result};
 use crate::cassette::replayer::CassetteReplayer;
-use crate::ports::FileSystem;
+use crate::ports::{AsyncFileSystem, FileMetadata, FileSystem, FsFuture};
 
 /// Serves recorded filesystem results from a cassette.
 pub struct ReplayingFileSystem {
@@ -44,6 +44,38 @@ impl FileSystem for ReplayingFileSystem {
         replay_result(output)
     }
 
+    fn create_dir_all(&self, _path: &Path) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let output = next_output(self.replayer.as_ref(), "fs", "create_dir_all");
+        replay_result(output)
+    }
+
+    fn rename(
+        &self,
+        _from: &Path,
+        _to: &Path,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let output = next_output(self.replayer.as_ref(), "fs", "rename");
+        replay_result(output)
+    }
+
+    fn remove_file(&self, _path: &Path) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let output = next_output(self.replayer.as_ref(), "fs", "remove_file");
+        replay_result(output)
+    }
+
+    fn remove_dir(&self, _path: &Path) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let output = next_output(self.replayer.as_ref(), "fs", "remove_dir");
+        replay_result(output)
+    }
+
+    fn metadata(
+        &self,
+        _path: &Path,
+    ) -> Result<FileMetadata, Box<dyn std::error::Error + Send + Sync>> {
+        let output = next_output(self.replayer.as_ref(), "fs", "metadata");
+        replay_result(output)
+    }
+
     fn exists(&self, _path: &Path) -> bool {
         let output = next_output(self.replayer.as_ref(), "fs", "exists");
         serde_json::from_value(output)
@@ -58,3 +90,63 @@ impl FileSystem for ReplayingFileSystem {
         replay_result(output)
     }
 }
+
+/// Serves recorded filesystem results to [`AsyncFileSystem`] callers.
+///
+/// Concurrent reads complete in no fixed order, so instead of replaying in
+/// recorded order each call takes the first unconsumed `fs` interaction for
+/// its method whose recorded path matches. Interactions recorded without a
+/// path match any call.
+pub struct ReplayingAsyncFileSystem {
+    replayer: Option<Arc<Mutex<CassetteReplayer>>>,
+}
+
+impl ReplayingAsyncFileSystem {
+    /// Create a replaying async filesystem backed by the given replayer.
+    #[must_use]
+    pub fn new(replayer: Arc<Mutex<CassetteReplayer>>) -> Self {
+        Self { replayer: Some(replayer) }
+    }
+
+    /// Create a replaying async filesystem with no cassette. Panics when called.
+    #[must_use]
+    pub fn unconfigured() -> Self {
+        Self { replayer: None }
+    }
+
+    /// Take the recorded output of `method` for `path`.
+    fn output_for(&self, method: &str, path: &Path) -> serde_json::Value {
+        let Some(replayer) = &self.replayer else {
+            return next_output(None, "fs", method);
+        };
+        let path = path.display().to_string();
+        let mut guard = replayer.lock().expect("replayer lock poisoned");
+        guard
+            .take_matching("fs", method, |i| {
+                i.input.get("path").is_none_or(|p| p.as_str() == Some(path.as_str()))
+            })
+            .output
+    }
+}
+
+impl AsyncFileSystem for ReplayingAsyncFileSystem {
+    fn read_to_string<'a>(&'a self, path: &'a Path) -> FsFuture<'a, String> {
+        let output = self.output_for("read_to_string", path);
+        Box::pin(async move { replay_result(output) })
+    }
+
+    fn write<'a>(&'a self, path: &'a Path, _contents: &'a str) -> FsFuture<'a, ()> {
+        let output = self.output_for("write", path);
+        Box::pin(async move { replay_result(output) })
+    }
+
+    fn create_dir_all<'a>(&'a self, path: &'a Path) -> FsFuture<'a, ()> {
+        let output = self.output_for("create_dir_all", path);
+        Box::pin(async move { replay_result(output) })
+    }
+
+    fn list_dir<'a>(&'a self, path: &'a Path) -> FsFuture<'a, Vec<String>> {
+        let output = self.output_for("list_dir", path);
+        Box::pin(async move { replay_result(output) })
+    }
+}
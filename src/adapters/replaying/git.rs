@@ -3,6 +3,8 @@
 use std::path::Path;
 use std::sync::{Arc, Mutex};
 
+use chrono::{DateTime, Utc};
+
 use super::{next_output, replay_result};
 use crate::cassette::replayer::CassetteReplayer;
 use crate::ports::GitRepo;
@@ -37,6 +39,14 @@ impl GitRepo for ReplayingGitRepo {
         replay_result(output)
     }
 
+    fn last_modified(
+        &self,
+        _path: &Path,
+    ) -> Result<(DateTime<Utc>, String), Box<dyn std::error::Error + Send + Sync>> {
+        let output = next_output(self.replayer.as_ref(), "git", "last_modified");
+        replay_result(output)
+    }
+
     fn list_files(
         &self,
         _path: &Path,
@@ -44,4 +54,29 @@ impl GitRepo for ReplayingGitRepo {
         let output = next_output(self.replayer.as_ref(), "git", "list_files");
         replay_result(output)
     }
+
+    fn fetch_remote_commit(
+        &self,
+        _remote_ref: &str,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let output = next_output(self.replayer.as_ref(), "git", "fetch_remote_commit");
+        replay_result(output)
+    }
+
+    fn list_files_at(
+        &self,
+        _commit: &str,
+    ) -> Result<Vec<String>, Box<dyn std::error::Error + Send + Sync>> {
+        let output = next_output(self.replayer.as_ref(), "git", "list_files_at");
+        replay_result(output)
+    }
+
+    fn read_file_at(
+        &self,
+        _commit: &str,
+        _path: &Path,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let output = next_output(self.replayer.as_ref(), "git", "read_file_at");
+        replay_result(output)
+    }
 }
@@ -4,7 +4,7 @@ use std::sync::{Arc, Mutex};
 
 use super::{next_output, replay_result};
 use crate::cassette::replayer::CassetteReplayer;
-use crate::ports::{Issue, IssueTracker};
+use crate::ports::{Issue, IssueStatus, IssueTracker};
 
 /// Serves recorded issue tracker results from a cassette.
 pub struct ReplayingIssueTracker {
@@ -30,6 +30,7 @@ impl IssueTracker for ReplayingIssueTracker {
         &self,
         _title: &str,
         _body: &str,
+        _labels: &[String],
     ) -> Result<Issue, Box<dyn std::error::Error + Send + Sync>> {
         let output = next_output(self.replayer.as_ref(), "issues", "create_issue");
         replay_result(output)
@@ -40,7 +41,8 @@ impl IssueTracker for ReplayingIssueTracker {
         _id: &str,
         _title: Option<&str>,
         _body: Option<&str>,
-        _status: Option<&str>,
+        _status: Option<IssueStatus>,
+        _labels: &[String],
     ) -> Result<Issue, Box<dyn std::error::Error + Send + Sync>> {
         let output = next_output(self.replayer.as_ref(), "issues", "update_issue");
         replay_result(output)
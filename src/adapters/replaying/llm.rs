@@ -3,31 +3,111 @@
 use std::sync::{Arc, Mutex};
 
 use super::{next_output, replay_result};
+use crate::cassette::format::Interaction;
 use crate::cassette::replayer::CassetteReplayer;
-use crate::ports::{CompletionFuture, CompletionRequest, LlmClient};
+use crate::ports::{CompletionFuture, CompletionRequest, EmbeddingFuture, LlmClient};
 
 /// Serves recorded LLM completions from a cassette.
+///
+/// By default completions are served in recorded order. With
+/// [`with_prompt_matching`](Self::with_prompt_matching), each completion is
+/// instead the first unconsumed one whose recorded prompt appears in the
+/// request's prompt, so tests survive reordered LLM calls.
 pub struct ReplayingLlmClient {
     replayer: Option<Arc<Mutex<CassetteReplayer>>>,
+    match_prompts: bool,
 }
 
 impl ReplayingLlmClient {
     /// Create a replaying LLM client backed by the given replayer.
     #[must_use]
     pub fn new(replayer: Arc<Mutex<CassetteReplayer>>) -> Self {
-        Self { replayer: Some(replayer) }
+        Self { replayer: Some(replayer), match_prompts: false }
+    }
+
+    /// Match completions by prompt substring instead of recorded order.
+    #[must_use]
+    pub fn with_prompt_matching(mut self) -> Self {
+        self.match_prompts = true;
+        self
     }
 
     /// Create a replaying LLM client with no cassette. Panics when called.
     #[must_use]
     pub fn unconfigured() -> Self {
-        Self { replayer: None }
+        Self { replayer: None, match_prompts: false }
     }
 }
 
 impl LlmClient for ReplayingLlmClient {
-    fn complete(&self, _request: &CompletionRequest) -> CompletionFuture<'_> {
-        let output = next_output(self.replayer.as_ref(), "llm", "complete");
+    fn complete(&self, request: &CompletionRequest) -> CompletionFuture<'_> {
+        let output = match (&self.replayer, self.match_prompts) {
+            (Some(replayer), true) => {
+                let mut guard = replayer.lock().expect("replayer lock poisoned");
+                guard
+                    .take_matching("llm", "complete", |i| recorded_prompt_in(i, &request.prompt))
+                    .output
+            }
+            _ => next_output(self.replayer.as_ref(), "llm", "complete"),
+        };
         Box::pin(async move { replay_result(output) })
     }
+
+    fn embed(&self, _texts: &[String]) -> EmbeddingFuture<'_> {
+        let output = next_output(self.replayer.as_ref(), "llm", "embed");
+        Box::pin(async move { replay_result(output) })
+    }
+}
+
+/// Whether the interaction's recorded prompt is a substring of `prompt`.
+fn recorded_prompt_in(interaction: &Interaction, prompt: &str) -> bool {
+    interaction
+        .input
+        .get("prompt")
+        .and_then(serde_json::Value::as_str)
+        .is_some_and(|p| prompt.contains(p))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cassette::format::Cassette;
+    use chrono::Utc;
+    use serde_json::json;
+
+    fn completion(seq: u64, prompt: &str, text: &str) -> Interaction {
+        Interaction {
+            seq,
+            port: "llm".into(),
+            method: "complete".into(),
+            input: json!({"model": "m", "prompt": prompt, "max_tokens": 100}),
+            output: json!({"Ok": {"text": text, "prompt_tokens": 1, "completion_tokens": 1}}),
+        }
+    }
+
+    fn request(prompt: &str) -> CompletionRequest {
+        CompletionRequest { model: "m".into(), prompt: prompt.into(), max_tokens: 100 }
+    }
+
+    #[test]
+    fn prompt_matching_replays_completions_out_of_order() {
+        let cassette = Cassette {
+            name: "reordered".into(),
+            recorded_at: Utc::now(),
+            commit: "abc".into(),
+            interactions: vec![
+                completion(0, "Survey the codebase", "survey result"),
+                completion(1, "Classify the requirement", "classify result"),
+            ],
+        };
+        let replayer = Arc::new(Mutex::new(CassetteReplayer::new(&cassette)));
+        let llm = ReplayingLlmClient::new(replayer).with_prompt_matching();
+
+        let rt = tokio::runtime::Builder::new_current_thread().build().unwrap();
+        let classify =
+            rt.block_on(llm.complete(&request("Classify the requirement: add export"))).unwrap();
+        let survey = rt.block_on(llm.complete(&request("Survey the codebase at abc"))).unwrap();
+        assert_eq!(classify.text, "classify result");
+        assert_eq!(survey.text, "survey result");
+    }
 }
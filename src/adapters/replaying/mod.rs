@@ -1,6 +1,7 @@
 //! Replaying adapters that replay recorded interactions from cassettes.
 
 pub mod clock;
+pub mod env;
 pub mod filesystem;
 pub mod git;
 pub mod id_gen;
@@ -4,9 +4,13 @@ use std::sync::{Arc, Mutex};
 
 use super::{next_output, replay_result};
 use crate::cassette::replayer::CassetteReplayer;
+use crate::cassette::shell_output::RecordedShellOutput;
 use crate::ports::{ShellExecutor, ShellOutput};
 
 /// Serves recorded shell execution results from a cassette.
+///
+/// Runs recorded in minimal mode are reconstructed with a marker where
+/// output was elided.
 pub struct ReplayingShellExecutor {
     replayer: Option<Arc<Mutex<CassetteReplayer>>>,
 }
@@ -28,6 +32,6 @@ impl ReplayingShellExecutor {
 impl ShellExecutor for ReplayingShellExecutor {
     fn run(&self, _command: &str) -> Result<ShellOutput, Box<dyn std::error::Error + Send + Sync>> {
         let output = next_output(self.replayer.as_ref(), "shell", "run");
-        replay_result(output)
+        replay_result::<RecordedShellOutput>(output).map(ShellOutput::from)
     }
 }
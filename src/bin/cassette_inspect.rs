@@ -0,0 +1,178 @@
+//! Summarizes a cassette YAML file for debugging replays.
+//!
+//! Usage: `cassette_inspect <file.yaml> [--port <port>]`
+//!
+//! Without `--port`, prints the recording's commit and time, the `seq` range,
+//! and interaction counts per port and per method. With `--port`, lists that
+//! port's interactions in order with a one-line summary of each input.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::path::PathBuf;
+use std::{env, fs, process};
+
+use speck::cassette::format::Cassette;
+
+/// Longest input summary printed per interaction in `--port` mode.
+const MAX_INPUT_CHARS: usize = 100;
+
+/// Aggregate counts for a cassette.
+#[derive(Debug, PartialEq)]
+struct Summary {
+    /// Interactions per port.
+    ports: BTreeMap<String, usize>,
+    /// Interactions per `port.method`.
+    methods: BTreeMap<String, usize>,
+    /// Lowest and highest `seq`, if there are any interactions.
+    seq_range: Option<(u64, u64)>,
+}
+
+fn summarize(cassette: &Cassette) -> Summary {
+    let mut ports = BTreeMap::new();
+    let mut methods = BTreeMap::new();
+    for interaction in &cassette.interactions {
+        *ports.entry(interaction.port.clone()).or_insert(0) += 1;
+        *methods.entry(format!("{}.{}", interaction.port, interaction.method)).or_insert(0) += 1;
+    }
+    let seqs = cassette.interactions.iter().map(|i| i.seq);
+    let seq_range = seqs.clone().min().zip(seqs.max());
+    Summary { ports, methods, seq_range }
+}
+
+fn format_summary(cassette: &Cassette, summary: &Summary) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "Cassette: {}", cassette.name);
+    let _ = writeln!(out, "Commit: {}", cassette.commit);
+    let _ = writeln!(out, "Recorded at: {}", cassette.recorded_at.to_rfc3339());
+    let _ = writeln!(out, "Interactions: {}", cassette.interactions.len());
+    if let Some((first, last)) = summary.seq_range {
+        let _ = writeln!(out, "Seq range: {first}..={last}");
+    }
+    out.push_str("\nPorts:\n");
+    for (port, count) in &summary.ports {
+        let _ = writeln!(out, "  {port:<10} {count}");
+    }
+    out.push_str("\nMethods:\n");
+    for (method, count) in &summary.methods {
+        let _ = writeln!(out, "  {method:<30} {count}");
+    }
+    out
+}
+
+/// Lists `port`'s interactions, numbered from 1 in replay order.
+fn format_port(cassette: &Cassette, port: &str) -> String {
+    let mut out = String::new();
+    let interactions: Vec<_> = cassette.interactions.iter().filter(|i| i.port == port).collect();
+    if interactions.is_empty() {
+        let _ = writeln!(out, "No interactions for port '{port}'.");
+        return out;
+    }
+    for (n, interaction) in interactions.iter().enumerate() {
+        let mut input = interaction.input.to_string();
+        if input.chars().count() > MAX_INPUT_CHARS {
+            input = input.chars().take(MAX_INPUT_CHARS).collect::<String>() + "…";
+        }
+        let _ = writeln!(
+            out,
+            "#{:<3} seq {:<5} {:<20} {input}",
+            n + 1,
+            interaction.seq,
+            interaction.method
+        );
+    }
+    out
+}
+
+fn inspect(input: &str, port: Option<&str>) -> Result<String, String> {
+    let path = PathBuf::from(input);
+    let content =
+        fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+    let cassette: Cassette = serde_yaml::from_str(&content)
+        .map_err(|e| format!("Failed to parse {}: {e}", path.display()))?;
+    Ok(match port {
+        Some(port) => format_port(&cassette, port),
+        None => format_summary(&cassette, &summarize(&cassette)),
+    })
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let port = match args.len() {
+        2 => None,
+        4 if args[2] == "--port" => Some(args[3].as_str()),
+        _ => {
+            eprintln!("Usage: cassette_inspect <file.yaml> [--port <port>]");
+            process::exit(1);
+        }
+    };
+
+    match inspect(&args[1], port) {
+        Ok(report) => print!("{report}"),
+        Err(e) => {
+            eprintln!("Error: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use speck::cassette::format::Interaction;
+
+    fn multi_port_fixture() -> Cassette {
+        let interaction = |seq: u64, port: &str, method: &str, input| Interaction {
+            seq,
+            port: port.into(),
+            method: method.into(),
+            input,
+            output: json!(null),
+        };
+        Cassette {
+            name: "test-session".into(),
+            recorded_at: "2025-06-15T10:00:00Z".parse().unwrap(),
+            commit: "abc123".into(),
+            interactions: vec![
+                interaction(0, "git", "current_commit", json!(null)),
+                interaction(1, "llm", "complete", json!({"prompt": "first"})),
+                interaction(2, "fs", "read_to_string", json!({"path": "/p/a.rs"})),
+                interaction(3, "llm", "complete", json!({"prompt": "second"})),
+                interaction(4, "fs", "write", json!({"path": "/p/b.rs"})),
+                interaction(5, "llm", "complete", json!({"prompt": "x".repeat(200)})),
+            ],
+        }
+    }
+
+    #[test]
+    fn summary_counts_ports_methods_and_seq_range() {
+        let cassette = multi_port_fixture();
+        let summary = summarize(&cassette);
+
+        assert_eq!(summary.ports["llm"], 3);
+        assert_eq!(summary.ports["fs"], 2);
+        assert_eq!(summary.ports["git"], 1);
+        assert_eq!(summary.methods["llm.complete"], 3);
+        assert_eq!(summary.methods["fs.read_to_string"], 1);
+        assert_eq!(summary.methods["fs.write"], 1);
+        assert_eq!(summary.seq_range, Some((0, 5)));
+
+        let report = format_summary(&cassette, &summary);
+        assert!(report.contains("Commit: abc123"), "{report}");
+        assert!(report.contains("Recorded at: 2025-06-15T10:00:00+00:00"), "{report}");
+        assert!(report.contains("Seq range: 0..=5"), "{report}");
+    }
+
+    #[test]
+    fn port_mode_numbers_interactions_in_replay_order() {
+        let cassette = multi_port_fixture();
+        let report = format_port(&cassette, "llm");
+        let lines: Vec<&str> = report.lines().collect();
+
+        assert_eq!(lines.len(), 3);
+        assert!(lines[1].starts_with("#2   seq 3"), "{report}");
+        assert!(lines[1].contains(r#"{"prompt":"second"}"#), "{report}");
+        assert!(lines[2].ends_with('…'), "long inputs are truncated: {report}");
+        assert!(format_port(&cassette, "shell").contains("No interactions"));
+    }
+}
@@ -4,6 +4,7 @@ use std::path::{Path, PathBuf};
 
 use super::format::Cassette;
 use super::replayer::CassetteReplayer;
+use super::sanitize;
 
 /// Per-port cassette file paths. Each port can optionally have its own
 /// cassette file for replay. Ports without a cassette path will panic
@@ -24,6 +25,11 @@ pub struct CassetteConfig {
     pub id_gen: Option<PathBuf>,
     /// Path to the issues port cassette file.
     pub issues: Option<PathBuf>,
+    /// Path to the environment port cassette file.
+    pub env: Option<PathBuf>,
+    /// Serve LLM completions by prompt substring rather than recorded order
+    /// (see `ReplayingLlmClient::with_prompt_matching`).
+    pub match_llm_prompts: bool,
 }
 
 /// Per-port replayers, each with its own interaction stream.
@@ -42,6 +48,8 @@ pub struct PortReplayers {
     pub id_gen: Option<CassetteReplayer>,
     /// Replayer for the issues port.
     pub issues: Option<CassetteReplayer>,
+    /// Replayer for the environment port.
+    pub env: Option<CassetteReplayer>,
 }
 
 impl CassetteConfig {
@@ -54,6 +62,9 @@ impl CassetteConfig {
 
     /// Load a monolithic cassette file and create a single replayer.
     ///
+    /// Recorded project-root placeholders are replaced with the current
+    /// project root (see [`sanitize::project_root`]).
+    ///
     /// # Errors
     ///
     /// Returns an error if the file cannot be read or parsed.
@@ -62,7 +73,44 @@ impl CassetteConfig {
             .map_err(|e| format!("Failed to read cassette file {}: {e}", path.display()))?;
         let cassette: Cassette = serde_yaml::from_str(&content)
             .map_err(|e| format!("Failed to parse cassette file {}: {e}", path.display()))?;
-        Ok(CassetteReplayer::new(&cassette))
+        let replayer = CassetteReplayer::new(&cassette);
+        Ok(match sanitize::project_root() {
+            Some(root) => replayer.with_root(&root),
+            None => replayer,
+        })
+    }
+
+    /// Build a config from `(port, path)` pairs, e.g. from repeated
+    /// `--port-cassette <port>=<path>` flags. Later pairs for the same port
+    /// replace earlier ones.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the valid ports if a port name is unknown.
+    pub fn from_ports<'a>(
+        ports: impl IntoIterator<Item = &'a (String, PathBuf)>,
+    ) -> Result<Self, String> {
+        let mut config = Self::default();
+        for (port, path) in ports {
+            let slot = match port.as_str() {
+                "llm" => &mut config.llm,
+                "fs" => &mut config.fs,
+                "git" => &mut config.git,
+                "clock" => &mut config.clock,
+                "shell" => &mut config.shell,
+                "id_gen" => &mut config.id_gen,
+                "issues" => &mut config.issues,
+                "env" => &mut config.env,
+                other => {
+                    return Err(format!(
+                        "unknown port '{other}': expected one of llm, fs, git, clock, shell, \
+                         id_gen, issues, env"
+                    ))
+                }
+            };
+            *slot = Some(path.clone());
+        }
+        Ok(config)
     }
 
     /// Load a single per-port cassette file and create a replayer.
@@ -88,6 +136,7 @@ impl CassetteConfig {
             shell: self.shell.as_deref().map(Self::load_port_cassette).transpose()?,
             id_gen: self.id_gen.as_deref().map(Self::load_port_cassette).transpose()?,
             issues: self.issues.as_deref().map(Self::load_port_cassette).transpose()?,
+            env: self.env.as_deref().map(Self::load_port_cassette).transpose()?,
         })
     }
 }
@@ -120,6 +169,7 @@ mod tests {
         assert!(config.shell.is_none());
         assert!(config.id_gen.is_none());
         assert!(config.issues.is_none());
+        assert!(config.env.is_none());
     }
 
     #[test]
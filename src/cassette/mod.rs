@@ -4,4 +4,6 @@ pub mod config;
 pub mod format;
 pub mod recorder;
 pub mod replayer;
+pub mod sanitize;
 pub mod session;
+pub mod shell_output;
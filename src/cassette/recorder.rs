@@ -1,10 +1,11 @@
 //! Records interactions into a cassette file.
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use chrono::Utc;
 
 use super::format::{Cassette, Interaction};
+use super::sanitize;
 
 /// Records interactions and writes them as a YAML cassette file.
 #[derive(Debug)]
@@ -14,6 +15,8 @@ pub struct CassetteRecorder {
     commit: String,
     interactions: Vec<Interaction>,
     next_seq: u64,
+    flush_on_drop: bool,
+    root: Option<PathBuf>,
 }
 
 impl CassetteRecorder {
@@ -29,17 +32,41 @@ impl CassetteRecorder {
             commit: commit.into(),
             interactions: Vec::new(),
             next_seq: 0,
+            flush_on_drop: false,
+            root: None,
         }
     }
 
+    /// Rewrite `root` to [`sanitize::ROOT_PLACEHOLDER`] in recorded inputs
+    /// and outputs, so the cassette replays under any project root.
+    #[must_use]
+    pub fn with_root(mut self, root: &Path) -> Self {
+        self.root = Some(root.to_path_buf());
+        self
+    }
+
+    /// Write the cassette automatically when the recorder is dropped.
+    ///
+    /// Recorders with no interactions write nothing on drop. Parent
+    /// directories are created as needed; write errors are reported on stderr.
+    #[must_use]
+    pub fn with_flush_on_drop(mut self) -> Self {
+        self.flush_on_drop = true;
+        self
+    }
+
     /// Record an interaction. The `seq` field is assigned automatically.
     pub fn record(
         &mut self,
         port: impl Into<String>,
         method: impl Into<String>,
-        input: serde_json::Value,
-        output: serde_json::Value,
+        mut input: serde_json::Value,
+        mut output: serde_json::Value,
     ) {
+        if let Some(root) = &self.root {
+            sanitize::strip_root(&mut input, root);
+            sanitize::strip_root(&mut output, root);
+        }
         let interaction = Interaction {
             seq: self.next_seq,
             port: port.into(),
@@ -56,16 +83,35 @@ impl CassetteRecorder {
     /// # Errors
     ///
     /// Returns an error if the file cannot be written.
-    pub fn finish(self) -> Result<PathBuf, std::io::Error> {
+    pub fn finish(mut self) -> Result<PathBuf, std::io::Error> {
+        self.flush_on_drop = false;
+        self.write()
+    }
+
+    fn write(&mut self) -> Result<PathBuf, std::io::Error> {
         let cassette = Cassette {
-            name: self.name,
+            name: std::mem::take(&mut self.name),
             recorded_at: Utc::now(),
-            commit: self.commit,
-            interactions: self.interactions,
+            commit: std::mem::take(&mut self.commit),
+            interactions: std::mem::take(&mut self.interactions),
         };
         let yaml = serde_yaml::to_string(&cassette).map_err(std::io::Error::other)?;
         std::fs::write(&self.path, yaml)?;
-        Ok(self.path)
+        Ok(self.path.clone())
+    }
+}
+
+impl Drop for CassetteRecorder {
+    fn drop(&mut self) {
+        if !self.flush_on_drop || self.interactions.is_empty() {
+            return;
+        }
+        if let Some(parent) = self.path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        if let Err(e) = self.write() {
+            eprintln!("Failed to write cassette {}: {e}", self.path.display());
+        }
     }
 }
 
@@ -103,4 +149,25 @@ mod tests {
         // Cleanup
         let _ = std::fs::remove_dir_all(&dir);
     }
+
+    #[test]
+    fn flush_on_drop_writes_only_non_empty_recorders() {
+        let dir = std::env::temp_dir().join("speck_cassette_flush_on_drop");
+        let _ = std::fs::remove_dir_all(&dir);
+        let used = dir.join("used/session.yaml");
+        let unused = dir.join("unused/session.yaml");
+
+        {
+            let mut recorder = CassetteRecorder::new(&used, "used", "abc").with_flush_on_drop();
+            recorder.record("clock", "now", json!(null), json!("2025-01-01T00:00:00Z"));
+            let _empty = CassetteRecorder::new(&unused, "unused", "abc").with_flush_on_drop();
+        }
+
+        let cassette: Cassette =
+            serde_yaml::from_str(&std::fs::read_to_string(&used).unwrap()).unwrap();
+        assert_eq!(cassette.interactions.len(), 1);
+        assert!(!unused.exists());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
 }
@@ -1,8 +1,10 @@
 //! Replays recorded interactions from a cassette.
 
 use std::collections::HashMap;
+use std::path::Path;
 
 use super::format::{Cassette, Interaction};
+use super::sanitize;
 
 /// Key for indexing interactions by port and method.
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
@@ -36,6 +38,25 @@ impl CassetteReplayer {
         Self { queues, cursors }
     }
 
+    /// Substitute `root` for [`sanitize::ROOT_PLACEHOLDER`] in every
+    /// interaction's input and output.
+    #[must_use]
+    pub fn with_root(mut self, root: &Path) -> Self {
+        for interaction in self.queues.values_mut().flatten() {
+            sanitize::restore_root(&mut interaction.input, root);
+            sanitize::restore_root(&mut interaction.output, root);
+        }
+        self
+    }
+
+    /// Number of unconsumed interactions for the given port and method.
+    #[must_use]
+    pub fn remaining(&self, port: &str, method: &str) -> usize {
+        let key = PortMethodKey { port: port.to_string(), method: method.to_string() };
+        let cursor = self.cursors.get(&key).copied().unwrap_or_default();
+        self.queues.get(&key).map_or(0, |q| q.len().saturating_sub(cursor))
+    }
+
     /// Return the next interaction for the given port and method.
     ///
     /// # Panics
@@ -69,6 +90,37 @@ impl CassetteReplayer {
         *cursor += 1;
         interaction
     }
+
+    /// Remove and return the first unconsumed interaction for the given port
+    /// and method that satisfies `matches`, regardless of recorded order.
+    ///
+    /// Interactions skipped over remain available to later calls.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no unconsumed interaction for the port/method pair matches.
+    pub fn take_matching(
+        &mut self,
+        port: &str,
+        method: &str,
+        matches: impl Fn(&Interaction) -> bool,
+    ) -> Interaction {
+        let key = PortMethodKey { port: port.to_string(), method: method.to_string() };
+        let cursor = self.cursors.get(&key).copied().unwrap_or_default();
+        let queue = self.queues.get_mut(&key);
+        let index = queue
+            .as_ref()
+            .and_then(|q| q.iter().skip(cursor).position(matches))
+            .map(|i| i + cursor);
+        match (queue, index) {
+            (Some(queue), Some(index)) => queue.remove(index),
+            (queue, _) => panic!(
+                "Cassette exhausted: no matching interaction for port={port:?} \
+                 method={method:?} among {remaining} remaining.",
+                remaining = queue.map_or(0, |q| q.len().saturating_sub(cursor)),
+            ),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -131,6 +183,51 @@ mod tests {
         assert_eq!(i3.output, json!({"text": "2"}));
     }
 
+    #[test]
+    fn path_recorded_under_one_root_replays_under_another() {
+        use crate::cassette::recorder::CassetteRecorder;
+
+        let dir = std::env::temp_dir().join("speck_replayer_root_rewrite");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("fs.cassette.yaml");
+
+        let mut recorder =
+            CassetteRecorder::new(&path, "fs", "abc").with_root(Path::new("/home/alice/project"));
+        recorder.record(
+            "fs",
+            "read_to_string",
+            json!({"path": "/home/alice/project/src/lib.rs"}),
+            json!({"Ok": "pub fn run() {}"}),
+        );
+        recorder.finish().unwrap();
+
+        let yaml = std::fs::read_to_string(&path).unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+        assert!(!yaml.contains("/home/alice"), "{yaml}");
+        let cassette: Cassette = serde_yaml::from_str(&yaml).unwrap();
+
+        let mut replayer = CassetteReplayer::new(&cassette).with_root(Path::new("/ci/build"));
+        let interaction = replayer.next_interaction("fs", "read_to_string");
+        assert_eq!(interaction.input, json!({"path": "/ci/build/src/lib.rs"}));
+    }
+
+    #[test]
+    fn take_matching_skips_over_unmatched_interactions() {
+        let complete = |seq, prompt: &str| Interaction {
+            seq,
+            port: "llm".into(),
+            method: "complete".into(),
+            input: json!({"prompt": prompt}),
+            output: json!({"text": prompt}),
+        };
+        let cassette = make_cassette(vec![complete(0, "a"), complete(1, "b")]);
+
+        let mut replayer = CassetteReplayer::new(&cassette);
+        let b = replayer.take_matching("llm", "complete", |i| i.input["prompt"] == "b");
+        assert_eq!(b.seq, 1);
+        assert_eq!(replayer.next_interaction("llm", "complete").seq, 0);
+    }
+
     #[test]
     #[should_panic(expected = "Cassette exhausted")]
     fn exhausted_replayer_panics_with_descriptive_message() {
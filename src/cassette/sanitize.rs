@@ -0,0 +1,105 @@
+//! Project-root path rewriting that keeps cassettes portable.
+//!
+//! Recorders replace the project root in recorded strings with
+//! [`ROOT_PLACEHOLDER`]; replayers substitute the current root back in, so a
+//! cassette recorded under `/home/alice/project` replays under `/ci/build`.
+
+use std::path::{Path, PathBuf};
+
+/// Placeholder written to cassettes in place of the project root.
+pub const ROOT_PLACEHOLDER: &str = "<ROOT>";
+
+/// Environment variable that overrides the project root used for rewriting.
+pub const ROOT_ENV_VAR: &str = "SPECK_CASSETTE_ROOT";
+
+/// The project root to rewrite: `SPECK_CASSETTE_ROOT` if set, else the cwd.
+#[must_use]
+pub fn project_root() -> Option<PathBuf> {
+    std::env::var(ROOT_ENV_VAR).map(PathBuf::from).ok().or_else(|| std::env::current_dir().ok())
+}
+
+/// Replaces `root` with [`ROOT_PLACEHOLDER`] in every string within `value`.
+///
+/// Only whole path prefixes are replaced: `/work/app` matches in
+/// `/work/app/src` but not in `/work/application`. Filesystem roots (`/`)
+/// are never rewritten.
+pub fn strip_root(value: &mut serde_json::Value, root: &Path) {
+    let root = root.to_string_lossy();
+    let root = root.trim_end_matches('/');
+    if root.is_empty() {
+        return;
+    }
+    map_strings(value, &|s| replace_path_prefix(s, root, ROOT_PLACEHOLDER));
+}
+
+/// Replaces [`ROOT_PLACEHOLDER`] with `root` in every string within `value`.
+pub fn restore_root(value: &mut serde_json::Value, root: &Path) {
+    let root = root.to_string_lossy();
+    let root = root.trim_end_matches('/');
+    map_strings(value, &|s| s.replace(ROOT_PLACEHOLDER, root));
+}
+
+/// Applies `f` to every string in `value`, recursing into arrays and objects.
+fn map_strings(value: &mut serde_json::Value, f: &dyn Fn(&str) -> String) {
+    match value {
+        serde_json::Value::String(s) => *s = f(s),
+        serde_json::Value::Array(items) => items.iter_mut().for_each(|v| map_strings(v, f)),
+        serde_json::Value::Object(map) => map.values_mut().for_each(|v| map_strings(v, f)),
+        _ => {}
+    }
+}
+
+/// Replaces occurrences of `prefix` that end at a path boundary.
+fn replace_path_prefix(text: &str, prefix: &str, replacement: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(pos) = rest.find(prefix) {
+        let end = pos + prefix.len();
+        let at_boundary = rest[end..].chars().next().is_none_or(|c| !is_path_char(c));
+        out.push_str(&rest[..pos]);
+        out.push_str(if at_boundary { replacement } else { prefix });
+        rest = &rest[end..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Characters that continue a path component.
+fn is_path_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '-' | '.')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn strip_and_restore_round_trip_under_new_root() {
+        let mut value = json!({
+            "path": "/home/alice/project/src/lib.rs",
+            "args": ["/home/alice/project", "/home/alice/projects/other"],
+            "len": 3
+        });
+        strip_root(&mut value, Path::new("/home/alice/project/"));
+        assert_eq!(
+            value,
+            json!({
+                "path": "<ROOT>/src/lib.rs",
+                "args": ["<ROOT>", "/home/alice/projects/other"],
+                "len": 3
+            })
+        );
+
+        restore_root(&mut value, Path::new("/ci/build"));
+        assert_eq!(value["path"], "/ci/build/src/lib.rs");
+        assert_eq!(value["args"][0], "/ci/build");
+    }
+
+    #[test]
+    fn filesystem_root_is_never_stripped() {
+        let mut value = json!("/etc/hosts");
+        strip_root(&mut value, Path::new("/"));
+        assert_eq!(value, json!("/etc/hosts"));
+    }
+}
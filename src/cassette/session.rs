@@ -1,11 +1,13 @@
 //! Recording session managing per-port cassette recorders.
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 
 use chrono::Utc;
 
 use super::recorder::CassetteRecorder;
+use super::sanitize;
+use super::shell_output::ShellRecordMode;
 
 /// Manages per-port `CassetteRecorder` instances for a recording session.
 ///
@@ -26,6 +28,10 @@ pub struct RecordingSession {
     pub id_gen: Arc<Mutex<CassetteRecorder>>,
     /// Recorder for issue tracker interactions.
     pub issues: Arc<Mutex<CassetteRecorder>>,
+    /// Recorder for environment interactions.
+    pub env: Arc<Mutex<CassetteRecorder>>,
+    /// How much of each shell run's output is recorded.
+    pub shell_mode: ShellRecordMode,
     /// Output directory containing all cassette files.
     output_dir: PathBuf,
 }
@@ -52,11 +58,15 @@ impl RecordingSession {
             .map_err(|e| format!("Failed to create cassette directory: {e}"))?;
 
         let commit = get_commit_hash();
+        let root = sanitize::project_root();
 
         let make_recorder = |port: &str| -> Arc<Mutex<CassetteRecorder>> {
             let path = output_dir.join(format!("{port}.cassette.yaml"));
             let name = format!("{timestamp}-{port}");
-            Arc::new(Mutex::new(CassetteRecorder::new(path, &name, &commit)))
+            Arc::new(Mutex::new(with_root(
+                CassetteRecorder::new(path, &name, &commit),
+                root.as_deref(),
+            )))
         };
 
         Ok(Self {
@@ -67,10 +77,51 @@ impl RecordingSession {
             shell: make_recorder("shell"),
             id_gen: make_recorder("id_gen"),
             issues: make_recorder("issues"),
+            env: make_recorder("env"),
+            shell_mode: ShellRecordMode::Full,
             output_dir,
         })
     }
 
+    /// Create a session that records each port to `dir/<port>/session.yaml`.
+    ///
+    /// Unlike [`RecordingSession::new`], each recorder flushes itself when its
+    /// last reference is dropped, so the session does not need to be finished.
+    /// Ports with no recorded interactions produce no file.
+    #[must_use]
+    pub fn per_port(dir: &Path) -> Self {
+        let commit = get_commit_hash();
+        let timestamp = Utc::now().format("%Y-%m-%dT%H-%M-%S").to_string();
+        let root = sanitize::project_root();
+
+        let make_recorder = |port: &str| -> Arc<Mutex<CassetteRecorder>> {
+            let path = dir.join(port).join("session.yaml");
+            let name = format!("{timestamp}-{port}");
+            let recorder = CassetteRecorder::new(path, &name, &commit).with_flush_on_drop();
+            Arc::new(Mutex::new(with_root(recorder, root.as_deref())))
+        };
+
+        Self {
+            llm: make_recorder("llm"),
+            fs: make_recorder("fs"),
+            git: make_recorder("git"),
+            clock: make_recorder("clock"),
+            shell: make_recorder("shell"),
+            id_gen: make_recorder("id_gen"),
+            issues: make_recorder("issues"),
+            env: make_recorder("env"),
+            shell_mode: ShellRecordMode::Full,
+            output_dir: dir.to_path_buf(),
+        }
+    }
+
+    /// Record shell runs in `mode` instead of in full.
+    #[must_use]
+    pub fn with_shell_mode(mut self, mode: ShellRecordMode) -> Self {
+        self.shell_mode = mode;
+        self
+    }
+
     /// Finish all recorders and write cassette files to disk.
     ///
     /// Consumes the session and writes each port's cassette file.
@@ -95,11 +146,20 @@ impl RecordingSession {
         finish_one(self.shell, "shell")?;
         finish_one(self.id_gen, "id_gen")?;
         finish_one(self.issues, "issues")?;
+        finish_one(self.env, "env")?;
 
         Ok(self.output_dir)
     }
 }
 
+/// Apply project-root path rewriting to `recorder` when a root is known.
+fn with_root(recorder: CassetteRecorder, root: Option<&Path>) -> CassetteRecorder {
+    match root {
+        Some(root) => recorder.with_root(root),
+        None => recorder,
+    }
+}
+
 /// Get the current git commit hash, or "unknown" with a warning if unavailable.
 fn get_commit_hash() -> String {
     let hash = std::process::Command::new("git")
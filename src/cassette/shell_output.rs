@@ -0,0 +1,155 @@
+//! Compact recorded form of shell output.
+//!
+//! In [`ShellRecordMode::Minimal`], a shell run is recorded as its exit code
+//! plus the head and tail of each output stream, so long test-suite logs do
+//! not bloat cassettes. Runs whose output a check reads, such as coverage
+//! reports or golden files, go through
+//! [`ShellExecutor::run_inspected`](crate::ports::ShellExecutor::run_inspected)
+//! and are always recorded in full. Replayers accept both forms.
+
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::ports::ShellOutput;
+
+/// Bytes kept from each end of an output stream in minimal mode.
+pub const EXCERPT_BYTES: usize = 256;
+
+/// How much of each shell run a recording session keeps.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ShellRecordMode {
+    /// Record stdout and stderr in full.
+    #[default]
+    Full,
+    /// Record the exit code plus the head and tail of each stream, except
+    /// for runs whose output a check reads.
+    Minimal,
+}
+
+impl FromStr for ShellRecordMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "full" => Ok(Self::Full),
+            "minimal" => Ok(Self::Minimal),
+            other => {
+                Err(format!("unknown shell recording mode '{other}': expected full or minimal"))
+            }
+        }
+    }
+}
+
+/// A recorded output stream: the full text, or its head and tail.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum RecordedStream {
+    /// The stream in full.
+    Full(String),
+    /// The first and last bytes of a long stream.
+    Excerpt {
+        /// Start of the stream.
+        head: String,
+        /// End of the stream.
+        tail: String,
+        /// Number of bytes dropped between `head` and `tail`.
+        elided_bytes: usize,
+    },
+}
+
+impl RecordedStream {
+    /// Keeps `text` whole if short, otherwise its first and last [`EXCERPT_BYTES`].
+    #[must_use]
+    pub fn minimal(text: &str) -> Self {
+        if text.len() <= 2 * EXCERPT_BYTES {
+            return Self::Full(text.to_string());
+        }
+        let head_end = floor_char_boundary(text, EXCERPT_BYTES);
+        let mut tail_start = text.len() - EXCERPT_BYTES;
+        while !text.is_char_boundary(tail_start) {
+            tail_start += 1;
+        }
+        Self::Excerpt {
+            head: text[..head_end].to_string(),
+            tail: text[tail_start..].to_string(),
+            elided_bytes: tail_start - head_end,
+        }
+    }
+
+    /// Reconstructs the stream text, marking where bytes were elided.
+    #[must_use]
+    pub fn into_text(self) -> String {
+        match self {
+            Self::Full(text) => text,
+            Self::Excerpt { head, tail, elided_bytes } => {
+                format!("{head}\n[... {elided_bytes} bytes elided ...]\n{tail}")
+            }
+        }
+    }
+}
+
+/// A [`ShellOutput`] as stored in a cassette, in either form.
+///
+/// A full recording deserializes with both streams as [`RecordedStream::Full`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordedShellOutput {
+    /// The exit code of the process.
+    pub exit_code: i32,
+    /// The recorded standard output.
+    pub stdout: RecordedStream,
+    /// The recorded standard error.
+    pub stderr: RecordedStream,
+}
+
+impl RecordedShellOutput {
+    /// The minimal recorded form of `output`.
+    #[must_use]
+    pub fn minimal(output: &ShellOutput) -> Self {
+        Self {
+            exit_code: output.exit_code,
+            stdout: RecordedStream::minimal(&output.stdout),
+            stderr: RecordedStream::minimal(&output.stderr),
+        }
+    }
+}
+
+impl From<RecordedShellOutput> for ShellOutput {
+    fn from(recorded: RecordedShellOutput) -> Self {
+        Self {
+            exit_code: recorded.exit_code,
+            stdout: recorded.stdout.into_text(),
+            stderr: recorded.stderr.into_text(),
+        }
+    }
+}
+
+/// The largest char boundary in `text` at or before `index`.
+fn floor_char_boundary(text: &str, mut index: usize) -> usize {
+    while !text.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn long_stream_keeps_head_and_tail_on_char_boundaries() {
+        let text = format!("é{}end", "x".repeat(1000));
+        let RecordedStream::Excerpt { head, tail, elided_bytes } = RecordedStream::minimal(&text)
+        else {
+            panic!("expected an excerpt");
+        };
+        assert_eq!(head.len(), EXCERPT_BYTES);
+        assert!(head.starts_with('é'));
+        assert!(tail.ends_with("end"));
+        assert_eq!(head.len() + elided_bytes + tail.len(), text.len());
+
+        let short = RecordedStream::minimal("ok\n");
+        assert_eq!(short, RecordedStream::Full("ok\n".to_string()));
+        assert_eq!(short.into_text(), "ok\n");
+    }
+}
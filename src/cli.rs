@@ -2,15 +2,59 @@
 
 use std::path::PathBuf;
 
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 
 /// Top-level CLI parser for `speck`.
 #[derive(Debug, Parser)]
-#[command(name = "speck", version, about = "Plan and verify product work")]
+#[command(
+    name = "speck",
+    version,
+    about = "Plan and verify product work",
+    after_help = "Exit codes:\n  0  success\n  1  validation failed (`validate`)\n  \
+                  2  usage, configuration, or input error (unknown spec ID, malformed spec)\n  \
+                  3  internal, I/O, tracker, or LLM error"
+)]
 pub struct Cli {
     /// The command to execute.
     #[command(subcommand)]
     pub command: Command,
+    /// Record all port interactions to `<DIR>/<port>/session.yaml`.
+    #[arg(long, global = true, value_name = "DIR")]
+    pub output_dir: Option<PathBuf>,
+    /// Read settings from this file instead of `.speck/config.toml`.
+    #[arg(long, global = true, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+    /// Replay one port from its own cassette (repeatable); other ports panic if called.
+    #[arg(
+        long = "port-cassette",
+        global = true,
+        value_name = "PORT=PATH",
+        value_parser = parse_port_cassette
+    )]
+    pub port_cassettes: Vec<(String, PathBuf)>,
+}
+
+/// Parse a `--port-cassette` value of the form `<port>=<path>`.
+fn parse_port_cassette(value: &str) -> Result<(String, PathBuf), String> {
+    match value.split_once('=') {
+        Some((port, path)) if !port.is_empty() && !path.is_empty() => {
+            Ok((port.to_string(), PathBuf::from(path)))
+        }
+        _ => Err(format!("expected <port>=<path>, got '{value}'")),
+    }
+}
+
+/// Output formats accepted by `validate --format`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ValidateFormat {
+    /// Human-readable report.
+    Text,
+    /// One pretty-printed JSON object per spec.
+    Json,
+    /// Newline-delimited JSON, one object per spec.
+    JsonLines,
+    /// A JUnit XML document covering every spec, for CI dashboards.
+    Junit,
 }
 
 /// Supported top-level subcommands.
@@ -19,11 +63,41 @@ pub enum Command {
     /// Produce a lightweight implementation plan from a spec document.
     Plan {
         /// Path to the spec document (markdown file).
-        doc: PathBuf,
+        #[arg(required_unless_present = "revise")]
+        doc: Option<PathBuf>,
+        /// Regenerate the codebase map instead of reusing the cached one.
+        #[arg(long)]
+        no_cache: bool,
+        /// Apply LLM-proposed spec updates without previewing and confirming each.
+        ///
+        /// Without this flag, updates are only applied when confirmed on a
+        /// terminal; when stdin is not a terminal they are skipped.
+        #[arg(long)]
+        apply_updates: bool,
+        /// Save the foundational specs reconciliation suggests extracting.
+        #[arg(long)]
+        apply_extractions: bool,
+        /// Fail instead of warning when a requirement is too vague to classify.
+        #[arg(long)]
+        strict_pushback: bool,
+        /// Give up on planning after this many seconds.
+        #[arg(long, value_name = "SECS")]
+        timeout: Option<u64>,
+        /// Re-plan an existing spec in place, keeping its ID and dependencies.
+        #[arg(long, value_name = "ID")]
+        revise: Option<String>,
+        /// Link the specs to this requirement, merging into its existing specs.
+        #[arg(long, value_name = "RID", conflicts_with = "revise")]
+        requirement_id: Option<String>,
+        /// Plan new specs without asking when a stored spec looks like a duplicate.
+        #[arg(long, conflicts_with = "revise")]
+        allow_duplicate: bool,
     },
     /// Validate behavior and quality checks.
+    ///
+    /// Exits with status 1 when any check fails.
     Validate {
-        /// The spec ID to validate.
+        /// The spec ID to validate, or `-` to read a spec document from stdin.
         spec_id: Option<String>,
         /// Validate all specs in the store.
         #[arg(long)]
@@ -31,25 +105,123 @@ pub enum Command {
         /// Read the spec from a bead in bd by bead ID.
         #[arg(long)]
         bead: Option<String>,
-        /// Output structured JSON instead of human-readable text.
+        /// Read a full spec YAML document from stdin instead of the store.
+        #[arg(long, conflicts_with_all = ["spec_id", "all", "bead"])]
+        spec_stdin: bool,
+        /// Print the resolved commands each check would run, without running them.
+        #[arg(long, conflicts_with = "spec_stdin")]
+        dry_run: bool,
+        /// Validate the spec's dependencies first and skip it if any fail.
+        #[arg(long, requires = "spec_id", conflicts_with_all = ["all", "bead"])]
+        require_deps: bool,
+        /// Re-run checks even for specs with a current cached pass.
+        ///
+        /// A cached pass is reused while the spec, its modules' files, and the
+        /// files under its affected globs are unchanged. Use this when checks
+        /// depend on anything else, such as environment variables or tools.
         #[arg(long)]
+        force: bool,
+        /// Stop starting checks once this many seconds have passed in total.
+        #[arg(long, value_name = "SECS")]
+        deadline: Option<u64>,
+        /// Rewrite golden files with the current output instead of failing on a mismatch.
+        #[arg(long, conflicts_with = "dry_run")]
+        update_golden: bool,
+        /// Archive the output of passing checks to `<DIR>/<spec_id>/` with a manifest.
+        #[arg(long, value_name = "DIR", conflicts_with = "dry_run")]
+        capture_on_pass: Option<PathBuf>,
+        /// Validate disabled specs too under `--all`.
+        #[arg(long, requires = "all")]
+        include_disabled: bool,
+        /// Output structured JSON instead of human-readable text.
+        #[arg(long, conflicts_with = "json_lines")]
         json: bool,
+        /// Stream newline-delimited JSON, one object per spec as each finishes.
+        #[arg(long)]
+        json_lines: bool,
+        /// Output format; `--json` and `--json-lines` are shorthands for it.
+        #[arg(long, value_enum, conflicts_with_all = ["json", "json_lines"])]
+        format: Option<ValidateFormat>,
     },
     /// Map dependencies between tasks.
     Map {
         /// Show what changed since the last map.
         #[arg(long)]
         diff: bool,
+        /// Annotate drifted modules with their last-modified time and author.
+        #[arg(long, requires = "diff")]
+        blame: bool,
+        /// Print each module's fan-in and fan-out, highest fan-in first.
+        #[arg(long, conflicts_with = "diff")]
+        metrics: bool,
+        /// Open a tracker issue summarizing spec drift, unless one is already open.
+        #[arg(long, requires = "diff")]
+        notify: bool,
+        /// Write the generated map here instead of `.spec-cache/codebase_map.yaml`.
+        #[arg(long, value_name = "PATH")]
+        out: Option<PathBuf>,
+        /// Diff against this map file instead of the cached map.
+        #[arg(long, value_name = "PATH", requires = "diff")]
+        since: Option<PathBuf>,
+        /// Diff against a map of this remote ref (e.g. `origin/main`), fetched first.
+        #[arg(long, value_name = "REF", requires = "diff", conflicts_with = "since")]
+        against: Option<String>,
+        /// Report drift for disabled specs too.
+        #[arg(long, requires = "diff")]
+        include_disabled: bool,
     },
     /// Show details of a specific item.
     Show {
         /// The identifier to show.
+        #[arg(conflicts_with = "history")]
         id: Option<String>,
+        /// List the history snapshots of this spec.
+        #[arg(long, value_name = "ID")]
+        history: Option<String>,
+        /// Show the history snapshot taken at this timestamp.
+        #[arg(long, value_name = "TIMESTAMP", requires = "history")]
+        at: Option<String>,
+        /// Show what changed between two history snapshots.
+        #[arg(
+            long,
+            num_args = 2,
+            value_names = ["T1", "T2"],
+            requires = "history",
+            conflicts_with = "at"
+        )]
+        diff: Option<Vec<String>>,
+        /// Run the spec's verification checks and append a pass/fail summary.
+        #[arg(long, requires = "id")]
+        verify: bool,
+        /// Show how the spec's module references resolve against the cached map.
+        #[arg(long, requires = "id", conflicts_with = "verify")]
+        links: bool,
+        /// Resolve `--links` by embedding similarity, falling back to name matching.
+        #[arg(long, requires = "links")]
+        semantic: bool,
+    },
+    /// Edit a stored spec.
+    Edit {
+        /// The spec ID to edit.
+        id: String,
+        /// Replace the acceptance criteria (repeat for each criterion).
+        #[arg(long = "criterion", value_name = "TEXT")]
+        criteria: Vec<String>,
+        /// Re-run signal classification against the edited criteria.
+        #[arg(long)]
+        reclassify: bool,
+        /// Apply reclassification without prompting.
+        #[arg(long, short = 'y')]
+        yes: bool,
     },
     /// Display current project status.
     Status,
     /// List dependency relationships.
-    Deps,
+    Deps {
+        /// Print only aggregate graph stats instead of the per-spec listing.
+        #[arg(long)]
+        summary: bool,
+    },
     /// Sync specs to an external tracker.
     Sync {
         /// The sync target (e.g., "beads").
@@ -57,24 +229,67 @@ pub enum Command {
         /// Show what would happen without making changes.
         #[arg(long)]
         dry_run: bool,
+        /// Refuse to sync while local reconciliation finds specs that should be merged.
+        #[arg(long)]
+        reconcile_first: bool,
+        /// Sync disabled specs too.
+        #[arg(long)]
+        include_disabled: bool,
+    },
+    /// Show what changes between two stored specs.
+    DiffSpecs {
+        /// The spec to compare from.
+        a: String,
+        /// The spec to compare to.
+        b: String,
+    },
+    /// Remove history for deleted specs and stale cache entries.
+    Gc {
+        /// Report what would be removed without deleting anything.
+        #[arg(long)]
+        dry_run: bool,
+        /// Also remove cache entries not modified in this many days.
+        #[arg(long, value_name = "DAYS")]
+        max_age_days: Option<u32>,
+    },
+    /// Import a Markdown requirement draft as a new task spec.
+    Import {
+        /// Markdown file with a `#` title and an `## Acceptance Criteria` checklist.
+        file: PathBuf,
     },
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{Cli, Command};
+    use super::{Cli, Command, ValidateFormat};
     use clap::Parser;
 
     #[test]
     fn parses_plan_with_doc() {
         let cli = Cli::parse_from(["speck", "plan", "spec.md"]);
-        if let Command::Plan { doc } = cli.command {
-            assert_eq!(doc.to_str().unwrap(), "spec.md");
+        if let Command::Plan { doc, no_cache, .. } = cli.command {
+            assert_eq!(doc.unwrap().to_str().unwrap(), "spec.md");
+            assert!(!no_cache);
         } else {
             panic!("expected Plan command");
         }
     }
 
+    #[test]
+    fn parses_plan_revise_without_doc() {
+        let cli = Cli::parse_from(["speck", "plan", "--revise", "TASK-1"]);
+        assert!(matches!(
+            cli.command,
+            Command::Plan { doc: None, revise: Some(ref id), .. } if id == "TASK-1"
+        ));
+    }
+
+    #[test]
+    fn parses_plan_no_cache() {
+        let cli = Cli::parse_from(["speck", "plan", "spec.md", "--no-cache"]);
+        assert!(matches!(cli.command, Command::Plan { no_cache: true, .. }));
+    }
+
     #[test]
     fn plan_requires_doc_arg() {
         let result = Cli::try_parse_from(["speck", "plan"]);
@@ -86,7 +301,46 @@ mod tests {
         let cli = Cli::parse_from(["speck", "validate"]);
         assert!(matches!(
             cli.command,
-            Command::Validate { spec_id: None, all: false, bead: None, json: false }
+            Command::Validate {
+                spec_id: None,
+                all: false,
+                bead: None,
+                spec_stdin: false,
+                dry_run: false,
+                require_deps: false,
+                force: false,
+                deadline: None,
+                update_golden: false,
+                capture_on_pass: None,
+                include_disabled: false,
+                json: false,
+                json_lines: false,
+                format: None
+            }
+        ));
+    }
+
+    #[test]
+    fn parses_validate_deadline() {
+        let cli = Cli::parse_from(["speck", "validate", "--all", "--deadline", "300"]);
+        assert!(matches!(cli.command, Command::Validate { deadline: Some(300), .. }));
+    }
+
+    #[test]
+    fn parses_plan_requirement_id() {
+        let cli = Cli::parse_from(["speck", "plan", "prd.md", "--requirement-id", "REQ-7"]);
+        assert!(matches!(
+            cli.command,
+            Command::Plan { requirement_id: Some(ref rid), .. } if rid == "REQ-7"
+        ));
+    }
+
+    #[test]
+    fn parses_validate_format_junit() {
+        let cli = Cli::parse_from(["speck", "validate", "--all", "--format", "junit"]);
+        assert!(matches!(
+            cli.command,
+            Command::Validate { format: Some(ValidateFormat::Junit), .. }
         ));
     }
 
@@ -120,25 +374,98 @@ mod tests {
     #[test]
     fn parses_map_subcommand() {
         let cli = Cli::parse_from(["speck", "map"]);
-        assert!(matches!(cli.command, Command::Map { diff: false }));
+        assert!(matches!(
+            cli.command,
+            Command::Map { diff: false, blame: false, metrics: false, notify: false, .. }
+        ));
     }
 
     #[test]
     fn parses_map_diff() {
         let cli = Cli::parse_from(["speck", "map", "--diff"]);
-        assert!(matches!(cli.command, Command::Map { diff: true }));
+        assert!(matches!(
+            cli.command,
+            Command::Map { diff: true, blame: false, metrics: false, notify: false, .. }
+        ));
+    }
+
+    #[test]
+    fn parses_map_out_and_since() {
+        let cli = Cli::parse_from(["speck", "map", "--out", "before.yaml"]);
+        assert!(matches!(
+            cli.command,
+            Command::Map { diff: false, out: Some(ref out), since: None, .. }
+                if out.to_str() == Some("before.yaml")
+        ));
+
+        let cli = Cli::parse_from(["speck", "map", "--diff", "--since", "before.yaml"]);
+        assert!(matches!(
+            cli.command,
+            Command::Map { diff: true, out: None, since: Some(ref since), .. }
+                if since.to_str() == Some("before.yaml")
+        ));
+        assert!(Cli::try_parse_from(["speck", "map", "--since", "before.yaml"]).is_err());
     }
 
     #[test]
     fn parses_show_subcommand() {
         let cli = Cli::parse_from(["speck", "show"]);
-        assert!(matches!(cli.command, Command::Show { id: None }));
+        assert!(matches!(cli.command, Command::Show { id: None, history: None, .. }));
     }
 
     #[test]
     fn parses_show_with_id() {
         let cli = Cli::parse_from(["speck", "show", "task-1"]);
-        assert!(matches!(cli.command, Command::Show { id: Some(_) }));
+        assert!(matches!(cli.command, Command::Show { id: Some(_), .. }));
+    }
+
+    #[test]
+    fn parses_show_history_at() {
+        let cli =
+            Cli::parse_from(["speck", "show", "--history", "T-1", "--at", "20260301T090000Z"]);
+        if let Command::Show { history, at, diff, .. } = cli.command {
+            assert_eq!(history.as_deref(), Some("T-1"));
+            assert_eq!(at.as_deref(), Some("20260301T090000Z"));
+            assert!(diff.is_none());
+        } else {
+            panic!("expected Show command");
+        }
+    }
+
+    #[test]
+    fn parses_show_history_diff_takes_two_timestamps() {
+        let cli = Cli::parse_from(["speck", "show", "--history", "T-1", "--diff", "t1", "t2"]);
+        assert!(matches!(cli.command, Command::Show { diff: Some(ref d), .. } if d.len() == 2));
+        assert!(Cli::try_parse_from(["speck", "show", "--at", "t1"]).is_err());
+    }
+
+    #[test]
+    fn parses_show_links_semantic() {
+        let cli = Cli::parse_from(["speck", "show", "T-1", "--links", "--semantic"]);
+        assert!(matches!(cli.command, Command::Show { links: true, semantic: true, .. }));
+        assert!(Cli::try_parse_from(["speck", "show", "T-1", "--semantic"]).is_err());
+    }
+
+    #[test]
+    fn parses_edit_with_criteria_and_reclassify() {
+        let cli = Cli::parse_from([
+            "speck",
+            "edit",
+            "TASK-1",
+            "--criterion",
+            "a",
+            "--criterion",
+            "b",
+            "--reclassify",
+        ]);
+        if let Command::Edit { id, criteria, reclassify, yes } = cli.command {
+            assert_eq!(id, "TASK-1");
+            assert_eq!(criteria, ["a", "b"]);
+            assert!(reclassify);
+            assert!(!yes);
+        } else {
+            panic!("expected Edit command");
+        }
     }
 
     #[test]
@@ -147,10 +474,46 @@ mod tests {
         assert!(matches!(cli.command, Command::Status));
     }
 
+    #[test]
+    fn parses_global_config_path() {
+        let cli = Cli::parse_from(["speck", "--config", "ci/speck.toml", "deps"]);
+        assert_eq!(cli.config.unwrap().to_str().unwrap(), "ci/speck.toml");
+        assert!(matches!(cli.command, Command::Deps { .. }));
+    }
+
+    #[test]
+    fn parses_diff_specs_subcommand() {
+        let cli = Cli::parse_from(["speck", "diff-specs", "TASK-1", "TASK-2"]);
+        assert!(matches!(
+            cli.command,
+            Command::DiffSpecs { ref a, ref b } if a == "TASK-1" && b == "TASK-2"
+        ));
+    }
+
+    #[test]
+    fn parses_gc_subcommand() {
+        let cli = Cli::parse_from(["speck", "gc", "--dry-run", "--max-age-days", "30"]);
+        assert!(matches!(cli.command, Command::Gc { dry_run: true, max_age_days: Some(30) }));
+    }
+
+    #[test]
+    fn parses_import_subcommand() {
+        let cli = Cli::parse_from(["speck", "import", "draft.md"]);
+        assert!(
+            matches!(cli.command, Command::Import { ref file } if file.to_str() == Some("draft.md"))
+        );
+    }
+
     #[test]
     fn parses_deps_subcommand() {
         let cli = Cli::parse_from(["speck", "deps"]);
-        assert!(matches!(cli.command, Command::Deps));
+        assert!(matches!(cli.command, Command::Deps { summary: false }));
+    }
+
+    #[test]
+    fn parses_deps_summary_flag() {
+        let cli = Cli::parse_from(["speck", "deps", "--summary"]);
+        assert!(matches!(cli.command, Command::Deps { summary: true }));
     }
 
     #[test]
@@ -158,7 +521,7 @@ mod tests {
         let cli = Cli::parse_from(["speck", "sync", "beads"]);
         assert!(matches!(
             cli.command,
-            Command::Sync { ref target, dry_run: false } if target == "beads"
+            Command::Sync { ref target, dry_run: false, .. } if target == "beads"
         ));
     }
 
@@ -167,7 +530,73 @@ mod tests {
         let cli = Cli::parse_from(["speck", "sync", "beads", "--dry-run"]);
         assert!(matches!(
             cli.command,
-            Command::Sync { ref target, dry_run: true } if target == "beads"
+            Command::Sync { ref target, dry_run: true, .. } if target == "beads"
         ));
     }
+
+    #[test]
+    fn parses_sync_reconcile_first() {
+        let cli = Cli::parse_from(["speck", "sync", "beads", "--reconcile-first"]);
+        assert!(matches!(cli.command, Command::Sync { reconcile_first: true, .. }));
+    }
+
+    #[test]
+    fn parses_map_metrics() {
+        let cli = Cli::parse_from(["speck", "map", "--metrics"]);
+        assert!(matches!(cli.command, Command::Map { metrics: true, diff: false, .. }));
+        assert!(Cli::try_parse_from(["speck", "map", "--metrics", "--diff"]).is_err());
+    }
+
+    #[test]
+    fn parses_global_output_dir() {
+        let cli = Cli::parse_from(["speck", "status", "--output-dir", "cassettes"]);
+        assert_eq!(cli.output_dir.unwrap().to_str().unwrap(), "cassettes");
+        assert!(matches!(cli.command, Command::Status));
+    }
+
+    #[test]
+    fn parses_validate_spec_stdin_and_rejects_spec_id() {
+        let cli = Cli::parse_from(["speck", "validate", "--spec-stdin"]);
+        assert!(matches!(cli.command, Command::Validate { spec_stdin: true, spec_id: None, .. }));
+        assert!(Cli::try_parse_from(["speck", "validate", "T-1", "--spec-stdin"]).is_err());
+    }
+
+    #[test]
+    fn parses_validate_json_lines_flag() {
+        let cli = Cli::parse_from(["speck", "validate", "--all", "--json-lines"]);
+        assert!(matches!(cli.command, Command::Validate { json_lines: true, json: false, .. }));
+        assert!(
+            Cli::try_parse_from(["speck", "validate", "--all", "--json", "--json-lines"]).is_err()
+        );
+    }
+
+    #[test]
+    fn parses_map_diff_blame() {
+        let cli = Cli::parse_from(["speck", "map", "--diff", "--blame"]);
+        assert!(matches!(cli.command, Command::Map { diff: true, blame: true, .. }));
+        assert!(Cli::try_parse_from(["speck", "map", "--blame"]).is_err());
+    }
+
+    #[test]
+    fn parses_repeated_port_cassettes_into_config() {
+        use crate::cassette::config::CassetteConfig;
+        use std::path::PathBuf;
+
+        let cli = Cli::parse_from([
+            "speck",
+            "status",
+            "--port-cassette",
+            "llm=./llm.yaml",
+            "--port-cassette",
+            "fs=./fs.yaml",
+        ]);
+        let config = CassetteConfig::from_ports(&cli.port_cassettes).unwrap();
+        assert_eq!(config.llm, Some(PathBuf::from("./llm.yaml")));
+        assert_eq!(config.fs, Some(PathBuf::from("./fs.yaml")));
+        assert!(config.git.is_none() && config.shell.is_none() && config.clock.is_none());
+
+        assert!(Cli::try_parse_from(["speck", "status", "--port-cassette", "llm"]).is_err());
+        let unknown = [("db".to_string(), PathBuf::from("db.yaml"))];
+        assert!(CassetteConfig::from_ports(&unknown).unwrap_err().contains("unknown port 'db'"));
+    }
 }
@@ -1,21 +1,25 @@
 //! `speck deps` command.
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::{Path, PathBuf};
 
 use crate::context::ServiceContext;
+use crate::plan::reconcile::detect_circular_dependencies;
+use crate::spec::TaskSpec;
 use crate::store::SpecStore;
 
 /// Execute the `deps` command.
 ///
 /// Displays the dependency graph for all task specs. Each task shows
-/// which other tasks it depends on and which tasks depend on it.
+/// which other tasks it depends on and which tasks depend on it. With
+/// `summary`, only aggregate graph stats are printed. Disabled specs are
+/// included, with "(disabled)" after their title.
 ///
 /// # Errors
 ///
 /// Returns an error string if spec listing or loading fails.
-pub fn run() -> Result<(), String> {
-    run_with_store_root(None)
+pub fn run(summary: bool) -> Result<(), String> {
+    run_with_store_root(summary, None)
 }
 
 /// Execute the `deps` command with an optional explicit store root.
@@ -23,7 +27,7 @@ pub fn run() -> Result<(), String> {
 /// # Errors
 ///
 /// Returns an error string if spec listing or loading fails.
-pub fn run_with_store_root(override_root: Option<&Path>) -> Result<(), String> {
+pub fn run_with_store_root(summary: bool, override_root: Option<&Path>) -> Result<(), String> {
     let ctx = ServiceContext::live();
     let root = match override_root {
         Some(r) => r.to_path_buf(),
@@ -38,14 +42,109 @@ pub fn run_with_store_root(override_root: Option<&Path>) -> Result<(), String> {
     }
     ids.sort();
 
+    let specs = ids.iter().map(|id| store.load_task_spec(id)).collect::<Result<Vec<_>, _>>()?;
+    if summary {
+        println!("{}", format_summary(&summarize(&specs)));
+    } else {
+        println!("{}", format_graph(&specs));
+    }
+
+    Ok(())
+}
+
+/// Aggregate shape of a dependency graph, for `deps --summary`.
+#[derive(Debug, PartialEq, Eq)]
+struct GraphSummary {
+    total: usize,
+    roots: usize,
+    leaves: usize,
+    /// Number of edges on the longest dependency chain; `None` when the graph
+    /// has cycles and no longest path exists.
+    max_depth: Option<usize>,
+    cycles: Vec<Vec<String>>,
+}
+
+/// Computes aggregate stats for the given specs.
+///
+/// Dependencies on specs outside `specs` are ignored for depth.
+fn summarize(specs: &[TaskSpec]) -> GraphSummary {
+    let deps_of = |spec: &TaskSpec| -> Vec<String> {
+        spec.context.as_ref().map(|c| c.dependencies.clone()).unwrap_or_default()
+    };
+    let depends_on: HashMap<&str, Vec<String>> =
+        specs.iter().map(|spec| (spec.id.as_str(), deps_of(spec))).collect();
+    let depended_on: Vec<&String> = depends_on.values().flatten().collect();
+
+    let roots = specs.iter().filter(|spec| depends_on[spec.id.as_str()].is_empty()).count();
+    let leaves = specs.iter().filter(|spec| !depended_on.contains(&&spec.id)).count();
+    let cycles = detect_circular_dependencies(specs);
+    let max_depth = cycles.is_empty().then(|| {
+        let mut memo = HashMap::new();
+        specs.iter().map(|spec| depth(&spec.id, &depends_on, &mut memo)).max().unwrap_or(0)
+    });
+
+    GraphSummary { total: specs.len(), roots, leaves, max_depth, cycles }
+}
+
+/// Longest dependency chain below `id`, counted in edges. The graph must be
+/// acyclic.
+fn depth<'a>(
+    id: &'a str,
+    depends_on: &'a HashMap<&str, Vec<String>>,
+    memo: &mut HashMap<&'a str, usize>,
+) -> usize {
+    if let Some(&known) = memo.get(id) {
+        return known;
+    }
+    let result = depends_on
+        .get(id)
+        .into_iter()
+        .flatten()
+        .filter(|dep| depends_on.contains_key(dep.as_str()))
+        .map(|dep| depth(dep, depends_on, memo) + 1)
+        .max()
+        .unwrap_or(0);
+    memo.insert(id, result);
+    result
+}
+
+/// Renders a [`GraphSummary`] as a short report.
+fn format_summary(summary: &GraphSummary) -> String {
+    let mut lines = vec![
+        format!("Specs: {}", summary.total),
+        format!("Roots (no dependencies): {}", summary.roots),
+        format!("Leaves (nothing depends on them): {}", summary.leaves),
+    ];
+    match summary.max_depth {
+        Some(depth) => lines.push(format!("Max dependency depth: {depth}")),
+        None => lines.push("Max dependency depth: n/a (graph has cycles)".to_string()),
+    }
+    if summary.cycles.is_empty() {
+        lines.push("Cycles: none".to_string());
+    } else {
+        lines.push(format!("Cycles: {}", summary.cycles.len()));
+        for cycle in &summary.cycles {
+            let mut path = cycle.clone();
+            path.push(cycle[0].clone());
+            lines.push(format!("  {}", path.join(" -> ")));
+        }
+    }
+    lines.join("\n")
+}
+
+/// Renders the dependency graph for the given specs.
+///
+/// Circular dependencies are detected up front and reported before the graph.
+/// When any cycle exists, the roots/leaves summary is omitted because no
+/// topological order exists.
+fn format_graph(specs: &[TaskSpec]) -> String {
     // Build dependency maps: depends_on[id] = Vec<dep_ids>, depended_by[id] = Vec<dependent_ids>.
     let mut depends_on: BTreeMap<String, Vec<String>> = BTreeMap::new();
     let mut depended_by: BTreeMap<String, Vec<String>> = BTreeMap::new();
     let mut titles: BTreeMap<String, String> = BTreeMap::new();
 
-    for id in &ids {
-        let spec = store.load_task_spec(id)?;
-        titles.insert(spec.id.clone(), spec.title.clone());
+    for spec in specs {
+        titles.insert(spec.id.clone(), spec.display_title());
 
         let deps = spec.context.as_ref().map(|c| c.dependencies.clone()).unwrap_or_default();
         for dep in &deps {
@@ -53,60 +152,87 @@ pub fn run_with_store_root(override_root: Option<&Path>) -> Result<(), String> {
         }
         depends_on.insert(spec.id.clone(), deps);
     }
+    let ids: Vec<&String> = titles.keys().collect();
+
+    let mut lines = Vec::new();
 
     // Find roots (no dependencies).
-    let roots: Vec<&String> =
-        ids.iter().filter(|id| depends_on.get(*id).is_none_or(std::vec::Vec::is_empty)).collect();
+    let roots: Vec<&String> = ids
+        .iter()
+        .copied()
+        .filter(|id| depends_on.get(*id).is_none_or(std::vec::Vec::is_empty))
+        .collect();
 
     if roots.len() == ids.len() {
-        println!("No dependencies found among {} spec(s).", ids.len());
-        println!("\nAll specs are independent:");
+        lines.push(format!("No dependencies found among {} spec(s).", ids.len()));
+        lines.push(String::new());
+        lines.push("All specs are independent:".to_string());
         for id in &ids {
-            let title = titles.get(id).map_or("", |t| t.as_str());
-            println!("  {id} — {title}");
+            lines.push(format!("  {id} — {}", titles[*id]));
         }
-        return Ok(());
+        return lines.join("\n");
     }
 
-    println!("Dependency Graph:");
-    println!();
+    let cycles = detect_circular_dependencies(specs);
+    if !cycles.is_empty() {
+        lines.push(format!("WARNING: {} circular dependency cycle(s) detected:", cycles.len()));
+        for cycle in &cycles {
+            let mut path = cycle.clone();
+            path.push(cycle[0].clone());
+            lines.push(format!("  {}", path.join(" -> ")));
+        }
+        lines.push(String::new());
+    }
+
+    lines.push("Dependency Graph:".to_string());
+    lines.push(String::new());
 
     for id in &ids {
-        let title = titles.get(id).map_or("", |t| t.as_str());
-        let deps = depends_on.get(id).cloned().unwrap_or_default();
-        let dependents = depended_by.get(id).cloned().unwrap_or_default();
+        let deps = depends_on.get(*id).cloned().unwrap_or_default();
+        let dependents = depended_by.get(*id).cloned().unwrap_or_default();
 
-        println!("{id} — {title}");
+        lines.push(format!("{id} — {}", titles[*id]));
         if deps.is_empty() {
-            println!("  depends on: (none)");
+            lines.push("  depends on: (none)".to_string());
         } else {
-            println!("  depends on: {}", deps.join(", "));
+            lines.push(format!("  depends on: {}", deps.join(", ")));
         }
         if dependents.is_empty() {
-            println!("  blocks: (none)");
+            lines.push("  blocks: (none)".to_string());
         } else {
-            println!("  blocks: {}", dependents.join(", "));
+            lines.push(format!("  blocks: {}", dependents.join(", ")));
         }
-        println!();
+        lines.push(String::new());
+    }
+
+    if !cycles.is_empty() {
+        lines.push(
+            "Roots/leaves summary skipped: the graph has cycles, so no topological order exists."
+                .to_string(),
+        );
+        return lines.join("\n");
     }
 
     // Print topological summary.
     if !roots.is_empty() {
-        println!(
+        lines.push(format!(
             "Roots (no dependencies): {}",
             roots.iter().map(|s| s.as_str()).collect::<Vec<_>>().join(", ")
-        );
+        ));
     }
-    let leaves: Vec<&String> =
-        ids.iter().filter(|id| depended_by.get(*id).is_none_or(std::vec::Vec::is_empty)).collect();
+    let leaves: Vec<&String> = ids
+        .iter()
+        .copied()
+        .filter(|id| depended_by.get(*id).is_none_or(std::vec::Vec::is_empty))
+        .collect();
     if !leaves.is_empty() {
-        println!(
+        lines.push(format!(
             "Leaves (nothing depends on them): {}",
             leaves.iter().map(|s| s.as_str()).collect::<Vec<_>>().join(", ")
-        );
+        ));
     }
 
-    Ok(())
+    lines.join("\n")
 }
 
 fn store_root() -> PathBuf {
@@ -120,38 +246,39 @@ mod tests {
     #[test]
     fn deps_command_empty_store() {
         let dir = PathBuf::from("/tmp/speck_test_deps_empty_nonexistent");
-        let result = run_with_store_root(Some(&dir));
+        let result = run_with_store_root(false, Some(&dir));
         assert!(result.is_ok());
     }
 
     #[test]
     fn deps_command_with_independent_specs() {
-        use crate::spec::{SignalType, TaskSpec, VerificationCheck, VerificationStrategy};
+        use crate::spec::{TaskSpec, VerificationCheck, VerificationStrategy};
 
         let dir = std::env::temp_dir().join("speck_cli_deps_independent");
         let tasks_dir = dir.join("tasks");
         std::fs::create_dir_all(&tasks_dir).unwrap();
 
         let spec = TaskSpec {
-            id: "TASK-1".to_string(),
-            title: "Standalone".to_string(),
-            requirement: None,
-            context: None,
             acceptance_criteria: vec!["works".to_string()],
-            signal_type: SignalType::Clear,
             verification: VerificationStrategy::DirectAssertion {
                 checks: vec![VerificationCheck::TestSuite {
                     command: "cargo test".to_string(),
                     expected: "pass".to_string(),
+                    retries: 0,
+                    retry_backoff_ms: 0,
+                    name: None,
                 }],
+                groups: vec![],
+                setup: vec![],
+                teardown: vec![],
             },
-            affected_globs: None,
+            ..TaskSpec::for_test("TASK-1", "Standalone")
         };
 
         std::fs::write(tasks_dir.join("TASK-1.yaml"), serde_yaml::to_string(&spec).unwrap())
             .unwrap();
 
-        let result = run_with_store_root(Some(&dir));
+        let result = run_with_store_root(false, Some(&dir));
 
         let _ = std::fs::remove_dir_all(&dir);
         assert!(result.is_ok());
@@ -159,47 +286,48 @@ mod tests {
 
     #[test]
     fn deps_command_with_dependency_graph() {
-        use crate::spec::{
-            SignalType, TaskContext, TaskSpec, VerificationCheck, VerificationStrategy,
-        };
+        use crate::spec::{TaskContext, TaskSpec, VerificationCheck, VerificationStrategy};
 
         let dir = std::env::temp_dir().join("speck_cli_deps_graph");
         let tasks_dir = dir.join("tasks");
         std::fs::create_dir_all(&tasks_dir).unwrap();
 
         let spec1 = TaskSpec {
-            id: "TASK-A".to_string(),
-            title: "Base task".to_string(),
-            requirement: None,
-            context: None,
             acceptance_criteria: vec!["done".to_string()],
-            signal_type: SignalType::Clear,
             verification: VerificationStrategy::DirectAssertion {
                 checks: vec![VerificationCheck::TestSuite {
                     command: "cargo test".to_string(),
                     expected: "pass".to_string(),
+                    retries: 0,
+                    retry_backoff_ms: 0,
+                    name: None,
                 }],
+                groups: vec![],
+                setup: vec![],
+                teardown: vec![],
             },
-            affected_globs: None,
+            ..TaskSpec::for_test("TASK-A", "Base task")
         };
         let spec2 = TaskSpec {
-            id: "TASK-B".to_string(),
-            title: "Dependent task".to_string(),
-            requirement: None,
             context: Some(TaskContext {
                 modules: vec![],
                 patterns: None,
                 dependencies: vec!["TASK-A".to_string()],
             }),
             acceptance_criteria: vec!["done".to_string()],
-            signal_type: SignalType::Clear,
             verification: VerificationStrategy::DirectAssertion {
                 checks: vec![VerificationCheck::TestSuite {
                     command: "cargo test".to_string(),
                     expected: "pass".to_string(),
+                    retries: 0,
+                    retry_backoff_ms: 0,
+                    name: None,
                 }],
+                groups: vec![],
+                setup: vec![],
+                teardown: vec![],
             },
-            affected_globs: None,
+            ..TaskSpec::for_test("TASK-B", "Dependent task")
         };
 
         std::fs::write(tasks_dir.join("TASK-A.yaml"), serde_yaml::to_string(&spec1).unwrap())
@@ -207,9 +335,111 @@ mod tests {
         std::fs::write(tasks_dir.join("TASK-B.yaml"), serde_yaml::to_string(&spec2).unwrap())
             .unwrap();
 
-        let result = run_with_store_root(Some(&dir));
+        let result = run_with_store_root(false, Some(&dir));
+
+        let _ = std::fs::remove_dir_all(&dir);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn deps_command_reports_cycle_and_skips_summary() {
+        use crate::spec::{TaskContext, TaskSpec, VerificationCheck, VerificationStrategy};
+
+        let dir = std::env::temp_dir().join("speck_cli_deps_cycle");
+        let tasks_dir = dir.join("tasks");
+        std::fs::create_dir_all(&tasks_dir).unwrap();
+
+        let make = |id: &str, dep: &str| TaskSpec {
+            context: Some(TaskContext {
+                modules: vec![],
+                patterns: None,
+                dependencies: vec![dep.to_string()],
+            }),
+            acceptance_criteria: vec!["done".to_string()],
+            verification: VerificationStrategy::DirectAssertion {
+                checks: vec![VerificationCheck::TestSuite {
+                    command: "cargo test".to_string(),
+                    expected: "pass".to_string(),
+                    retries: 0,
+                    retry_backoff_ms: 0,
+                    name: None,
+                }],
+                groups: vec![],
+                setup: vec![],
+                teardown: vec![],
+            },
+            ..TaskSpec::for_test(id, &format!("Task {id}"))
+        };
+
+        for spec in [make("TASK-A", "TASK-B"), make("TASK-B", "TASK-A")] {
+            std::fs::write(
+                tasks_dir.join(format!("{}.yaml", spec.id)),
+                serde_yaml::to_string(&spec).unwrap(),
+            )
+            .unwrap();
+        }
+
+        let result = run_with_store_root(false, Some(&dir));
+        let ctx = ServiceContext::live();
+        let store = SpecStore::new(&ctx, &dir);
+        let specs =
+            vec![store.load_task_spec("TASK-A").unwrap(), store.load_task_spec("TASK-B").unwrap()];
+        let output = format_graph(&specs);
 
         let _ = std::fs::remove_dir_all(&dir);
         assert!(result.is_ok());
+        assert!(output.contains("WARNING: 1 circular dependency cycle(s) detected"));
+        assert!(output.contains("TASK-A -> TASK-B -> TASK-A"));
+        assert!(!output.contains("Roots (no dependencies)"));
+    }
+
+    #[test]
+    fn summary_reports_depth_roots_and_leaves() {
+        use crate::spec::{TaskContext, TaskSpec, VerificationCheck, VerificationStrategy};
+
+        let make = |id: &str, deps: &[&str]| TaskSpec {
+            context: Some(TaskContext {
+                modules: vec![],
+                patterns: None,
+                dependencies: deps.iter().map(ToString::to_string).collect(),
+            }),
+            acceptance_criteria: vec!["done".to_string()],
+            verification: VerificationStrategy::DirectAssertion {
+                checks: vec![VerificationCheck::TestSuite {
+                    command: "cargo test".to_string(),
+                    expected: "pass".to_string(),
+                    retries: 0,
+                    retry_backoff_ms: 0,
+                    name: None,
+                }],
+                groups: vec![],
+                setup: vec![],
+                teardown: vec![],
+            },
+            ..TaskSpec::for_test(id, &format!("Task {id}"))
+        };
+
+        // A <- B <- C, A <- D, and E standalone; C also depends on A directly.
+        let specs = vec![
+            make("TASK-A", &[]),
+            make("TASK-B", &["TASK-A"]),
+            make("TASK-C", &["TASK-B", "TASK-A"]),
+            make("TASK-D", &["TASK-A"]),
+            make("TASK-E", &[]),
+        ];
+        let summary = summarize(&specs);
+
+        assert_eq!(summary.total, 5);
+        assert_eq!(summary.roots, 2);
+        assert_eq!(summary.leaves, 3);
+        assert_eq!(summary.max_depth, Some(2));
+        assert!(summary.cycles.is_empty());
+        let report = format_summary(&summary);
+        assert!(report.contains("Max dependency depth: 2"), "{report}");
+        assert!(report.contains("Cycles: none"), "{report}");
+
+        let cyclic = summarize(&[make("TASK-A", &["TASK-B"]), make("TASK-B", &["TASK-A"])]);
+        assert_eq!(cyclic.max_depth, None);
+        assert_eq!(cyclic.cycles.len(), 1);
     }
 }
@@ -0,0 +1,86 @@
+//! `speck diff-specs` command.
+
+use std::path::{Path, PathBuf};
+
+use crate::context::ServiceContext;
+use crate::error::SpeckError;
+use crate::store::SpecStore;
+
+use super::show::format_diff;
+
+/// Execute the `diff-specs` command.
+///
+/// Loads specs `a` and `b` from the store and prints what changes going
+/// from `a` to `b`: title, acceptance criteria, signal type, and whether the
+/// verification strategy differs.
+///
+/// # Errors
+///
+/// Returns an error if either spec is missing or cannot be parsed.
+pub fn run(ctx: &ServiceContext, a: &str, b: &str) -> Result<(), SpeckError> {
+    run_with_store_root(ctx, a, b, None)
+}
+
+/// Execute the `diff-specs` command with an optional explicit store root.
+///
+/// # Errors
+///
+/// Returns an error if either spec is missing or cannot be parsed.
+pub fn run_with_store_root(
+    ctx: &ServiceContext,
+    a: &str,
+    b: &str,
+    override_root: Option<&Path>,
+) -> Result<(), SpeckError> {
+    let root = override_root.map_or_else(store_root, Path::to_path_buf);
+    let store = SpecStore::new(ctx, &root);
+    let before = store.load_task_spec(a)?;
+    let after = store.load_task_spec(b)?;
+    println!("{a} -> {b}\n{}", format_diff(&before.diff(&after)));
+    Ok(())
+}
+
+fn store_root() -> PathBuf {
+    std::env::var("SPECK_STORE").map_or_else(|_| PathBuf::from(".speck"), PathBuf::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::{SignalType, TaskSpec};
+
+    fn spec(id: &str, criteria: &[&str], signal: SignalType) -> TaskSpec {
+        TaskSpec {
+            acceptance_criteria: criteria.iter().map(|c| (*c).to_string()).collect(),
+            signal_type: signal,
+            ..TaskSpec::for_test(id, "Export report")
+        }
+    }
+
+    #[test]
+    fn diff_specs_compares_criteria_and_signal() {
+        let dir = std::env::temp_dir().join("speck_diff_specs");
+        let _ = std::fs::remove_dir_all(&dir);
+        let ctx = ServiceContext::live();
+        let store = SpecStore::new(&ctx, &dir);
+        let old = spec("TASK-A", &["exports csv", "feels fast"], SignalType::Fuzzy);
+        let new = spec("TASK-B", &["exports csv", "finishes in 2s"], SignalType::Clear);
+        store.save_task_spec(&old).unwrap();
+        store.save_task_spec(&new).unwrap();
+
+        let loaded_old = store.load_task_spec("TASK-A").unwrap();
+        let loaded_new = store.load_task_spec("TASK-B").unwrap();
+        let ok = run_with_store_root(&ctx, "TASK-A", "TASK-B", Some(&dir));
+        let missing = run_with_store_root(&ctx, "TASK-A", "TASK-GONE", Some(&dir));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert_eq!(
+            format_diff(&loaded_old.diff(&loaded_new)),
+            "Signal: fuzzy -> clear\nAcceptance Criteria:\n  - feels fast\n  + finishes in 2s"
+        );
+        assert!(ok.is_ok());
+        let err = missing.unwrap_err();
+        assert!(matches!(err, SpeckError::NotFound(_)), "{err:?}");
+        assert!(err.to_string().starts_with("Spec 'TASK-GONE' not found"));
+    }
+}
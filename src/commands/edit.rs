@@ -0,0 +1,261 @@
+//! `speck edit` command.
+
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use crate::context::ServiceContext;
+use crate::error::SpeckError;
+use crate::plan::signal::{self, ClassificationResult};
+use crate::spec::TaskSpec;
+use crate::store::SpecStore;
+
+use super::plan::{map_signal_type, map_verification_strategy};
+use super::show::format_signal;
+
+/// Execute the `edit` command against the default spec store.
+///
+/// Confirmation prompts are read from stdin unless `yes` is set.
+///
+/// # Errors
+///
+/// Returns an error if the spec cannot be loaded, reclassified, or saved.
+pub fn run(
+    ctx: &ServiceContext,
+    id: &str,
+    criteria: &[String],
+    reclassify: bool,
+    yes: bool,
+) -> Result<(), SpeckError> {
+    let confirm =
+        |question: &str| yes || super::prompt_yes_no(question, &mut std::io::stdin().lock());
+    run_with_context(ctx, id, criteria, reclassify, &confirm, None)
+}
+
+/// Execute the `edit` command with a provided service context.
+///
+/// Non-empty `criteria` replace the spec's acceptance criteria. With
+/// `reclassify`, the edited spec is re-run through signal classification;
+/// if the signal type changes, `confirm` is asked before the signal type and
+/// verification strategy are overwritten.
+///
+/// # Errors
+///
+/// Returns an error if the spec cannot be loaded, reclassified, or saved.
+pub fn run_with_context(
+    ctx: &ServiceContext,
+    id: &str,
+    criteria: &[String],
+    reclassify: bool,
+    confirm: &dyn Fn(&str) -> bool,
+    override_root: Option<&Path>,
+) -> Result<(), SpeckError> {
+    let root = override_root.map_or_else(store_root, Path::to_path_buf);
+    let store = SpecStore::new(ctx, &root);
+    let mut spec = store.load_task_spec(id)?;
+
+    if !criteria.is_empty() {
+        spec.acceptance_criteria = criteria.to_vec();
+        println!("Updated {} acceptance criteria for {id}.", criteria.len());
+    }
+
+    if reclassify {
+        reclassify_spec(ctx, &mut spec, confirm)?;
+    }
+
+    store.save_task_spec(&spec)?;
+    println!("Saved {id}.");
+    Ok(())
+}
+
+/// Re-run signal classification and apply the result if confirmed.
+fn reclassify_spec(
+    ctx: &ServiceContext,
+    spec: &mut TaskSpec,
+    confirm: &dyn Fn(&str) -> bool,
+) -> Result<(), String> {
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| format!("failed to create async runtime: {e}"))?;
+    let (requirement, codebase_context) = classification_input(spec);
+    let classification = rt
+        .block_on(signal::classify(ctx.llm.as_ref(), &requirement, &codebase_context))
+        .map_err(|e| format!("signal classification failed for {}: {e}", spec.id))?;
+
+    match classification {
+        ClassificationResult::Classified { signal_type, strategy, .. } => {
+            let new_signal = map_signal_type(&signal_type);
+            if new_signal == spec.signal_type {
+                println!("Signal type unchanged ({}).", format_signal(&spec.signal_type));
+                return Ok(());
+            }
+            let question = format!(
+                "Signal type changed {} -> {}. Overwrite signal type and verification?",
+                format_signal(&spec.signal_type),
+                format_signal(&new_signal)
+            );
+            if confirm(&question) {
+                spec.signal_type = new_signal;
+                spec.verification = map_verification_strategy(strategy);
+                println!("Reclassified {} as {}.", spec.id, format_signal(&spec.signal_type));
+            } else {
+                println!("Kept existing classification.");
+            }
+        }
+        ClassificationResult::PushbackRequired { reason } => {
+            eprintln!("Note: pushback required for {} — {reason}", spec.id);
+        }
+    }
+    Ok(())
+}
+
+/// Build the requirement text and codebase context passed to classification.
+pub(super) fn classification_input(spec: &TaskSpec) -> (String, String) {
+    let mut requirement = spec.title.clone();
+    if !spec.acceptance_criteria.is_empty() {
+        requirement.push_str("\n\nAcceptance criteria:\n");
+        for criterion in &spec.acceptance_criteria {
+            let _ = writeln!(requirement, "- {criterion}");
+        }
+    }
+    let codebase_context = spec
+        .context
+        .as_ref()
+        .filter(|c| !c.modules.is_empty())
+        .map(|c| format!("Modules: {}", c.modules.join(", ")))
+        .unwrap_or_default();
+    (requirement, codebase_context)
+}
+
+fn store_root() -> PathBuf {
+    std::env::var("SPECK_STORE").map_or_else(|_| PathBuf::from(".speck"), PathBuf::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cassette::format::{Cassette, Interaction};
+    use crate::spec::{SignalType, VerificationCheck, VerificationStrategy};
+    use serde_json::json;
+
+    fn fuzzy_spec() -> TaskSpec {
+        TaskSpec {
+            acceptance_criteria: vec!["export feels snappy".to_string()],
+            signal_type: SignalType::Fuzzy,
+            verification: VerificationStrategy::DirectAssertion {
+                checks: vec![VerificationCheck::Custom {
+                    description: "feels fast".to_string(),
+                    name: None,
+                }],
+                groups: vec![],
+                setup: vec![],
+                teardown: vec![],
+            },
+            ..TaskSpec::for_test("TASK-E", "Export feels fast")
+        }
+    }
+
+    fn context_with_classification(dir: &Path, response: &serde_json::Value) -> ServiceContext {
+        let cassette = Cassette {
+            name: "edit-reclassify".into(),
+            recorded_at: chrono::Utc::now(),
+            commit: "abc".into(),
+            interactions: vec![Interaction {
+                seq: 0,
+                port: "llm".into(),
+                method: "complete".into(),
+                input: json!({}),
+                output: json!({"ok": {
+                    "text": response.to_string(),
+                    "prompt_tokens": 100,
+                    "completion_tokens": 40
+                }}),
+            }],
+        };
+        let path = dir.join("edit.cassette.yaml");
+        std::fs::write(&path, serde_yaml::to_string(&cassette).unwrap()).unwrap();
+        let mut ctx = ServiceContext::replaying(&path).unwrap();
+        ctx.fs = Box::new(crate::adapters::live::filesystem::LiveFileSystem);
+        ctx
+    }
+
+    fn clear_response() -> serde_json::Value {
+        json!({
+            "type": "clear",
+            "confidence": 0.9,
+            "checks": [{
+                "check_type": "command_output",
+                "command": "speck export --time",
+                "expected": "under 2s"
+            }]
+        })
+    }
+
+    #[test]
+    fn reclassify_flips_fuzzy_to_clear_after_criteria_edit() {
+        let dir = std::env::temp_dir().join("speck_edit_reclassify");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let ctx = context_with_classification(&dir, &clear_response());
+        let store = SpecStore::new(&ctx, &dir);
+        store.save_task_spec(&fuzzy_spec()).unwrap();
+
+        let criteria = vec!["export of 10k rows finishes in under 2s".to_string()];
+        run_with_context(&ctx, "TASK-E", &criteria, true, &|_| true, Some(&dir)).unwrap();
+
+        let saved = store.load_task_spec("TASK-E").unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+        assert_eq!(saved.acceptance_criteria, criteria);
+        assert_eq!(saved.signal_type, SignalType::Clear);
+        assert_eq!(
+            saved.verification,
+            VerificationStrategy::DirectAssertion {
+                checks: vec![VerificationCheck::CommandOutput {
+                    command: "speck export --time".to_string(),
+                    expected: "under 2s".to_string(),
+                    retries: 0,
+                    retry_backoff_ms: 0,
+                    name: None
+                }],
+                groups: vec![],
+                setup: vec![],
+                teardown: vec![],
+            }
+        );
+    }
+
+    #[test]
+    fn declined_reclassification_keeps_signal_but_saves_criteria() {
+        let dir = std::env::temp_dir().join("speck_edit_reclassify_declined");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let ctx = context_with_classification(&dir, &clear_response());
+        let store = SpecStore::new(&ctx, &dir);
+        store.save_task_spec(&fuzzy_spec()).unwrap();
+
+        let criteria = vec!["export finishes in under 2s".to_string()];
+        run_with_context(&ctx, "TASK-E", &criteria, true, &|_| false, Some(&dir)).unwrap();
+
+        let saved = store.load_task_spec("TASK-E").unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+        assert_eq!(saved.acceptance_criteria, criteria);
+        assert_eq!(saved.signal_type, SignalType::Fuzzy);
+        assert_eq!(saved.verification, fuzzy_spec().verification);
+    }
+
+    #[test]
+    fn classification_input_lists_criteria_and_modules() {
+        let mut spec = fuzzy_spec();
+        spec.context = Some(crate::spec::TaskContext {
+            modules: vec!["Exporter".to_string()],
+            patterns: None,
+            dependencies: vec![],
+        });
+        let (requirement, context) = classification_input(&spec);
+        assert_eq!(
+            requirement,
+            "Export feels fast\n\nAcceptance criteria:\n- export feels snappy\n"
+        );
+        assert_eq!(context, "Modules: Exporter");
+    }
+}
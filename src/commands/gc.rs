@@ -0,0 +1,194 @@
+//! `speck gc` command.
+//!
+//! History snapshots, cached validation results, and project cache entries
+//! are the artifacts that can go stale.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use chrono::Duration;
+
+use crate::context::ServiceContext;
+use crate::store::SpecStore;
+use crate::validate::VERIFICATION_CACHE_DIR;
+
+/// Directory (relative to the project root) holding cached maps and responses.
+const CACHE_DIR: &str = ".spec-cache";
+
+/// Files and bytes reclaimed (or reclaimable, in a dry run) by `gc`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GcReport {
+    /// Paths of the files removed.
+    pub removed: Vec<PathBuf>,
+    /// Total size of the removed files, in bytes.
+    pub bytes: u64,
+}
+
+/// Execute the `gc` command against the default store and cache locations.
+///
+/// # Errors
+///
+/// Returns an error string if the store or cache cannot be read, or a file
+/// cannot be removed.
+pub fn run(ctx: &ServiceContext, dry_run: bool, max_age_days: Option<u32>) -> Result<(), String> {
+    let cwd = std::env::current_dir().map_err(|e| format!("Cannot determine cwd: {e}"))?;
+    let store_root =
+        std::env::var("SPECK_STORE").map_or_else(|_| cwd.join(".speck"), PathBuf::from);
+    run_with_roots(ctx, &store_root, &cwd.join(CACHE_DIR), dry_run, max_age_days).map(|_| ())
+}
+
+/// Execute the `gc` command with explicit store and cache directories.
+///
+/// Removes `<store>/history/<id>/` and `<store>/verification-cache/<id>.yaml`
+/// for every spec ID no longer in the store. With `max_age_days`, also removes
+/// files in `cache_dir` last modified more than that many days before
+/// `ctx.clock.now()`. With `dry_run`, prints what would be removed without
+/// removing anything.
+///
+/// # Errors
+///
+/// Returns an error string if the store or cache cannot be read, or a file
+/// cannot be removed.
+pub fn run_with_roots(
+    ctx: &ServiceContext,
+    store_root: &Path,
+    cache_dir: &Path,
+    dry_run: bool,
+    max_age_days: Option<u32>,
+) -> Result<GcReport, String> {
+    let mut report = GcReport::default();
+    let verb = if dry_run { "Would remove" } else { "Removed" };
+
+    let live: HashSet<String> =
+        SpecStore::new(ctx, store_root).list_task_specs()?.into_iter().collect();
+    let history_root = store_root.join("history");
+    for id in list_dir(ctx, &history_root)? {
+        if live.contains(&id) {
+            continue;
+        }
+        let dir = history_root.join(&id);
+        for name in list_dir(ctx, &dir)? {
+            remove(ctx, &dir.join(name), dry_run, &mut report)?;
+        }
+        if !dry_run {
+            ctx.fs
+                .remove_dir(&dir)
+                .map_err(|e| format!("Failed to remove {}: {e}", dir.display()))?;
+        }
+        println!("{verb} history for deleted spec {id}");
+    }
+
+    let verification_cache = store_root.join(VERIFICATION_CACHE_DIR);
+    for name in list_dir(ctx, &verification_cache)? {
+        let Some(id) = name.strip_suffix(".yaml") else {
+            continue;
+        };
+        if !live.contains(id) {
+            remove(ctx, &verification_cache.join(&name), dry_run, &mut report)?;
+            println!("{verb} cached result for deleted spec {id}");
+        }
+    }
+
+    if let Some(days) = max_age_days {
+        let cutoff = ctx.clock.now() - Duration::days(i64::from(days));
+        for name in list_dir(ctx, cache_dir)? {
+            let path = cache_dir.join(name);
+            let meta = metadata(ctx, &path)?;
+            if !meta.is_dir && meta.modified < cutoff {
+                remove(ctx, &path, dry_run, &mut report)?;
+                println!("{verb} stale cache entry {}", path.display());
+            }
+        }
+    }
+
+    println!("{verb} {} file(s), {} byte(s).", report.removed.len(), report.bytes);
+    Ok(report)
+}
+
+/// Record `path` in the report and delete it unless `dry_run`.
+fn remove(
+    ctx: &ServiceContext,
+    path: &Path,
+    dry_run: bool,
+    report: &mut GcReport,
+) -> Result<(), String> {
+    report.bytes += metadata(ctx, path)?.len;
+    if !dry_run {
+        ctx.fs
+            .remove_file(path)
+            .map_err(|e| format!("Failed to remove {}: {e}", path.display()))?;
+    }
+    report.removed.push(path.to_path_buf());
+    Ok(())
+}
+
+fn metadata(ctx: &ServiceContext, path: &Path) -> Result<crate::ports::FileMetadata, String> {
+    ctx.fs.metadata(path).map_err(|e| format!("Failed to inspect {}: {e}", path.display()))
+}
+
+/// List a directory, treating a missing directory as empty.
+fn list_dir(ctx: &ServiceContext, dir: &Path) -> Result<Vec<String>, String> {
+    if !ctx.fs.exists(dir) {
+        return Ok(Vec::new());
+    }
+    ctx.fs.list_dir(dir).map_err(|e| format!("Failed to list {}: {e}", dir.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cassette::config::CassetteConfig;
+    use crate::spec::TaskSpec;
+
+    fn spec(id: &str) -> TaskSpec {
+        TaskSpec::for_test(id, &format!("Task {id}"))
+    }
+
+    #[test]
+    fn gc_removes_orphaned_history_and_cached_results_and_keeps_live_ones() {
+        let dir = std::env::temp_dir().join("speck_gc_orphaned_history");
+        let _ = std::fs::remove_dir_all(&dir);
+        let mut ctx = ServiceContext::replaying_from(&CassetteConfig::panic_on_unspecified())
+            .expect("panic config should always succeed");
+        ctx.fs = Box::new(crate::adapters::live::filesystem::LiveFileSystem);
+
+        let store = SpecStore::new(&ctx, &dir);
+        let at = chrono::Utc::now();
+        store.save_task_spec(&spec("LIVE-1")).unwrap();
+        store.save_history_snapshot(&spec("LIVE-1"), at).unwrap();
+        store.save_history_snapshot(&spec("GONE-1"), at).unwrap();
+        let results = crate::validate::VerificationCache::new(&ctx, &dir);
+        for id in ["LIVE-1", "GONE-1"] {
+            let result = crate::validate::ValidationResult {
+                spec_id: id.into(),
+                checks: vec![],
+                known_failures: vec![],
+                teardown_failures: vec![],
+            };
+            results.save("f00d", &result).unwrap();
+        }
+        let cached = |id: &str| dir.join(VERIFICATION_CACHE_DIR).join(format!("{id}.yaml"));
+
+        let cache = dir.join("cache");
+        let dry = run_with_roots(&ctx, &dir, &cache, true, None).unwrap();
+        let orphan_survived_dry_run = dir.join("history/GONE-1").exists();
+        let result_survived_dry_run = cached("GONE-1").exists();
+        let report = run_with_roots(&ctx, &dir, &cache, false, None).unwrap();
+
+        let live_history = store.list_history("LIVE-1").unwrap();
+        let orphan_exists = dir.join("history/GONE-1").exists();
+        let (orphan_result_exists, live_result_exists) =
+            (cached("GONE-1").exists(), cached("LIVE-1").exists());
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert_eq!(dry.removed.len(), 2);
+        assert!(orphan_survived_dry_run);
+        assert!(result_survived_dry_run);
+        assert_eq!(report.removed.len(), 2);
+        assert!(report.bytes > 0);
+        assert!(!orphan_exists);
+        assert!(!orphan_result_exists);
+        assert!(live_result_exists);
+        assert_eq!(live_history.len(), 1);
+    }
+}
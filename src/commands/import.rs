@@ -0,0 +1,177 @@
+//! `speck import` command.
+
+use std::path::{Path, PathBuf};
+
+use crate::context::ServiceContext;
+use crate::spec::{SignalType, TaskSpec, VerificationCheck, VerificationStrategy};
+use crate::store::SpecStore;
+
+/// Heading (after `## `) of the section holding the acceptance criteria.
+const CRITERIA_HEADING: &str = "acceptance criteria";
+
+/// Execute the `import` command against the default store.
+///
+/// # Errors
+///
+/// Returns an error string if the document cannot be read or parsed, or the
+/// spec cannot be saved.
+pub fn run(ctx: &ServiceContext, file: &Path) -> Result<(), String> {
+    run_with_store_root(ctx, file, None)
+}
+
+/// Import a Markdown requirement draft as a new task spec.
+///
+/// The spec gets an ID from `ctx.id_gen` and is stamped with the current
+/// time and commit. Its signal type is fuzzy and each criterion becomes a
+/// custom (manual) check until the spec is reclassified with `speck edit`.
+///
+/// # Errors
+///
+/// Returns an error string if the document cannot be read or parsed, or the
+/// spec cannot be saved.
+pub fn run_with_store_root(
+    ctx: &ServiceContext,
+    file: &Path,
+    override_root: Option<&Path>,
+) -> Result<(), String> {
+    let markdown = ctx
+        .fs
+        .read_to_string(file)
+        .map_err(|e| format!("Failed to read {}: {e}", file.display()))?;
+    let mut spec = parse_markdown(&ctx.id_gen.generate_id(), &markdown)
+        .map_err(|e| format!("Failed to import {}: {e}", file.display()))?;
+    spec.created_at = Some(ctx.clock.now());
+    spec.created_commit = ctx.git.current_commit().ok();
+
+    let root = override_root.map_or_else(store_root, Path::to_path_buf);
+    SpecStore::new(ctx, &root).save_task_spec(&spec)?;
+    println!("Imported {} — {} ({} criteria)", spec.id, spec.title, spec.acceptance_criteria.len());
+    Ok(())
+}
+
+/// Parse a Markdown requirement draft into a [`TaskSpec`] with the given ID.
+///
+/// The first `# ` heading is the title. List items under `## Acceptance
+/// Criteria` (up to the next heading) are the criteria, prefixed `[done]` for
+/// `- [x]` and `[pending]` for `- [ ]` or a plain `- ` item.
+///
+/// # Errors
+///
+/// Returns an error if the document has no title or no acceptance criteria.
+pub fn parse_markdown(id: &str, markdown: &str) -> Result<TaskSpec, String> {
+    let mut title = None;
+    let mut in_criteria = false;
+    let mut criteria = Vec::new();
+
+    for line in markdown.lines().map(str::trim) {
+        if let Some(heading) = line.strip_prefix("# ") {
+            title.get_or_insert_with(|| heading.trim().to_string());
+            in_criteria = false;
+        } else if let Some(heading) = line.strip_prefix("## ") {
+            in_criteria = heading.trim().eq_ignore_ascii_case(CRITERIA_HEADING);
+        } else if line.starts_with('#') {
+            in_criteria = false;
+        } else if in_criteria {
+            if let Some(item) = line.strip_prefix("- ").or_else(|| line.strip_prefix("* ")) {
+                criteria.push(parse_criterion(item));
+            }
+        }
+    }
+
+    let title = title.ok_or("no `# ` title heading found")?;
+    if criteria.is_empty() {
+        return Err("no list items found under `## Acceptance Criteria`".to_string());
+    }
+
+    let checks = criteria
+        .iter()
+        .map(|(_, text)| VerificationCheck::Custom { description: text.clone(), name: None })
+        .collect();
+    Ok(TaskSpec {
+        id: id.to_string(),
+        title,
+        requirement: None,
+        context: None,
+        acceptance_criteria: criteria
+            .into_iter()
+            .map(|(done, text)| format!("[{}] {text}", if done { "done" } else { "pending" }))
+            .collect(),
+        signal_type: SignalType::Fuzzy,
+        verification: VerificationStrategy::DirectAssertion {
+            checks,
+            groups: vec![],
+            setup: vec![],
+            teardown: vec![],
+        },
+        affected_globs: None,
+        created_at: None,
+        created_commit: None,
+        disabled: false,
+    })
+}
+
+/// Split a list item into whether it is checked off and its text.
+fn parse_criterion(item: &str) -> (bool, String) {
+    let item = item.trim();
+    if let Some(text) = item.strip_prefix("[x]").or_else(|| item.strip_prefix("[X]")) {
+        (true, text.trim().to_string())
+    } else {
+        (false, item.strip_prefix("[ ]").unwrap_or(item).trim().to_string())
+    }
+}
+
+fn store_root() -> PathBuf {
+    std::env::var("SPECK_STORE").map_or_else(|_| PathBuf::from(".speck"), PathBuf::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_markdown_checklist_into_spec() {
+        let markdown = "\
+# Export reports as CSV
+
+Finance wants spreadsheets.
+
+## Acceptance Criteria
+
+- [x] Export button on the report page
+- [ ] CSV includes a header row
+- Large reports stream instead of buffering
+
+## Notes
+
+- [ ] Not a criterion
+";
+        let spec = parse_markdown("IMPORT-1", markdown).unwrap();
+
+        let custom = |d: &str| VerificationCheck::Custom { description: d.to_string(), name: None };
+        assert_eq!(
+            spec,
+            TaskSpec {
+                acceptance_criteria: vec![
+                    "[done] Export button on the report page".to_string(),
+                    "[pending] CSV includes a header row".to_string(),
+                    "[pending] Large reports stream instead of buffering".to_string(),
+                ],
+                signal_type: SignalType::Fuzzy,
+                verification: VerificationStrategy::DirectAssertion {
+                    checks: vec![
+                        custom("Export button on the report page"),
+                        custom("CSV includes a header row"),
+                        custom("Large reports stream instead of buffering"),
+                    ],
+                    groups: vec![],
+                    setup: vec![],
+                    teardown: vec![],
+                },
+                ..TaskSpec::for_test("IMPORT-1", "Export reports as CSV")
+            }
+        );
+
+        let err = parse_markdown("IMPORT-2", "# Title only\n").unwrap_err();
+        assert!(err.contains("Acceptance Criteria"), "{err}");
+    }
+}
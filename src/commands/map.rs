@@ -1,69 +1,198 @@
 //! `speck map` command.
 
 use std::env;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use crate::context::ServiceContext;
+use crate::linkage::{self, ModuleBlame};
+use crate::map::analysis;
 use crate::map::diff;
 use crate::map::generator;
+use crate::map::CodebaseMap;
+use crate::ports::{Issue, IssueTracker};
+use crate::spec::TaskSpec;
+use crate::store::SpecStore;
 
 /// Output path for the generated codebase map (relative to project root).
 const MAP_OUTPUT_PATH: &str = ".spec-cache/codebase_map.yaml";
 
-/// Execute the `map` command.
+/// Title of the tracker issue opened by `map --diff --notify`.
+const DRIFT_ISSUE_TITLE: &str = "speck: spec drift detected";
+
+/// Options for `map --diff`.
+#[derive(Debug, Clone, Default)]
+pub struct DiffOptions {
+    /// Annotate drifted modules with their last change.
+    pub blame: bool,
+    /// Open a tracker issue when drift is found.
+    pub notify: bool,
+    /// Compare against this map file instead of the cached map.
+    pub since: Option<PathBuf>,
+    /// Compare against a map generated from this remote ref, fetched first.
+    pub against: Option<String>,
+    /// Report drift for disabled specs too.
+    pub include_disabled: bool,
+}
+
+/// Execute the `map` command with the given service context.
 ///
-/// When `diff` is false, generates a new codebase map and writes it to
-/// `.spec-cache/codebase_map.yaml`.
+/// When `diff` is `None`, generates a new codebase map and writes it to
+/// `out`, or to `.spec-cache/codebase_map.yaml` when `out` is `None`.
 ///
-/// When `diff` is true, loads the previous map, generates a new one, and
-/// displays the differences.
+/// When `diff` is given, loads the previous map (the cached one, or the file
+/// given by `since`), generates a new one, and displays the differences
+/// followed by a drift report for stored specs. With `against`, the previous
+/// map is instead generated from the tree of that remote ref after fetching
+/// it, so the working tree is compared with upstream. Disabled specs are left
+/// out of the drift report unless `include_disabled` is set.
+/// With `blame`, drifted modules are annotated with their last change.
+/// With `notify`, drift also opens a tracker issue via `ctx.issues`, unless
+/// a drift issue is already open. Files, git (including the fetch for
+/// `against`), and the tracker are all reached through `ctx`, so the command
+/// records and replays like any other.
+///
+/// When `metrics` is true, generates the map and prints each module's
+/// fan-in and fan-out so heavily depended-on hub modules stand out.
 ///
 /// # Errors
 ///
 /// Returns an error string if map generation or diffing fails.
-pub fn run(show_diff: bool) -> Result<(), String> {
-    let ctx = ServiceContext::live();
+pub fn run(
+    ctx: &ServiceContext,
+    diff: Option<DiffOptions>,
+    metrics: bool,
+    out: Option<&Path>,
+) -> Result<(), String> {
     let root = env::current_dir().map_err(|e| format!("failed to get current directory: {e}"))?;
 
-    if show_diff {
-        run_diff(&ctx, &root)
+    if let Some(options) = diff {
+        let store_root =
+            env::var("SPECK_STORE").map_or_else(|_| root.join(".speck"), PathBuf::from);
+        let store = SpecStore::new(ctx, &store_root);
+        let specs: Vec<TaskSpec> = store
+            .list_task_specs()
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|id| store.load_task_spec(id).ok())
+            .filter(|spec| options.include_disabled || !spec.disabled)
+            .collect();
+        run_diff(ctx, &root, &specs, &options, out)
+    } else if metrics {
+        run_metrics(ctx, &root, out)
     } else {
-        run_generate(&ctx, &root)
+        run_generate(ctx, &root, out)
     }
 }
 
 /// Generate a new map and print a summary.
-fn run_generate(ctx: &ServiceContext, root: &Path) -> Result<(), String> {
-    let map = generator::generate(ctx, root)?;
+fn run_generate(ctx: &ServiceContext, root: &Path, out: Option<&Path>) -> Result<(), String> {
+    let map = generator::generate(ctx, root, out)?;
     println!(
         "Map generated: {} modules, {} files, {} test files",
         map.modules.len(),
         map.directory_tree.len(),
         map.test_infrastructure.len(),
     );
-    println!("Written to {MAP_OUTPUT_PATH}");
+    println!("Written to {}", out.unwrap_or(Path::new(MAP_OUTPUT_PATH)).display());
+    if !map.unreadable.is_empty() {
+        eprintln!(
+            "Warning: {} file(s) could not be read and are not analyzed:",
+            map.unreadable.len()
+        );
+        for entry in &map.unreadable {
+            eprintln!("  {entry}");
+        }
+    }
     Ok(())
 }
 
-/// Load the previous map, generate a new one, and display the diff.
-fn run_diff(ctx: &ServiceContext, root: &Path) -> Result<(), String> {
-    let map_path = root.join(MAP_OUTPUT_PATH);
-    let old_yaml = ctx
-        .fs
-        .read_to_string(&map_path)
-        .map_err(|e| format!("failed to read previous map at {}: {e}", map_path.display()))?;
-    let old_map: crate::map::CodebaseMap = serde_yaml::from_str(&old_yaml)
-        .map_err(|e| format!("failed to parse previous map: {e}"))?;
+/// Generate a new map and print per-module fan-in/fan-out.
+fn run_metrics(ctx: &ServiceContext, root: &Path, out: Option<&Path>) -> Result<(), String> {
+    let map = generator::generate(ctx, root, out)?;
+    println!("{}", analysis::format_fan_metrics(&analysis::fan_metrics(&map)));
+    Ok(())
+}
 
-    let new_map = generator::generate(ctx, root)?;
+/// Load the previous map, generate a new one, and display the diff and spec drift.
+fn run_diff(
+    ctx: &ServiceContext,
+    root: &Path,
+    specs: &[TaskSpec],
+    options: &DiffOptions,
+    out: Option<&Path>,
+) -> Result<(), String> {
+    let old_map = previous_map(ctx, root, options)?;
+    let new_map = generator::generate(ctx, root, out)?;
 
     let d = diff::diff_maps(&old_map, &new_map);
     println!("{}", diff::format_diff(&d));
+
+    if !specs.is_empty() {
+        let report = linkage::detect_drift(specs, &old_map, &new_map);
+        let blame = if options.blame {
+            linkage::blame_drift(&report, ctx.git.as_ref())
+        } else {
+            ModuleBlame::new()
+        };
+        println!("\n{}", linkage::format_drift_report_with_blame(&report, &blame));
+        if options.notify {
+            notify_drift(ctx.issues.as_ref(), &report)?;
+        }
+    }
     Ok(())
 }
 
+/// The map to diff against: generated from `options.against`, or read from disk.
+fn previous_map(
+    ctx: &ServiceContext,
+    root: &Path,
+    options: &DiffOptions,
+) -> Result<CodebaseMap, String> {
+    if let Some(remote_ref) = &options.against {
+        let commit = ctx
+            .git
+            .fetch_remote_commit(remote_ref)
+            .map_err(|e| format!("failed to resolve {remote_ref}: {e}"))?;
+        return generator::generate_at_commit(ctx, &commit);
+    }
+    let map_path = root.join(options.since.as_deref().unwrap_or(Path::new(MAP_OUTPUT_PATH)));
+    let old_yaml = ctx
+        .fs
+        .read_to_string(&map_path)
+        .map_err(|e| format!("failed to read previous map at {}: {e}", map_path.display()))?;
+    serde_yaml::from_str(&old_yaml).map_err(|e| format!("failed to parse previous map: {e}"))
+}
+
+/// Open a drift issue for a non-clean `report`, unless one is already open.
+///
+/// Returns the created issue, or `None` if there was nothing to report or an
+/// open drift issue already exists.
+fn notify_drift(
+    issues: &dyn IssueTracker,
+    report: &linkage::DriftReport,
+) -> Result<Option<Issue>, String> {
+    if report.is_clean() {
+        return Ok(None);
+    }
+    let existing =
+        issues.list_issues(None).map_err(|e| format!("Failed to list existing issues: {e}"))?;
+    if let Some(open) =
+        existing.iter().find(|i| i.title == DRIFT_ISSUE_TITLE && i.status != "closed")
+    {
+        println!("Drift issue {} is already open; not creating another.", open.id);
+        return Ok(None);
+    }
+    let issue = issues
+        .create_issue(DRIFT_ISSUE_TITLE, &linkage::format_drift_report(report), &[])
+        .map_err(|e| format!("Failed to create drift issue: {e}"))?;
+    println!("Opened drift issue {}.", issue.id);
+    Ok(Some(issue))
+}
+
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
+
     use super::*;
     use crate::cassette::format::{Cassette, Interaction};
     use chrono::Utc;
@@ -114,6 +243,13 @@ mod tests {
                 Interaction {
                     seq: 5,
                     port: "fs".into(),
+                    method: "create_dir_all".into(),
+                    input: json!({"path": "/project/.spec-cache"}),
+                    output: json!(null),
+                },
+                Interaction {
+                    seq: 6,
+                    port: "fs".into(),
                     method: "write".into(),
                     input: json!({"path": "/project/.spec-cache/codebase_map.yaml"}),
                     output: json!(null),
@@ -132,7 +268,7 @@ mod tests {
         std::fs::write(&path, &yaml).unwrap();
 
         let ctx = ServiceContext::replaying(&path).unwrap();
-        let result = run_generate(&ctx, std::path::Path::new("/project"));
+        let result = run_generate(&ctx, std::path::Path::new("/project"), None);
         assert!(result.is_ok());
 
         let _ = std::fs::remove_dir_all(&dir);
@@ -151,9 +287,13 @@ mod tests {
                 path: "src".to_string(),
                 public_items: vec!["fn run".to_string()],
                 dependencies: vec![],
+                external_deps: vec![],
+                locations: BTreeMap::new(),
+                crate_name: None,
             }],
             directory_tree: vec!["src/lib.rs".to_string()],
             test_infrastructure: vec![],
+            unreadable: vec![],
         };
         let old_yaml = serde_yaml::to_string(&old_map).unwrap();
 
@@ -210,10 +350,17 @@ mod tests {
                     input: json!({"path": "/project/src/map/mod.rs"}),
                     output: json!("pub fn generate() {}\n"),
                 },
-                // fs.write — new map
                 Interaction {
                     seq: 6,
                     port: "fs".into(),
+                    method: "create_dir_all".into(),
+                    input: json!({"path": "/project/.spec-cache"}),
+                    output: json!(null),
+                },
+                // fs.write — new map
+                Interaction {
+                    seq: 7,
+                    port: "fs".into(),
                     method: "write".into(),
                     input: json!({"path": "/project/.spec-cache/codebase_map.yaml"}),
                     output: json!(null),
@@ -232,9 +379,129 @@ mod tests {
         std::fs::write(&path, &yaml).unwrap();
 
         let ctx = ServiceContext::replaying(&path).unwrap();
-        let result = run_diff(&ctx, std::path::Path::new("/project"));
+        let result =
+            run_diff(&ctx, std::path::Path::new("/project"), &[], &DiffOptions::default(), None);
         assert!(result.is_ok());
 
         let _ = std::fs::remove_dir_all(&dir);
     }
+
+    #[test]
+    fn run_uses_the_given_context() {
+        use crate::adapters::memory::filesystem::MemFileSystem;
+
+        let fs = MemFileSystem::new();
+        let ctx = ServiceContext::testing().with_fs(fs.clone());
+        let out = Path::new("/out/codebase_map.yaml");
+
+        run(&ctx, None, false, Some(out)).unwrap();
+        let against = DiffOptions { against: Some("origin/main".into()), ..DiffOptions::default() };
+        let err = run(&ctx, Some(against), false, Some(out)).unwrap_err();
+
+        assert!(fs.files().lock().unwrap().contains_key(out));
+        assert!(!out.exists());
+        assert_eq!(err, "failed to resolve origin/main: no remote ref origin/main");
+    }
+
+    #[test]
+    fn diff_against_remote_ref_reports_drift_from_upstream() {
+        let call = |seq, port: &str, method: &str, output| Interaction {
+            seq,
+            port: port.into(),
+            method: method.into(),
+            input: json!({}),
+            output,
+        };
+        let cassette = Cassette {
+            name: "map-diff-against".into(),
+            recorded_at: Utc::now(),
+            commit: "new456".into(),
+            interactions: vec![
+                // Upstream tree: `src` exposes only `run`.
+                call(0, "git", "fetch_remote_commit", json!("up789")),
+                call(1, "clock", "now", json!("2025-06-15T10:00:00Z")),
+                call(2, "git", "list_files_at", json!(["src/lib.rs"])),
+                call(3, "git", "read_file_at", json!("pub fn run() {}\n")),
+                // Working tree: `src` also exposes `stop`.
+                call(4, "clock", "now", json!("2025-06-15T10:00:01Z")),
+                call(5, "git", "current_commit", json!("new456")),
+                call(6, "git", "list_files", json!(["src/lib.rs"])),
+                call(7, "fs", "read_to_string", json!("pub fn run() {}\npub fn stop() {}\n")),
+                call(8, "fs", "create_dir_all", json!(null)),
+                call(9, "fs", "write", json!(null)),
+            ],
+        };
+        let dir = std::env::temp_dir().join("speck_map_diff_against");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("against.cassette.yaml");
+        std::fs::write(&path, serde_yaml::to_string(&cassette).unwrap()).unwrap();
+        let ctx = ServiceContext::replaying(&path).unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let root = Path::new("/project");
+        let options = DiffOptions { against: Some("origin/main".into()), ..DiffOptions::default() };
+        let old_map = previous_map(&ctx, root, &options).unwrap();
+        let new_map = generator::generate(&ctx, root, None).unwrap();
+        let spec: TaskSpec = serde_yaml::from_str(
+            "id: TASK-1\ntitle: Stop\ncontext:\n  modules: [src]\nacceptance_criteria: []\n\
+             signal_type: clear\nverification:\n  strategy: direct_assertion\n  checks: []\n",
+        )
+        .unwrap();
+        let report = linkage::detect_drift(&[spec], &old_map, &new_map);
+
+        assert_eq!(old_map.commit_hash, "up789");
+        assert_eq!(old_map.modules[0].public_items, ["fn run"]);
+        assert_eq!((report.old_commit.as_str(), report.new_commit.as_str()), ("up789", "new456"));
+        assert_eq!(report.entries.len(), 1);
+        assert_eq!(report.entries[0].changed_modules, ["src"]);
+    }
+
+    #[test]
+    fn drift_notification_is_not_duplicated() {
+        let drift_issue = json!({
+            "id": "bd-7",
+            "title": DRIFT_ISSUE_TITLE,
+            "body": "Drift detected",
+            "status": "open"
+        });
+        let issues_call = |seq, method: &str, output| Interaction {
+            seq,
+            port: "issues".into(),
+            method: method.into(),
+            input: json!({}),
+            output,
+        };
+        let cassette = Cassette {
+            name: "map-drift-notify".into(),
+            recorded_at: Utc::now(),
+            commit: "new456".into(),
+            interactions: vec![
+                issues_call(0, "list_issues", json!({"Ok": []})),
+                issues_call(1, "create_issue", json!({"Ok": drift_issue.clone()})),
+                issues_call(2, "list_issues", json!({"Ok": [drift_issue]})),
+            ],
+        };
+        let dir = std::env::temp_dir().join("speck_map_drift_notify");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("notify.cassette.yaml");
+        std::fs::write(&path, serde_yaml::to_string(&cassette).unwrap()).unwrap();
+        let ctx = ServiceContext::replaying(&path).unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let report = linkage::DriftReport {
+            entries: vec![linkage::DriftEntry {
+                spec_id: "TASK-1".into(),
+                changed_modules: vec!["src/auth".into()],
+                removed_modules: vec![],
+                replan_recommended: true,
+            }],
+            old_commit: "old123".into(),
+            new_commit: "new456".into(),
+        };
+        let first = notify_drift(ctx.issues.as_ref(), &report).unwrap();
+        let second = notify_drift(ctx.issues.as_ref(), &report).unwrap();
+
+        assert_eq!(first.map(|i| i.id).as_deref(), Some("bd-7"));
+        assert!(second.is_none());
+    }
 }
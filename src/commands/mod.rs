@@ -1,6 +1,10 @@
 //! Command dispatch and handlers.
 
 pub mod deps;
+pub mod diff_specs;
+pub mod edit;
+pub mod gc;
+pub mod import;
 pub mod map;
 pub mod plan;
 pub mod show;
@@ -9,60 +13,257 @@ pub mod sync;
 pub mod validate;
 
 use std::env;
+use std::io::{BufRead, Write as _};
+use std::path::PathBuf;
 
+use crate::cassette::config::CassetteConfig;
 use crate::cassette::session::RecordingSession;
-use crate::cli::Command;
+use crate::cli::{Cli, Command, ValidateFormat};
+use crate::config::SpeckConfig;
 use crate::context::ServiceContext;
+use crate::error::SpeckError;
+use crate::events::{ConsoleSink, EventSink};
 
-/// Dispatch a parsed command to its handler.
+/// Dispatch a parsed command line to its handler.
 ///
 /// When `SPECK_REC=true` is set, all port interactions are recorded to
 /// per-port cassette files in `.speck/cassettes/<timestamp>/`.
+/// `SPECK_REC_SHELL=minimal` records only the exit code and the head and tail
+/// of each shell run's output.
+///
+/// When `--output-dir <DIR>` is given, all port interactions are recorded
+/// directly to `<DIR>/<port>/session.yaml` instead.
 ///
 /// When `SPECK_REPLAY=<path>` is set, all port interactions are replayed
 /// from the given monolithic cassette file.
 ///
+/// When `--port-cassette <port>=<path>` is given (repeatably), each named
+/// port is replayed from its own cassette and every other port panics if
+/// called. This takes precedence over all of the above.
+///
+/// Each of these settings may also come from the config file (`--config
+/// <path>`, defaulting to `.speck/config.toml`). Flags and environment
+/// variables take precedence over the config file.
+///
 /// # Errors
 ///
-/// Returns an error string if the selected command handler fails.
-pub fn dispatch(command: &Command) -> Result<(), String> {
-    let recording_enabled = env::var("SPECK_REC").map(|v| v == "true").unwrap_or(false);
-    let replay_path = env::var("SPECK_REPLAY").ok();
+/// Returns [`SpeckError::Usage`] if the configuration is invalid, the
+/// [`SpeckError::Validation`] raised by `validate` when a check fails,
+/// [`SpeckError::NotFound`] or [`SpeckError::Parse`] for an unknown or
+/// malformed spec, and [`SpeckError::Internal`] for any other failure.
+pub fn dispatch(cli: &Cli) -> Result<(), SpeckError> {
+    dispatch_to(cli, None)
+}
+
+/// Dispatch like [`dispatch`], sending `validate`'s results to `sink`.
+///
+/// # Errors
+///
+/// Same as [`dispatch`].
+pub fn dispatch_with_sink(cli: &Cli, sink: &mut dyn EventSink) -> Result<(), SpeckError> {
+    dispatch_to(cli, Some(sink))
+}
 
-    let (ctx, session) = if let Some(path) = &replay_path {
-        let ctx = ServiceContext::replaying(std::path::Path::new(path))?;
+/// Dispatch `cli`, reporting to `sink` or, without one, to the console.
+fn dispatch_to(cli: &Cli, sink: Option<&mut dyn EventSink>) -> Result<(), SpeckError> {
+    let config = SpeckConfig::resolve(cli.config.as_deref()).map_err(SpeckError::Usage)?;
+    let recording_enabled =
+        env::var("SPECK_REC").map_or(config.record.unwrap_or(false), |v| v == "true");
+    let replay_path = env::var("SPECK_REPLAY").ok().map(PathBuf::from).or(config.replay.clone());
+    let output_dir = cli.output_dir.clone().or(config.output_dir.clone());
+    let shell_mode = match env::var("SPECK_REC_SHELL") {
+        Ok(mode) => mode.parse().map_err(SpeckError::Usage)?,
+        Err(_) => config.record_shell.unwrap_or_default(),
+    };
+
+    let port_config = if cli.port_cassettes.is_empty() {
+        None
+    } else {
+        Some(CassetteConfig::from_ports(&cli.port_cassettes).map_err(SpeckError::Usage)?)
+    };
+    // Under replay, shell runs only succeed if a cassette recorded some.
+    let can_run_shell = match (&port_config, &replay_path) {
+        (Some(ports), _) => ports.shell.as_deref().is_some_and(show::cassette_can_verify),
+        (None, Some(path)) => show::cassette_can_verify(path),
+        (None, None) => true,
+    };
+
+    let (ctx, session) = if let Some(ports) = &port_config {
+        (ServiceContext::replaying_from(ports).map_err(SpeckError::Internal)?, None)
+    } else if let Some(path) = &replay_path {
+        let ctx = ServiceContext::replaying(path).map_err(SpeckError::Internal)?;
         (ctx, None)
+    } else if let Some(dir) = &output_dir {
+        (ServiceContext::recording_per_port(dir, shell_mode), None)
     } else if recording_enabled {
-        let (ctx, session) = ServiceContext::recording()?;
+        let (ctx, session) = ServiceContext::recording(shell_mode).map_err(SpeckError::Internal)?;
         (ctx, Some(session))
     } else {
         (ServiceContext::live(), None)
     };
 
-    let result = dispatch_with_context(command, &ctx);
+    let result = dispatch_with_context(&cli.command, &ctx, &config, can_run_shell, sink);
 
     // Finish recording after command completes (even on error)
     if let Some(session) = session {
         // Drop context first to release Arc references
         drop(ctx);
-        finish_recording(session)?;
+        finish_recording(session).map_err(SpeckError::Internal)?;
+    } else if let (None, None, Some(dir)) = (&port_config, &replay_path, &output_dir) {
+        // Per-port recorders flush when the context is dropped.
+        drop(ctx);
+        eprintln!("Recording saved to: {}", dir.display());
     }
 
     result
 }
 
 /// Dispatch a command with the given service context.
-fn dispatch_with_context(command: &Command, ctx: &ServiceContext) -> Result<(), String> {
+///
+/// `config` supplies defaults for opt-in behaviour such as semantic linkage.
+/// `can_run_shell` is `false` when `ctx` replays a cassette with no recorded
+/// shell runs, so commands that would merely run checks can skip them.
+/// Commands that report events send them to `sink`, or print them when it
+/// is `None`.
+fn dispatch_with_context(
+    command: &Command,
+    ctx: &ServiceContext,
+    config: &SpeckConfig,
+    can_run_shell: bool,
+    sink: Option<&mut dyn EventSink>,
+) -> Result<(), SpeckError> {
+    match command {
+        Command::Plan { revise: None, doc: None, .. } => {
+            Err(SpeckError::Usage("plan requires a spec document".to_string()))
+        }
+        Command::Validate {
+            spec_id,
+            all,
+            bead,
+            spec_stdin,
+            dry_run,
+            require_deps,
+            force,
+            deadline,
+            update_golden,
+            capture_on_pass,
+            include_disabled,
+            json,
+            json_lines,
+            format,
+        } => {
+            let format = match format {
+                Some(ValidateFormat::Json) => validate::OutputFormat::Json,
+                Some(ValidateFormat::JsonLines) => validate::OutputFormat::JsonLines,
+                Some(ValidateFormat::Junit) => validate::OutputFormat::Junit,
+                None if *json_lines => validate::OutputFormat::JsonLines,
+                None if *json => validate::OutputFormat::Json,
+                Some(ValidateFormat::Text) | None => validate::OutputFormat::Text,
+            };
+            let mut console = ConsoleSink::new(format);
+            let sink = sink.unwrap_or(&mut console);
+            if *spec_stdin || spec_id.as_deref() == Some("-") {
+                return validate::run_spec_reader(ctx, &mut std::io::stdin().lock(), sink);
+            }
+            validate::run_with_sink(
+                ctx,
+                spec_id.as_deref(),
+                *all,
+                bead.as_deref(),
+                validate::ValidateOptions {
+                    format,
+                    dry_run: *dry_run,
+                    require_deps: *require_deps,
+                    force: *force,
+                    deadline: deadline.map(std::time::Duration::from_secs),
+                    update_golden: *update_golden,
+                    capture_on_pass: capture_on_pass.as_deref(),
+                    include_disabled: *include_disabled,
+                },
+                None,
+                sink,
+            )
+        }
+        command => run_command(command, ctx, config, can_run_shell),
+    }
+}
+
+/// Run any command other than `validate`.
+///
+/// Handlers that load specs by ID report [`SpeckError`]s directly, so an
+/// unknown ID or malformed spec keeps its category. Handlers that report
+/// plain error strings fail with [`SpeckError::Internal`].
+fn run_command(
+    command: &Command,
+    ctx: &ServiceContext,
+    config: &SpeckConfig,
+    can_run_shell: bool,
+) -> Result<(), SpeckError> {
     match command {
-        Command::Plan { ref doc } => plan::run(ctx, doc),
-        Command::Validate { spec_id, all, bead, json } => {
-            validate::run_with_context(ctx, spec_id.as_deref(), *all, bead.as_deref(), *json, None)
+        Command::Plan { revise: Some(id), doc, .. } => {
+            plan::run_revise(ctx, id, doc.as_deref(), None)
+        }
+        Command::Plan {
+            doc: Some(doc),
+            no_cache,
+            apply_updates,
+            apply_extractions,
+            strict_pushback,
+            timeout,
+            requirement_id,
+            allow_duplicate,
+            ..
+        } => plan::run(
+            ctx,
+            doc,
+            plan::PlanOptions {
+                no_cache: *no_cache,
+                apply_updates: *apply_updates,
+                apply_extractions: *apply_extractions,
+                strict_pushback: *strict_pushback,
+                timeout: timeout.map(std::time::Duration::from_secs),
+                requirement_id: requirement_id.as_deref(),
+                allow_duplicate: *allow_duplicate,
+            },
+        ),
+        Command::Plan { doc: None, .. } | Command::Validate { .. } => {
+            unreachable!("handled by dispatch_with_context")
+        }
+        Command::Map { diff, blame, metrics, notify, out, since, against, include_disabled } => {
+            let diff = diff.then(|| map::DiffOptions {
+                blame: *blame,
+                notify: *notify,
+                since: since.clone(),
+                against: against.clone(),
+                include_disabled: *include_disabled,
+            });
+            map::run(ctx, diff, *metrics, out.as_deref()).map_err(SpeckError::Internal)
+        }
+        Command::Show { history: Some(history), at, diff, .. } => {
+            let diff = diff.as_deref().map(|d| (d[0].as_str(), d[1].as_str()));
+            show::run_history(history, at.as_deref(), diff)
         }
-        Command::Map { diff } => map::run(*diff),
-        Command::Show { id } => show::run(id.as_deref()),
-        Command::Status => status::run(),
-        Command::Deps => deps::run(),
-        Command::Sync { target, dry_run } => sync::run_with_context(ctx, target, *dry_run, None),
+        Command::Show { id: Some(id), links: true, semantic, .. } => {
+            let semantic = *semantic || config.semantic_linkage.unwrap_or(false);
+            show::run_links(ctx, id, semantic, None)
+        }
+        Command::Show { id: Some(id), verify: true, .. } => {
+            show::run_verify(ctx, id, can_run_shell, None)
+        }
+        Command::Show { id, .. } => show::run(id.as_deref()),
+        Command::Edit { id, criteria, reclassify, yes } => {
+            edit::run(ctx, id, criteria, *reclassify, *yes)
+        }
+        Command::Status => status::run().map_err(SpeckError::Internal),
+        Command::Deps { summary } => deps::run(*summary).map_err(SpeckError::Internal),
+        Command::Sync { target, dry_run, reconcile_first, include_disabled } => {
+            sync::run_with_context(ctx, target, *dry_run, *reconcile_first, *include_disabled, None)
+        }
+        Command::DiffSpecs { a, b } => diff_specs::run(ctx, a, b),
+        Command::Gc { dry_run, max_age_days } => {
+            gc::run(ctx, *dry_run, *max_age_days).map_err(SpeckError::Internal)
+        }
+        Command::Import { file } => import::run(ctx, file).map_err(SpeckError::Internal),
     }
 }
 
@@ -72,3 +273,17 @@ fn finish_recording(session: RecordingSession) -> Result<(), String> {
     eprintln!("Recording saved to: {}", output_dir.display());
     Ok(())
 }
+
+/// Ask a yes/no question on stdout and read the answer from `input`.
+///
+/// Anything other than `y` or `yes` (case-insensitive), including a read
+/// error or end of input, counts as no.
+fn prompt_yes_no(question: &str, input: &mut dyn BufRead) -> bool {
+    print!("{question} [y/N] ");
+    let _ = std::io::stdout().flush();
+    let mut answer = String::new();
+    if input.read_line(&mut answer).is_err() {
+        return false;
+    }
+    matches!(answer.trim().to_ascii_lowercase().as_str(), "y" | "yes")
+}
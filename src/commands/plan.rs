@@ -1,58 +1,495 @@
 //! `speck plan` command.
 
+use std::cell::Cell;
 use std::fmt::Write as _;
+use std::io::{BufRead, IsTerminal as _};
 use std::path::Path;
 
+use crate::adapters::middleware::llm::CachingLlmClient;
 use crate::context::ServiceContext;
+use crate::error::SpeckError;
 use crate::linkage;
+use crate::map::CodebaseMap;
 use crate::plan::conversation::{self, AnalysisResult, DecompositionResult};
-use crate::plan::reconcile::{self, PlanDiff, ReconciliationResult, SpecMatchAction};
+use crate::plan::llm_cache;
+use crate::plan::reconcile::{
+    self, ExtractionSuggestion, PlanDiff, ReconciliationResult, SpecMatchAction,
+};
 use crate::plan::score::{self, ScoreResult};
 use crate::plan::signal::{
     self, ClassificationResult, PlanCheck, SignalType as PlanSignalType,
     VerificationStrategy as PlanVerificationStrategy,
 };
-use crate::plan::survey::{broad_survey, SurveyResult};
-use crate::spec::{SignalType, TaskSpec, VerificationCheck, VerificationStrategy};
+use crate::plan::similarity::{SimilarityScorer, TokenJaccard, MERGE_THRESHOLD};
+use crate::plan::survey::{self, SurveyResult};
+use crate::spec::{SignalType, SpecDiff, TaskSpec, VerificationCheck, VerificationStrategy};
 use crate::store::SpecStore;
+use crate::validate::Deadline;
+
+use super::show::format_diff;
+
+/// Classifications below this confidence are flagged for pushback.
+const LOW_CONFIDENCE_THRESHOLD: f64 = 0.5;
+
+/// How `plan` runs its passes and persists the result.
+#[derive(Debug, Clone, Copy, Default)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct PlanOptions<'a> {
+    /// Regenerate the codebase map instead of reusing the cached one.
+    pub no_cache: bool,
+    /// Apply LLM-proposed spec updates without previewing and confirming each.
+    pub apply_updates: bool,
+    /// Create the foundational specs reconciliation suggests extracting.
+    pub apply_extractions: bool,
+    /// Fail instead of warning when a requirement is too vague to classify.
+    pub strict_pushback: bool,
+    /// Wall-clock budget for the whole run.
+    pub timeout: Option<std::time::Duration>,
+    /// Requirement to link the planned specs to.
+    pub requirement_id: Option<&'a str>,
+    /// Plan new specs even when a stored spec looks like a duplicate.
+    pub allow_duplicate: bool,
+}
 
 /// Execute the `plan` command.
 ///
 /// Reads a spec document from `doc_path`, then runs all analysis passes
 /// non-interactively: survey, signal classification, spec analysis, and
 /// reconciliation. Writes derived `TaskSpec`s to `.speck/tasks/` and prints
-/// structured feedback to stdout.
+/// structured feedback to stdout. With `no_cache`, the codebase map is
+/// regenerated even when a cached map matches the current commit. Each spec
+/// update proposed during analysis is previewed and applied only if the user
+/// confirms it on stdin; without an answer it is skipped. With
+/// `apply_updates`, proposed updates are applied without asking. With
+/// `apply_extractions`, each shared abstraction reconciliation suggests is
+/// saved as a foundational spec that the tasks sharing it depend on.
+///
+/// With `timeout`, the whole run is bounded by a budget measured on
+/// `ctx.clock`. When it runs out, the in-flight pass is cancelled, nothing is
+/// persisted, and a "planning timed out" notice follows the partial result:
+/// whatever finished passes (such as the survey and decomposition) printed.
+/// A timed-out run still succeeds.
+///
+/// With `strict_pushback`, a requirement the classifier finds under-specified
+/// fails the run with the classifier's reason instead of producing an empty
+/// spec with a warning; nothing is persisted.
+///
+/// Unless `requirement_id` is given, stored specs are checked before any LLM
+/// call for one whose requirement or title closely matches the document's
+/// title or headings; if one does, the user chooses on stdin to revise that
+/// spec instead (as `plan --revise`), create new specs anyway, or abort.
+/// When stdin is not a terminal the run fails instead of guessing, unless
+/// `allow_duplicate` is set.
+///
+/// With `requirement_id`, every generated spec is linked to that requirement
+/// and matched only against the specs already linked to it, so re-planning a
+/// grown requirement extends its spec set instead of starting a new one.
 ///
 /// # Errors
 ///
-/// Returns an error string if reading the doc fails, any analysis pass fails,
-/// or spec persistence fails.
-pub fn run(ctx: &ServiceContext, doc_path: &Path) -> Result<(), String> {
+/// Returns [`SpeckError::Usage`] if a likely duplicate is found with no
+/// terminal to confirm on, and an error if reading the doc fails, any
+/// analysis pass fails, or spec persistence fails.
+pub fn run(
+    ctx: &ServiceContext,
+    doc_path: &Path,
+    options: PlanOptions<'_>,
+) -> Result<(), SpeckError> {
+    let PlanOptions { timeout, requirement_id, allow_duplicate, .. } = options;
     let requirement_text = std::fs::read_to_string(doc_path)
         .map_err(|e| format!("failed to read spec document '{}': {e}", doc_path.display()))?;
 
-    let root =
-        std::env::current_dir().map_err(|e| format!("failed to get current directory: {e}"))?;
+    if let Some(rid) = requirement_id {
+        validate_requirement_id(rid)?;
+    }
+
+    let store_root = store_root()?;
+    let store = SpecStore::new(ctx, &store_root);
+    let existing_specs = load_existing_specs(&store);
+    if let Some(rid) = requirement_id {
+        let linked = existing_specs.iter().any(|s| s.requirement.as_deref() == Some(rid));
+        if !linked && !store.has_requirement(rid) {
+            println!("Requirement {rid} is new to the store; its specs will start a new set.");
+        }
+    } else {
+        let stdin = std::io::stdin();
+        let interactive = stdin.is_terminal();
+        let mut input = stdin.lock();
+        let input = interactive.then_some(&mut input as &mut dyn BufRead);
+        match check_for_duplicate(&existing_specs, &requirement_text, allow_duplicate, input)? {
+            DuplicateChoice::Create => {}
+            DuplicateChoice::Revise(id) => return run_revise(ctx, &id, Some(doc_path), None),
+            DuplicateChoice::Abort => {
+                println!("Aborted; no specs were saved.");
+                return Ok(());
+            }
+        }
+    }
 
     let rt = tokio::runtime::Builder::new_current_thread()
         .enable_all()
         .build()
         .map_err(|e| format!("failed to create async runtime: {e}"))?;
+    let budget = Budget::start(ctx, timeout);
+    let plan = Planner { ctx, rt: &rt, budget: &budget, store: &store, store_root: &store_root };
+
+    match plan.run_passes(&requirement_text, existing_specs, options) {
+        Err(_) if budget.timed_out.get() => {
+            println!("\n{}", budget.timed_out_message());
+            Ok(())
+        }
+        result => result.map_err(SpeckError::from),
+    }
+}
+
+/// What the planning passes of one `plan` run share.
+struct Planner<'a> {
+    ctx: &'a ServiceContext,
+    rt: &'a tokio::runtime::Runtime,
+    budget: &'a Budget<'a>,
+    store: &'a SpecStore<'a>,
+    store_root: &'a Path,
+}
+
+impl Planner<'_> {
+    /// Run every pass from scoring through persistence, printing as each finishes.
+    fn run_passes(
+        &self,
+        requirement_text: &str,
+        existing_specs: Vec<TaskSpec>,
+        options: PlanOptions<'_>,
+    ) -> Result<(), String> {
+        let Self { ctx, rt, budget, store, store_root } = *self;
+        let root =
+            std::env::current_dir().map_err(|e| format!("failed to get current directory: {e}"))?;
+
+        // Pass 0: Score the document for specificity and verifiability
+        let score_result = budget
+            .block_on(rt, score::score_document(ctx.llm.as_ref(), requirement_text))?
+            .map_err(|e| format!("document scoring failed: {e}"))?;
+
+        // Pass 1 and 1.5: Broad codebase survey and PRD decomposition
+        let (survey, codebase_map, decomposition) =
+            survey_and_decompose(ctx, rt, budget, &root, requirement_text, options.no_cache)?;
+
+        // Pass 2: Signal classification (per-item)
+        let codebase_context = build_codebase_context(&survey);
+        let mut specs = Vec::with_capacity(decomposition.items.len());
+
+        for (i, prd_item) in decomposition.items.iter().enumerate() {
+            let spec = classify_item(
+                ctx,
+                rt,
+                budget,
+                i,
+                prd_item,
+                &codebase_context,
+                options.strict_pushback,
+            )?;
+            specs.push(spec);
+        }
 
-    // Pass 0: Score the document for specificity and verifiability
-    let score_result = rt
-        .block_on(score::score_document(ctx.llm.as_ref(), &requirement_text))
-        .map_err(|e| format!("document scoring failed: {e}"))?;
+        // Wire up inter-spec dependencies from the decomposition
+        wire_dependencies(&mut specs, &decomposition);
+
+        // Pass 2.5: Glob derivation from survey routing table + linkage resolution
+        let glob_warnings = assign_affected_globs(&mut specs, &survey, &codebase_map);
+
+        // Pass 2.5a: Single-pass spec analysis (non-interactive feedback)
+        let analysis = budget
+            .block_on(rt, conversation::analyze_specs(ctx, &specs))?
+            .map_err(|e| format!("spec analysis failed: {e}"))?;
+        let stdin = std::io::stdin();
+        let interactive = stdin.is_terminal();
+        let mut input = stdin.lock();
+        let input = interactive.then_some(&mut input as &mut dyn BufRead);
+        apply_analysis_updates(&mut specs, &analysis, options.apply_updates, input);
+
+        // IDs are assigned first so reconciliation can name the specs.
+        let diff = assign_ids(ctx, &mut specs, existing_specs, options.requirement_id);
+
+        // Pass 2.5b: Reconciliation
+        let reconciliation = budget
+            .block_on(rt, reconcile::reconcile(ctx, &specs, &TokenJaccard))?
+            .map_err(|e| format!("reconciliation failed: {e}"))?;
+
+        let requirement = options.requirement_id.map(|rid| (rid, requirement_text));
+        save_plan(store, &specs, requirement)?;
+
+        // Print structured output
+        print_structured_output(
+            &specs,
+            &diff,
+            &analysis,
+            &reconciliation,
+            &score_result,
+            store_root,
+            &glob_warnings,
+        );
+
+        if options.apply_extractions {
+            apply_extractions(ctx, store, &reconciliation.suggested_extractions);
+        }
 
-    // Pass 1: Broad codebase survey (also returns the codebase map for reuse)
-    let (survey, codebase_map) = rt.block_on(broad_survey(ctx, &root, &requirement_text))?;
+        Ok(())
+    }
+}
+
+/// Survey the codebase (pass 1) and decompose the document into items (pass 1.5).
+///
+/// Both LLM calls are cached on the codebase map's commit and saved under
+/// `.spec-cache/` (see [`llm_cache`]), so re-planning at an unchanged commit
+/// does not call the model again. With `no_cache`, saved responses are
+/// ignored along with the cached map.
+fn survey_and_decompose(
+    ctx: &ServiceContext,
+    rt: &tokio::runtime::Runtime,
+    budget: &Budget<'_>,
+    root: &Path,
+    requirement_text: &str,
+    no_cache: bool,
+) -> Result<(SurveyResult, CodebaseMap, DecompositionResult), String> {
+    let map = budget.block_on(rt, survey::survey_map(ctx, root, no_cache))??;
+    let cache = if no_cache {
+        CachingLlmClient::new(Box::new(ctx.llm.as_ref()))
+    } else {
+        llm_cache::load(ctx, root, &map.commit_hash)
+    };
+    let llm = cache.at_commit(&map.commit_hash);
+
+    let survey = budget.block_on(rt, survey::survey_codebase(&llm, &map, requirement_text))??;
     print_survey_result(&survey);
 
-    // Pass 1.5: Decompose PRD into individual requirement items
-    let decomposition = rt
-        .block_on(conversation::decompose_prd(ctx, &requirement_text))
+    let decomposition = budget
+        .block_on(rt, conversation::decompose_prd(&llm, requirement_text))?
         .map_err(|e| format!("PRD decomposition failed: {e}"))?;
+    print_decomposition(&decomposition);
+
+    if let Err(e) = llm_cache::save(ctx, root, &map.commit_hash, &cache) {
+        eprintln!("Warning: {e}");
+    }
+    Ok((survey, map, decomposition))
+}
+
+/// How to proceed when a stored spec already covers the requirement being planned.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum DuplicateChoice {
+    /// Revise the stored spec with this ID instead of planning new ones.
+    Revise(String),
+    /// Plan new specs anyway.
+    Create,
+    /// Stop without planning.
+    Abort,
+}
+
+/// Look for a stored spec similar to `requirement` and ask how to proceed.
+///
+/// Returns [`DuplicateChoice::Create`] without prompting when no stored spec
+/// is similar enough or `allow_duplicate` is set. Otherwise the answer is
+/// read from `input`; anything but `r` or `c`, including end of input,
+/// aborts.
+///
+/// # Errors
+///
+/// Returns [`SpeckError::Usage`] when a similar spec exists but there is no
+/// `input` (stdin is not a terminal) to ask on.
+fn check_for_duplicate(
+    existing: &[TaskSpec],
+    requirement: &str,
+    allow_duplicate: bool,
+    input: Option<&mut dyn BufRead>,
+) -> Result<DuplicateChoice, SpeckError> {
+    let Some(id) = find_similar_spec(existing, requirement, &TokenJaccard) else {
+        return Ok(DuplicateChoice::Create);
+    };
+    if allow_duplicate {
+        return Ok(DuplicateChoice::Create);
+    }
+    let Some(input) = input else {
+        return Err(SpeckError::Usage(format!(
+            "A similar spec {id} exists and stdin is not a terminal to confirm on. \
+             Re-run with --allow-duplicate to plan new specs anyway, or --revise {id}."
+        )));
+    };
+    print!("A similar spec {id} exists. Revise it (r), create new (c), or abort (a)? ");
+    let _ = std::io::Write::flush(&mut std::io::stdout());
+    let mut answer = String::new();
+    if input.read_line(&mut answer).is_err() {
+        return Ok(DuplicateChoice::Abort);
+    }
+    Ok(match answer.trim().to_ascii_lowercase().as_str() {
+        "r" | "revise" => DuplicateChoice::Revise(id),
+        "c" | "create" => DuplicateChoice::Create,
+        _ => DuplicateChoice::Abort,
+    })
+}
+
+/// The ID of the spec in `existing` most similar to `requirement`, if any
+/// scores at or above [`MERGE_THRESHOLD`].
+///
+/// A whole document shares too few words with a one-line spec title to ever
+/// score highly, so only the document's title lines (see [`title_lines`])
+/// are compared. Each is scored against each spec's requirement and title,
+/// and the best score counts.
+fn find_similar_spec(
+    existing: &[TaskSpec],
+    requirement: &str,
+    scorer: &dyn SimilarityScorer,
+) -> Option<String> {
+    // Scorers compare acceptance criteria, so wrap each text as a lone criterion.
+    let probe = |spec: &TaskSpec, text: &str| TaskSpec {
+        acceptance_criteria: vec![text.to_string()],
+        ..spec.clone()
+    };
+    let titles = title_lines(requirement);
+    let mut best: Option<(&str, f64)> = None;
+    for spec in existing {
+        let stored: Vec<TaskSpec> = [spec.requirement.as_deref(), Some(spec.title.as_str())]
+            .into_iter()
+            .flatten()
+            .map(|text| probe(spec, text))
+            .collect();
+        let score = titles
+            .iter()
+            .flat_map(|title| {
+                let wanted = probe(spec, title);
+                stored.iter().map(move |s| scorer.score(&wanted, s))
+            })
+            .fold(0.0, f64::max);
+        if score >= MERGE_THRESHOLD && best.is_none_or(|(_, b)| score > b) {
+            best = Some((&spec.id, score));
+        }
+    }
+    best.map(|(id, _)| id.to_string())
+}
+
+/// The lines of a requirement document that name what it is about: its first
+/// non-empty line and every Markdown heading, with `#` markers stripped.
+fn title_lines(document: &str) -> Vec<&str> {
+    let mut lines = document.lines().map(str::trim).filter(|line| !line.is_empty());
+    let first = lines.next();
+    first
+        .into_iter()
+        .chain(lines.filter(|line| line.starts_with('#')))
+        .map(|line| line.trim_start_matches('#').trim())
+        .filter(|line| !line.is_empty())
+        .collect()
+}
+
+/// Every spec in the store; specs that fail to load are skipped.
+fn load_existing_specs(store: &SpecStore<'_>) -> Vec<TaskSpec> {
+    let ids = store.list_task_specs().unwrap_or_default();
+    ids.iter().filter_map(|id| store.load_task_spec(id).ok()).collect()
+}
+
+/// Match planned specs to the store, then assign their IDs and provenance.
+///
+/// Specs are matched by title against every spec in `existing_specs`, or, with
+/// a `requirement_id`, only against the specs linked to that requirement;
+/// those unmatched by title are then folded into similar linked specs, so that
+/// re-planning a requirement merges into its spec set rather than duplicating
+/// it. Fresh IDs are drawn only for specs that end up new. Nothing is saved;
+/// see [`save_plan`].
+fn assign_ids(
+    ctx: &ServiceContext,
+    specs: &mut [TaskSpec],
+    mut existing_specs: Vec<TaskSpec>,
+    requirement_id: Option<&str>,
+) -> PlanDiff {
+    // Match new specs to existing ones (assigns IDs in-place).
+    let diff = if let Some(rid) = requirement_id {
+        for spec in specs.iter_mut() {
+            spec.requirement = Some(rid.to_string());
+        }
+        existing_specs.retain(|s| s.requirement.as_deref() == Some(rid));
+        reconcile::match_and_merge_existing(
+            specs,
+            &existing_specs,
+            ctx.id_gen.as_ref(),
+            &TokenJaccard,
+        )
+    } else {
+        reconcile::match_to_existing(specs, &existing_specs, ctx.id_gen.as_ref())
+    };
+
+    // Resolve positional dependency markers to real spec IDs.
+    resolve_positional_deps(specs);
+
+    // Record which commit new specs were planned against.
+    stamp_provenance(ctx, specs);
+    diff
+}
+
+/// Save planned specs and, with a `requirement` (its ID and document), the
+/// requirement itself to the store's `requirements/`.
+fn save_plan(
+    store: &SpecStore<'_>,
+    specs: &[TaskSpec],
+    requirement: Option<(&str, &str)>,
+) -> Result<(), String> {
+    if let Some((rid, document)) = requirement {
+        let record = serde_yaml::to_string(&RequirementRecord { id: rid, document })
+            .map_err(|e| format!("Failed to serialize requirement {rid}: {e}"))?;
+        store.save_requirement(rid, &record)?;
+    }
+    for spec in specs {
+        store.save_task_spec(spec)?;
+    }
+    Ok(())
+}
 
+/// Apply each suggested extraction to the saved plan, printing the outcome.
+///
+/// An extraction that cannot be applied (say, it names a task that was never
+/// saved, or would create a dependency cycle) is reported and skipped.
+fn apply_extractions(
+    ctx: &ServiceContext,
+    store: &SpecStore<'_>,
+    extractions: &[ExtractionSuggestion],
+) {
+    if extractions.is_empty() {
+        return;
+    }
+    println!("\n=== Applied Extractions ===");
+    for extraction in extractions {
+        match reconcile::apply_extraction(ctx, store, extraction) {
+            Ok(id) => println!(
+                "  {id} \"{}\" <- {}",
+                extraction.suggested_task_title,
+                extraction.task_ids.join(", ")
+            ),
+            Err(e) => eprintln!(
+                "Warning: skipped extraction \"{}\": {e}",
+                extraction.suggested_task_title
+            ),
+        }
+    }
+}
+
+/// A requirement as saved under the store's `requirements/`.
+#[derive(serde::Serialize)]
+struct RequirementRecord<'a> {
+    id: &'a str,
+    document: &'a str,
+}
+
+/// Check that `id` can name a requirement: letters, digits, `-`, `_`, and
+/// `.`, not starting with `.`, since it becomes a file name in the store.
+fn validate_requirement_id(id: &str) -> Result<(), SpeckError> {
+    let valid = !id.is_empty()
+        && !id.starts_with('.')
+        && id.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if valid {
+        Ok(())
+    } else {
+        Err(SpeckError::Usage(format!(
+            "invalid requirement ID '{id}': use letters, digits, '-', '_', or '.'"
+        )))
+    }
+}
+
+/// Print the numbered PRD items and their positional dependencies.
+fn print_decomposition(decomposition: &DecompositionResult) {
     println!("\n=== PRD Decomposition ({} item(s)) ===", decomposition.items.len());
     for (i, item) in decomposition.items.iter().enumerate() {
         println!("{}. {}", i + 1, item.title);
@@ -62,51 +499,186 @@ pub fn run(ctx: &ServiceContext, doc_path: &Path) -> Result<(), String> {
             println!("   Depends on: {}", dep_labels.join(", "));
         }
     }
+}
 
-    // Pass 2: Signal classification (per-item)
-    let codebase_context = build_codebase_context(&survey);
-    let mut specs = Vec::with_capacity(decomposition.items.len());
+/// How often an in-flight pass checks whether the planning budget has run out.
+const BUDGET_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(50);
+
+/// Wall-clock budget for a `plan` run, measured on the `Clock` port's
+/// monotonic reading so replays and tests see the same time as the budget.
+struct Budget<'a> {
+    ctx: &'a ServiceContext,
+    timeout: Option<std::time::Duration>,
+    deadline: Option<Deadline>,
+    /// Set once a pass has been cancelled because the budget ran out.
+    timed_out: Cell<bool>,
+}
 
-    for (i, prd_item) in decomposition.items.iter().enumerate() {
-        let classification = rt
-            .block_on(signal::classify(ctx.llm.as_ref(), &prd_item.requirement, &codebase_context))
-            .map_err(|e| format!("signal classification failed for item {}: {e}", i + 1))?;
+impl<'a> Budget<'a> {
+    /// Start the budget now; `None` means unbounded.
+    fn start(ctx: &'a ServiceContext, timeout: Option<std::time::Duration>) -> Self {
+        let deadline = timeout.map(|t| Deadline::after(ctx, t));
+        Self { ctx, timeout, deadline, timed_out: Cell::new(false) }
+    }
 
-        let task_spec = match classification {
-            ClassificationResult::Classified { signal_type, strategy } => {
-                let mut spec = build_task_spec(&prd_item.requirement, &signal_type, strategy);
-                spec.title.clone_from(&prd_item.title);
-                spec
-            }
-            ClassificationResult::PushbackRequired { reason } => {
-                eprintln!("Note: pushback required for item {} — {reason}", i + 1);
-                TaskSpec {
-                    id: String::new(),
-                    title: prd_item.title.clone(),
-                    requirement: Some(prd_item.requirement.clone()),
-                    context: None,
-                    acceptance_criteria: vec![],
-                    signal_type: SignalType::Fuzzy,
-                    verification: VerificationStrategy::DirectAssertion { checks: vec![] },
-                    affected_globs: None,
-                }
+    /// Drive `future` to completion, cancelling it once the budget runs out.
+    ///
+    /// The clock is checked every [`BUDGET_POLL_INTERVAL`] while `future` is
+    /// pending.
+    fn block_on<F: std::future::Future>(
+        &self,
+        rt: &tokio::runtime::Runtime,
+        future: F,
+    ) -> Result<F::Output, String> {
+        let Some(deadline) = self.deadline else {
+            return Ok(rt.block_on(future));
+        };
+        let expired = async {
+            while !deadline.exceeded(self.ctx) {
+                tokio::time::sleep(BUDGET_POLL_INTERVAL).await;
             }
         };
-        print_classification(&task_spec);
-        specs.push(task_spec);
+        let output = rt.block_on(async {
+            tokio::select! {
+                biased;
+                output = future => Some(output),
+                () = expired => None,
+            }
+        });
+        output.ok_or_else(|| {
+            self.timed_out.set(true);
+            self.timed_out_message()
+        })
     }
 
-    // Wire up inter-spec dependencies from the decomposition
-    wire_dependencies(&mut specs, &decomposition);
+    fn timed_out_message(&self) -> String {
+        let secs = self.timeout.unwrap_or_default().as_secs_f64();
+        format!("Planning timed out after {secs}s; remaining passes were skipped and no specs were saved.")
+    }
+}
 
-    // Pass 2.5: Glob derivation from survey routing table + linkage resolution
+/// Execute `plan --revise`: re-classify an existing spec and update it in place.
+///
+/// The requirement is read from `doc_path` when given, otherwise taken from
+/// the spec's stored requirement. It is classified together with the spec's
+/// current title and acceptance criteria; the resulting signal type and
+/// verification strategy replace the old ones, while the ID, context
+/// (including dependencies), and provenance are kept. The previous version
+/// is saved as a history snapshot before the revised spec is written.
+///
+/// # Errors
+///
+/// Returns an error if the spec or document cannot be read, the
+/// classifier asks for pushback, or persistence fails.
+pub fn run_revise(
+    ctx: &ServiceContext,
+    id: &str,
+    doc_path: Option<&Path>,
+    override_root: Option<&Path>,
+) -> Result<(), SpeckError> {
+    let root = match override_root {
+        Some(root) => root.to_path_buf(),
+        None => store_root()?,
+    };
+    let store = SpecStore::new(ctx, &root);
+    let previous = store.load_task_spec(id)?;
+
+    let requirement = match doc_path {
+        Some(path) => Some(
+            std::fs::read_to_string(path)
+                .map_err(|e| format!("failed to read spec document '{}': {e}", path.display()))?,
+        ),
+        None => previous.requirement.clone(),
+    };
+    let (current, codebase_context) = super::edit::classification_input(&previous);
+    let input = match &requirement {
+        Some(requirement) => format!("{requirement}\n\nCurrent spec:\n{current}"),
+        None => current,
+    };
+
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| format!("failed to create async runtime: {e}"))?;
+    let classification = rt
+        .block_on(signal::classify(ctx.llm.as_ref(), &input, &codebase_context))
+        .map_err(|e| format!("signal classification failed for {id}: {e}"))?;
+
+    let mut revised = previous.clone();
+    match classification {
+        ClassificationResult::Classified { signal_type, strategy, confidence, .. } => {
+            revised.signal_type = map_signal_type(&signal_type);
+            revised.verification = map_verification_strategy(strategy);
+            print_classification(&revised, confidence);
+        }
+        ClassificationResult::PushbackRequired { reason } => {
+            return Err(
+                format!("pushback required for {id} — {reason}; spec left unchanged").into()
+            );
+        }
+    }
+    revised.requirement = requirement;
+
+    store.save_history_snapshot(&previous, ctx.clock.now())?;
+    store.save_task_spec(&revised)?;
+    println!("\nRevised {id}:\n{}", format_diff(&previous.diff(&revised)));
+    Ok(())
+}
+
+/// Apply the spec updates proposed during analysis that the user accepts on
+/// `input`, or every one of them without asking if `apply_all`.
+///
+/// Without `input` (stdin is not a terminal) and without `apply_all`, no
+/// update is applied and a note says how many were skipped.
+fn apply_analysis_updates(
+    specs: &mut [TaskSpec],
+    analysis: &AnalysisResult,
+    apply_all: bool,
+    mut input: Option<&mut dyn BufRead>,
+) {
+    let mut skipped = 0;
+    let applied = conversation::apply_llm_updates(specs, &analysis.updates, &mut |spec, diff| {
+        if apply_all {
+            return true;
+        }
+        if let Some(input) = input.as_deref_mut() {
+            return confirm_update(spec, diff, input);
+        }
+        skipped += 1;
+        false
+    });
+    if applied > 0 {
+        println!("Applied {applied} LLM-proposed spec update(s).");
+    }
+    if skipped > 0 {
+        println!(
+            "Skipped {skipped} LLM-proposed spec update(s): stdin is not a terminal to confirm \
+             on. Re-run with --apply-updates to apply them."
+        );
+    }
+}
+
+/// Preview a proposed spec update and ask whether to apply it.
+fn confirm_update(spec: &TaskSpec, diff: &SpecDiff, input: &mut dyn BufRead) -> bool {
+    let label = if spec.id.is_empty() { &spec.title } else { &spec.id };
+    println!("\nProposed update for {label}:\n{}", format_diff(diff));
+    super::prompt_yes_no("Apply this update?", input)
+}
+
+/// Derive `affected_globs` for each spec from the survey routing table and
+/// linkage resolution. Returns warnings for unresolved module references.
+fn assign_affected_globs(
+    specs: &mut [TaskSpec],
+    survey: &SurveyResult,
+    codebase_map: &CodebaseMap,
+) -> Vec<String> {
     let mut glob_warnings: Vec<String> = Vec::new();
-    let survey_globs = derive_globs_from_survey(&survey);
-    for spec in &mut specs {
+    let survey_globs = derive_globs_from_survey(survey);
+    for spec in specs {
         let mut globs = survey_globs.clone();
 
         // Also attempt linkage resolution for any context modules on the spec.
-        let linkage_result = linkage::resolve(spec, &codebase_map);
+        let linkage_result = linkage::resolve(spec, codebase_map);
         let (linkage_globs, unresolved) = linkage::derive_globs(&linkage_result);
         for module_ref in &unresolved {
             glob_warnings.push(format!(
@@ -122,47 +694,7 @@ pub fn run(ctx: &ServiceContext, doc_path: &Path) -> Result<(), String> {
 
         spec.affected_globs = Some(globs);
     }
-
-    // Pass 2.5a: Single-pass spec analysis (non-interactive feedback)
-    let analysis = rt
-        .block_on(conversation::analyze_specs(ctx, &specs))
-        .map_err(|e| format!("spec analysis failed: {e}"))?;
-
-    // Pass 2.5b: Reconciliation
-    let reconciliation = rt
-        .block_on(reconcile::reconcile(ctx, &specs))
-        .map_err(|e| format!("reconciliation failed: {e}"))?;
-
-    // Load existing specs for idempotent re-plan matching.
-    let store_root = store_root()?;
-    let store = SpecStore::new(ctx, &store_root);
-    let existing_ids = store.list_task_specs().unwrap_or_default();
-    let existing_specs: Vec<_> =
-        existing_ids.iter().filter_map(|id| store.load_task_spec(id).ok()).collect();
-
-    // Match new specs to existing ones (assigns IDs in-place).
-    let diff = reconcile::match_to_existing(&mut specs, &existing_specs, ctx.id_gen.as_ref());
-
-    // Resolve positional dependency markers to real spec IDs.
-    resolve_positional_deps(&mut specs);
-
-    // Persist final specs to the store.
-    for spec in &specs {
-        store.save_task_spec(spec)?;
-    }
-
-    // Print structured output
-    print_structured_output(
-        &specs,
-        &diff,
-        &analysis,
-        &reconciliation,
-        &score_result,
-        &store_root,
-        &glob_warnings,
-    );
-
-    Ok(())
+    glob_warnings
 }
 
 /// Print the full structured output suitable for LLM consumption.
@@ -378,7 +910,7 @@ fn build_codebase_context(survey: &SurveyResult) -> String {
 }
 
 /// Map a plan signal type to a spec signal type.
-fn map_signal_type(plan_signal: &PlanSignalType) -> SignalType {
+pub(super) fn map_signal_type(plan_signal: &PlanSignalType) -> SignalType {
     match plan_signal {
         PlanSignalType::Clear => SignalType::Clear,
         PlanSignalType::FuzzyButConstrainable => SignalType::Fuzzy,
@@ -389,22 +921,35 @@ fn map_signal_type(plan_signal: &PlanSignalType) -> SignalType {
 /// Convert a plan-level `PlanCheck` to a spec-level `VerificationCheck`.
 fn plan_check_to_verification(check: PlanCheck) -> VerificationCheck {
     match check {
-        PlanCheck::CommandOutput { command, expected } => {
-            VerificationCheck::CommandOutput { command, expected }
-        }
-        PlanCheck::TestSuite { command, expected } => {
-            VerificationCheck::TestSuite { command, expected }
-        }
-        PlanCheck::Custom { description } => VerificationCheck::Custom { description },
+        PlanCheck::CommandOutput { command, expected } => VerificationCheck::CommandOutput {
+            command,
+            expected,
+            retries: 0,
+            retry_backoff_ms: 0,
+            name: None,
+        },
+        PlanCheck::TestSuite { command, expected } => VerificationCheck::TestSuite {
+            command,
+            expected,
+            retries: 0,
+            retry_backoff_ms: 0,
+            name: None,
+        },
+        PlanCheck::Custom { description } => VerificationCheck::Custom { description, name: None },
     }
 }
 
 /// Map a plan verification strategy to a spec verification strategy.
-fn map_verification_strategy(plan_strategy: PlanVerificationStrategy) -> VerificationStrategy {
+pub(super) fn map_verification_strategy(
+    plan_strategy: PlanVerificationStrategy,
+) -> VerificationStrategy {
     match plan_strategy {
         PlanVerificationStrategy::DirectAssertion { checks } => {
             VerificationStrategy::DirectAssertion {
                 checks: checks.into_iter().map(plan_check_to_verification).collect(),
+                groups: vec![],
+                setup: vec![],
+                teardown: vec![],
             }
         }
         PlanVerificationStrategy::StructuralDecomposition { sub_assertions } => {
@@ -414,10 +959,14 @@ fn map_verification_strategy(plan_strategy: PlanVerificationStrategy) -> Verific
                     .map(|sa| match sa.check {
                         PlanCheck::Custom { description } => VerificationCheck::Custom {
                             description: format!("{}: {}", sa.description, description),
+                            name: None,
                         },
                         other => plan_check_to_verification(other),
                     })
                     .collect(),
+                groups: vec![],
+                setup: vec![],
+                teardown: vec![],
             }
         }
         PlanVerificationStrategy::RefactorToExpose { description } => {
@@ -437,6 +986,77 @@ fn map_verification_strategy(plan_strategy: PlanVerificationStrategy) -> Verific
     }
 }
 
+/// Classify one decomposed PRD item (the `i`th) and build its spec.
+///
+/// A classified item gets the verification strategy and acceptance criteria
+/// proposed by the LLM; an item needing pushback gets an empty fuzzy spec,
+/// or is an error under `strict_pushback`.
+fn classify_item(
+    ctx: &ServiceContext,
+    rt: &tokio::runtime::Runtime,
+    budget: &Budget<'_>,
+    i: usize,
+    prd_item: &conversation::PrdItem,
+    codebase_context: &str,
+    strict_pushback: bool,
+) -> Result<TaskSpec, String> {
+    let classification = budget
+        .block_on(rt, signal::classify(ctx.llm.as_ref(), &prd_item.requirement, codebase_context))?
+        .map_err(|e| format!("signal classification failed for item {}: {e}", i + 1))?;
+
+    let spec = match classification {
+        ClassificationResult::Classified {
+            signal_type,
+            strategy,
+            confidence,
+            acceptance_criteria,
+        } => {
+            let mut spec = build_task_spec(&prd_item.requirement, &signal_type, strategy);
+            spec.title.clone_from(&prd_item.title);
+            spec.acceptance_criteria = acceptance_criteria;
+            print_classification(&spec, confidence);
+            if confidence.is_some_and(|c| c < LOW_CONFIDENCE_THRESHOLD) {
+                eprintln!(
+                    "Note: pushback recommended for item {} — low classification confidence",
+                    i + 1
+                );
+            }
+            spec
+        }
+        ClassificationResult::PushbackRequired { reason } if strict_pushback => {
+            return Err(format!(
+                "pushback required for item {} ({}) — {reason}; clarify the requirement and re-run",
+                i + 1,
+                prd_item.title
+            ));
+        }
+        ClassificationResult::PushbackRequired { reason } => {
+            eprintln!("Note: pushback required for item {} — {reason}", i + 1);
+            let spec = TaskSpec {
+                id: String::new(),
+                title: prd_item.title.clone(),
+                requirement: Some(prd_item.requirement.clone()),
+                context: None,
+                acceptance_criteria: vec![],
+                signal_type: SignalType::Fuzzy,
+                verification: VerificationStrategy::DirectAssertion {
+                    checks: vec![],
+                    groups: vec![],
+                    setup: vec![],
+                    teardown: vec![],
+                },
+                affected_globs: None,
+                created_at: None,
+                created_commit: None,
+                disabled: false,
+            };
+            print_classification(&spec, None);
+            spec
+        }
+    };
+    Ok(spec)
+}
+
 /// Build an initial `TaskSpec` skeleton from classification results.
 fn build_task_spec(
     requirement: &str,
@@ -455,6 +1075,24 @@ fn build_task_spec(
         signal_type,
         verification,
         affected_globs: None,
+        created_at: None,
+        created_commit: None,
+        disabled: false,
+    }
+}
+
+/// Record creation time and commit on specs that have no provenance yet.
+///
+/// Specs matched to an existing on-disk spec keep their original provenance.
+fn stamp_provenance(ctx: &ServiceContext, specs: &mut [TaskSpec]) {
+    if specs.iter().all(|s| s.created_at.is_some()) {
+        return;
+    }
+    let now = ctx.clock.now();
+    let commit = ctx.git.current_commit().ok();
+    for spec in specs.iter_mut().filter(|s| s.created_at.is_none()) {
+        spec.created_at = Some(now);
+        spec.created_commit.clone_from(&commit);
     }
 }
 
@@ -596,16 +1234,26 @@ fn derive_globs_from_survey(survey: &SurveyResult) -> Vec<String> {
 }
 
 /// Print the signal classification and verification strategy.
-fn print_classification(task_spec: &TaskSpec) {
+fn print_classification(task_spec: &TaskSpec, confidence: Option<f64>) {
     println!("\n=== Signal Classification ===");
     println!("  Type: {:?}", task_spec.signal_type);
+    if let Some(c) = confidence {
+        let note = if c < LOW_CONFIDENCE_THRESHOLD { " (low — consider pushback)" } else { "" };
+        println!("  Confidence: {:.0}%{note}", c * 100.0);
+    }
     println!("  Verification: {:?}", task_spec.verification);
+    for criterion in &task_spec.acceptance_criteria {
+        println!("  Criterion: {criterion}");
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::cassette::format::{Cassette, Interaction};
     use crate::plan::signal::{PlanCheck, SubAssertion, VerificationStrategy as PlanVS};
+    use crate::spec::TaskContext;
+    use serde_json::json;
     use std::collections::HashMap;
 
     #[test]
@@ -673,16 +1321,22 @@ mod tests {
         };
         let spec_strategy = map_verification_strategy(plan_strategy);
         match spec_strategy {
-            VerificationStrategy::DirectAssertion { checks } => {
+            VerificationStrategy::DirectAssertion { checks, .. } => {
                 assert_eq!(checks.len(), 2);
                 assert_eq!(
                     checks[0],
                     VerificationCheck::CommandOutput {
                         command: "ls".into(),
                         expected: "file.txt".into(),
+                        retries: 0,
+                        retry_backoff_ms: 0,
+                        name: None
                     }
                 );
-                assert_eq!(checks[1], VerificationCheck::Custom { description: "check2".into() });
+                assert_eq!(
+                    checks[1],
+                    VerificationCheck::Custom { description: "check2".into(), name: None }
+                );
             }
             other => panic!("expected DirectAssertion, got {other:?}"),
         }
@@ -714,12 +1368,15 @@ mod tests {
         };
         let spec_strategy = map_verification_strategy(plan_strategy);
         match spec_strategy {
-            VerificationStrategy::DirectAssertion { checks } => {
+            VerificationStrategy::DirectAssertion { checks, .. } => {
                 assert_eq!(checks.len(), 3);
                 // Custom checks get description prefixed
                 assert_eq!(
                     checks[0],
-                    VerificationCheck::Custom { description: "ordered: assert sorted".into() }
+                    VerificationCheck::Custom {
+                        description: "ordered: assert sorted".into(),
+                        name: None
+                    }
                 );
                 // Executable checks pass through directly
                 assert_eq!(
@@ -727,6 +1384,9 @@ mod tests {
                     VerificationCheck::TestSuite {
                         command: "cargo test".into(),
                         expected: "all pass".into(),
+                        retries: 0,
+                        retry_backoff_ms: 0,
+                        name: None
                     }
                 );
                 assert_eq!(
@@ -734,6 +1394,9 @@ mod tests {
                     VerificationCheck::CommandOutput {
                         command: "ls".into(),
                         expected: "file.txt".into(),
+                        retries: 0,
+                        retry_backoff_ms: 0,
+                        name: None
                     }
                 );
             }
@@ -793,7 +1456,7 @@ mod tests {
                 }],
             },
         );
-        print_classification(&spec);
+        print_classification(&spec, Some(0.3));
     }
 
     #[test]
@@ -836,16 +1499,76 @@ mod tests {
     // --- wire_dependencies tests ---
 
     fn bare_spec(id: &str, title: &str) -> TaskSpec {
-        TaskSpec {
-            id: id.into(),
-            title: title.into(),
-            requirement: None,
-            context: None,
-            acceptance_criteria: vec![],
-            signal_type: SignalType::Clear,
-            verification: VerificationStrategy::DirectAssertion { checks: vec![] },
-            affected_globs: None,
-        }
+        TaskSpec::for_test(id, title)
+    }
+
+    #[test]
+    fn rejected_update_leaves_spec_unchanged() {
+        use crate::plan::conversation::{apply_llm_updates, SpecUpdate};
+
+        let mut specs = vec![bare_spec("TASK-1", "Export CSV")];
+        let original = specs[0].clone();
+        let updates = vec![SpecUpdate {
+            task_id: "TASK-1".into(),
+            title: Some("Export spreadsheet".into()),
+            signal_type: None,
+        }];
+        let mut input = std::io::Cursor::new("n\n");
+
+        let applied = apply_llm_updates(&mut specs, &updates, &mut |spec, diff| {
+            confirm_update(spec, diff, &mut input)
+        });
+
+        assert_eq!(applied, 0);
+        assert_eq!(specs[0], original);
+    }
+
+    #[test]
+    fn confirmed_update_is_applied() {
+        use crate::plan::conversation::{apply_llm_updates, SpecUpdate};
+
+        let mut specs = vec![bare_spec("TASK-1", "Export CSV")];
+        let updates = vec![SpecUpdate {
+            task_id: "TASK-1".into(),
+            title: Some("Export spreadsheet".into()),
+            signal_type: Some(SignalType::Fuzzy),
+        }];
+        let mut input = std::io::Cursor::new("y\n");
+
+        let applied = apply_llm_updates(&mut specs, &updates, &mut |spec, diff| {
+            confirm_update(spec, diff, &mut input)
+        });
+
+        assert_eq!(applied, 1);
+        assert_eq!(specs[0].title, "Export spreadsheet");
+        assert_eq!(specs[0].signal_type, SignalType::Fuzzy);
+    }
+
+    #[test]
+    fn proposed_updates_are_not_applied_without_flag_or_answer() {
+        use crate::plan::conversation::SpecUpdate;
+
+        let mut specs = vec![bare_spec("TASK-1", "Export CSV")];
+        let original = specs[0].clone();
+        let analysis = AnalysisResult {
+            summary: String::new(),
+            questions: vec![],
+            updates: vec![SpecUpdate {
+                task_id: "TASK-1".into(),
+                title: Some("Export spreadsheet".into()),
+                signal_type: Some(SignalType::Fuzzy),
+            }],
+        };
+
+        apply_analysis_updates(&mut specs, &analysis, false, Some(&mut std::io::empty()));
+        assert_eq!(specs[0], original);
+
+        // Without a terminal to ask on, nothing is read and nothing applied.
+        apply_analysis_updates(&mut specs, &analysis, false, None);
+        assert_eq!(specs[0], original);
+
+        apply_analysis_updates(&mut specs, &analysis, true, None);
+        assert_eq!(specs[0].title, "Export spreadsheet");
     }
 
     #[test]
@@ -907,4 +1630,517 @@ mod tests {
         // No context set because the dep was out of bounds
         assert!(specs[0].context.is_none());
     }
+
+    #[test]
+    fn planned_spec_saved_with_provenance() {
+        use crate::cassette::recorder::CassetteRecorder;
+        use serde_json::json;
+
+        let dir = std::env::temp_dir().join("speck_plan_provenance");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let cassette_path = dir.join("provenance.cassette.yaml");
+        let mut recorder = CassetteRecorder::new(&cassette_path, "provenance", "abc");
+        recorder.record("clock", "now", json!({}), json!("2026-01-20T14:05:00Z"));
+        recorder.record("git", "current_commit", json!({}), json!({"Ok": "deadbeef"}));
+        recorder.finish().unwrap();
+
+        let mut ctx = ServiceContext::replaying(&cassette_path).unwrap();
+        ctx.fs = Box::new(crate::adapters::live::filesystem::LiveFileSystem);
+
+        let mut existing = bare_spec("T-OLD", "Existing");
+        existing.created_commit = Some("0ld".into());
+        existing.created_at = Some(chrono::Utc::now());
+        let mut specs = vec![bare_spec("T-NEW", "New"), existing.clone()];
+        stamp_provenance(&ctx, &mut specs);
+
+        let store = SpecStore::new(&ctx, &dir);
+        for spec in &specs {
+            store.save_task_spec(spec).unwrap();
+        }
+        let saved = store.load_task_spec("T-NEW").unwrap();
+        let kept = store.load_task_spec("T-OLD").unwrap();
+
+        let _ = std::fs::remove_dir_all(&dir);
+        assert_eq!(
+            saved.created_at.unwrap().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+            "2026-01-20T14:05:00Z"
+        );
+        assert_eq!(saved.created_commit.as_deref(), Some("deadbeef"));
+        assert_eq!(kept.created_commit.as_deref(), Some("0ld"));
+    }
+
+    #[test]
+    fn similar_stored_spec_triggers_duplicate_prompt() {
+        let mut oauth = bare_spec("T-AUTH", "OAuth login");
+        oauth.requirement = Some("Add OAuth login with Google".into());
+        let existing = vec![oauth, bare_spec("T-CSV", "Export reports as CSV")];
+
+        let requirement = "Add OAuth login with GitHub";
+        let ask = |answer: &str| {
+            check_for_duplicate(&existing, requirement, false, Some(&mut answer.as_bytes()))
+                .unwrap()
+        };
+        let unrelated =
+            check_for_duplicate(&existing, "Archive old invoices", false, None).unwrap();
+        let allowed = check_for_duplicate(&existing, requirement, true, None).unwrap();
+        let non_interactive = check_for_duplicate(&existing, requirement, false, None);
+
+        assert_eq!(ask("r\n"), DuplicateChoice::Revise("T-AUTH".into()));
+        assert_eq!(ask("c\n"), DuplicateChoice::Create);
+        assert_eq!(ask(""), DuplicateChoice::Abort);
+        // No similar spec, so nobody is asked.
+        assert_eq!(unrelated, DuplicateChoice::Create);
+        assert_eq!(allowed, DuplicateChoice::Create);
+        assert!(
+            matches!(non_interactive, Err(SpeckError::Usage(ref msg)) if msg.contains("--allow-duplicate")),
+            "{non_interactive:?}"
+        );
+    }
+
+    #[test]
+    fn multi_paragraph_document_matches_spec_by_title() {
+        let document = "# CSV export for reports\n\n\
+            Finance needs to pull monthly numbers into spreadsheets without copying \
+            tables by hand. Today the only option is a PDF, which loses formatting.\n\n\
+            ## Scope\n\n\
+            Every report page gets an Export button. The file uses the same column \
+            order as the on-screen table and includes a header row.\n\n\
+            ## Out of scope\n\n\
+            Scheduled exports and XLSX output are follow-up work.\n";
+        let existing = vec![
+            bare_spec("T-AUTH", "OAuth login"),
+            bare_spec("T-CSV", "Add CSV export for reports"),
+        ];
+
+        assert_eq!(find_similar_spec(&existing, document, &TokenJaccard).as_deref(), Some("T-CSV"));
+        let unrelated = document.replace("CSV export for reports", "Invoice archiving");
+        assert_eq!(find_similar_spec(&existing, &unrelated, &TokenJaccard), None);
+        assert_eq!(title_lines(document), ["CSV export for reports", "Scope", "Out of scope"]);
+    }
+
+    /// Assign IDs to `specs` against `store` and save them, as `plan` does.
+    fn persist_plan(
+        ctx: &ServiceContext,
+        store: &SpecStore<'_>,
+        specs: &mut [TaskSpec],
+        requirement: Option<(&str, &str)>,
+    ) -> PlanDiff {
+        let existing = load_existing_specs(store);
+        let diff = assign_ids(ctx, specs, existing, requirement.map(|(rid, _)| rid));
+        save_plan(store, specs, requirement).unwrap();
+        diff
+    }
+
+    #[test]
+    fn replanning_requirement_merges_into_linked_specs() {
+        use crate::cassette::recorder::CassetteRecorder;
+
+        let dir = std::env::temp_dir().join("speck_plan_requirement_merge");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let cassette_path = dir.join("requirement.cassette.yaml");
+        let mut recorder = CassetteRecorder::new(&cassette_path, "requirement", "abc");
+        for id in ["T-1", "T-2"] {
+            recorder.record("id_gen", "generate_id", json!({}), json!(id));
+            recorder.record("clock", "now", json!({}), json!("2026-03-02T10:00:00Z"));
+            recorder.record("git", "current_commit", json!({}), json!({"Ok": "cafe"}));
+        }
+        recorder.finish().unwrap();
+        let mut ctx = ServiceContext::replaying(&cassette_path).unwrap();
+        ctx.fs = Box::new(crate::adapters::live::filesystem::LiveFileSystem);
+        let store = SpecStore::new(&ctx, &dir);
+
+        // A spec with the same title that belongs to another requirement.
+        let mut unrelated = bare_spec("OTHER-1", "CSV export");
+        unrelated.requirement = Some("REQ-2".into());
+        store.save_task_spec(&unrelated).unwrap();
+
+        let criterion = "Users can export specs as CSV with a header row";
+        let mut csv = bare_spec("", "CSV export");
+        csv.acceptance_criteria = vec![criterion.into()];
+        let first = persist_plan(&ctx, &store, &mut [csv], Some(("REQ-1", "v1")));
+
+        let mut renamed = bare_spec("", "Export specs to CSV");
+        renamed.acceptance_criteria = vec![criterion.into()];
+        let mut pdf = bare_spec("", "PDF export");
+        pdf.acceptance_criteria = vec!["Users can print a spec to PDF".into()];
+        let second = persist_plan(&ctx, &store, &mut [renamed, pdf], Some(("REQ-1", "v2")));
+        let record = std::fs::read_to_string(dir.join("requirements/REQ-1.yaml")).unwrap();
+
+        let mut linked: Vec<_> = store
+            .list_task_specs()
+            .unwrap()
+            .iter()
+            .map(|id| store.load_task_spec(id).unwrap())
+            .filter(|s| s.requirement.as_deref() == Some("REQ-1"))
+            .map(|s| (s.id, s.title))
+            .collect();
+        linked.sort();
+        let unrelated = store.load_task_spec("OTHER-1").unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert_eq!(first.actions, vec![SpecMatchAction::New { id: "T-1".into() }]);
+        assert!(first.orphaned.is_empty(), "unrelated specs are not orphaned: {first:?}");
+        assert_eq!(
+            second.actions,
+            vec![
+                SpecMatchAction::Updated { id: "T-1".into() },
+                SpecMatchAction::New { id: "T-2".into() },
+            ]
+        );
+        assert_eq!(
+            linked,
+            vec![("T-1".into(), "Export specs to CSV".into()), ("T-2".into(), "PDF export".into())]
+        );
+        assert_eq!(unrelated.requirement.as_deref(), Some("REQ-2"));
+        assert_eq!(record, "id: REQ-1\ndocument: v2\n");
+    }
+
+    #[test]
+    fn accepted_extractions_add_foundational_specs() {
+        let ctx = ServiceContext::testing();
+        let store = SpecStore::new(&ctx, Path::new("/store"));
+        let mut specs = [bare_spec("", "CSV export"), bare_spec("", "JSON export")];
+        persist_plan(&ctx, &store, &mut specs, None);
+        let extraction = |ids: &[&str]| ExtractionSuggestion {
+            task_ids: ids.iter().map(ToString::to_string).collect(),
+            abstraction: "Shared serializer trait".into(),
+            suggested_task_title: "Add serializer trait".into(),
+        };
+
+        apply_extractions(
+            &ctx,
+            &store,
+            &[extraction(&["MISSING"]), extraction(&[&specs[0].id, &specs[1].id])],
+        );
+
+        let foundation = store
+            .list_task_specs()
+            .unwrap()
+            .into_iter()
+            .map(|id| store.load_task_spec(&id).unwrap())
+            .find(|s| s.title == "Add serializer trait")
+            .expect("foundational spec saved despite the skipped extraction");
+        for spec in &specs {
+            let deps = store.load_task_spec(&spec.id).unwrap().context.unwrap().dependencies;
+            assert_eq!(deps, [foundation.id.as_str()]);
+        }
+    }
+
+    #[test]
+    fn requirement_ids_must_be_safe_file_names() {
+        assert!(validate_requirement_id("REQ-1.2_a").is_ok());
+        for id in ["", "../REQ-1", ".hidden", "REQ 1", "a/b"] {
+            let err = validate_requirement_id(id).unwrap_err();
+            assert!(matches!(err, SpeckError::Usage(_)), "{id:?}: {err}");
+        }
+    }
+
+    fn revise_context(dir: &Path) -> ServiceContext {
+        let response = json!({
+            "type": "clear",
+            "confidence": 0.9,
+            "checks": [{
+                "check_type": "test_suite",
+                "command": "cargo test export",
+                "expected": "all pass"
+            }]
+        });
+        let cassette = Cassette {
+            name: "plan-revise".into(),
+            recorded_at: chrono::Utc::now(),
+            commit: "abc".into(),
+            interactions: vec![
+                Interaction {
+                    seq: 0,
+                    port: "llm".into(),
+                    method: "complete".into(),
+                    input: json!({}),
+                    output: json!({"ok": {
+                        "text": response.to_string(),
+                        "prompt_tokens": 100,
+                        "completion_tokens": 40
+                    }}),
+                },
+                Interaction {
+                    seq: 1,
+                    port: "clock".into(),
+                    method: "now".into(),
+                    input: json!({}),
+                    output: json!("2026-02-01T09:00:00Z"),
+                },
+            ],
+        };
+        let path = dir.join("revise.cassette.yaml");
+        std::fs::write(&path, serde_yaml::to_string(&cassette).unwrap()).unwrap();
+        let mut ctx = ServiceContext::replaying(&path).unwrap();
+        ctx.fs = Box::new(crate::adapters::live::filesystem::LiveFileSystem);
+        ctx
+    }
+
+    /// Context whose LLM replays one completion with `response` as its text.
+    fn classification_context(name: &str, response: &serde_json::Value) -> ServiceContext {
+        let dir = std::env::temp_dir().join(format!("speck_{name}"));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let cassette = Cassette {
+            name: name.into(),
+            recorded_at: chrono::Utc::now(),
+            commit: "abc".into(),
+            interactions: vec![Interaction {
+                seq: 0,
+                port: "llm".into(),
+                method: "complete".into(),
+                input: json!({}),
+                output: json!({"ok": {
+                    "text": response.to_string(),
+                    "prompt_tokens": 120,
+                    "completion_tokens": 60
+                }}),
+            }],
+        };
+        let path = dir.join("classify.cassette.yaml");
+        std::fs::write(&path, serde_yaml::to_string(&cassette).unwrap()).unwrap();
+        let ctx = ServiceContext::replaying(&path).unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+        ctx
+    }
+
+    fn csv_item() -> conversation::PrdItem {
+        conversation::PrdItem {
+            title: "CSV export".into(),
+            requirement: "Users can export specs as CSV".into(),
+            depends_on: vec![],
+        }
+    }
+
+    #[test]
+    fn classified_item_gets_acceptance_criteria_from_response() {
+        let response = json!({
+            "type": "clear",
+            "confidence": 0.9,
+            "checks": [{"check_type": "test_suite", "command": "cargo test csv", "expected": "pass"}],
+            "acceptance_criteria": [
+                "`speck export --csv` writes one row per spec",
+                "the header row names every column"
+            ]
+        });
+        let ctx = classification_context("plan_criteria", &response);
+
+        let rt = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+        let budget = Budget::start(&ctx, None);
+        let spec = classify_item(&ctx, &rt, &budget, 0, &csv_item(), "", false).unwrap();
+
+        assert_eq!(spec.title, "CSV export");
+        assert_eq!(
+            spec.acceptance_criteria,
+            ["`speck export --csv` writes one row per spec", "the header row names every column"]
+        );
+    }
+
+    #[test]
+    fn strict_pushback_fails_with_reason() {
+        let response = json!({"type": "pushback", "reason": "which columns?"});
+        let rt = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+
+        let lenient = classification_context("plan_pushback_lenient", &response);
+        let budget = Budget::start(&lenient, None);
+        let spec = classify_item(&lenient, &rt, &budget, 0, &csv_item(), "", false).unwrap();
+        assert!(spec.acceptance_criteria.is_empty());
+
+        let strict = classification_context("plan_pushback_strict", &response);
+        let budget = Budget::start(&strict, None);
+        let err = classify_item(&strict, &rt, &budget, 0, &csv_item(), "", true).unwrap_err();
+        assert!(err.starts_with("pushback required for item 1 (CSV export)"), "{err}");
+        assert!(err.contains("which columns?"));
+    }
+
+    /// LLM whose completions never finish.
+    struct StalledLlm;
+
+    impl crate::ports::LlmClient for StalledLlm {
+        fn complete(
+            &self,
+            _request: &crate::ports::CompletionRequest,
+        ) -> crate::ports::CompletionFuture<'_> {
+            Box::pin(std::future::pending())
+        }
+    }
+
+    /// Clock whose monotonic reading advances one second per reading.
+    #[derive(Default)]
+    struct SteppingClock(std::sync::atomic::AtomicU64);
+
+    impl crate::ports::Clock for SteppingClock {
+        fn now(&self) -> chrono::DateTime<chrono::Utc> {
+            chrono::DateTime::UNIX_EPOCH
+        }
+
+        fn monotonic(&self) -> std::time::Duration {
+            let secs = self.0.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            std::time::Duration::from_secs(secs)
+        }
+    }
+
+    #[test]
+    fn stalled_llm_times_out_on_the_context_clock() {
+        let dir = std::env::temp_dir().join("speck_plan_timeout");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let doc = dir.join("prd.md");
+        std::fs::write(&doc, "# Export\n\nUsers can export reports.").unwrap();
+        let fs = crate::adapters::memory::filesystem::MemFileSystem::new();
+        let files = fs.files();
+        let ctx = ServiceContext::testing()
+            .with_fs(fs)
+            .with_clock(SteppingClock::default())
+            .with_llm(StalledLlm);
+
+        // Budget starts at reading 0s; the poll readings 1s, 2s, 3s exhaust it.
+        let result = run(
+            &ctx,
+            &doc,
+            PlanOptions {
+                timeout: Some(std::time::Duration::from_secs(3)),
+                ..PlanOptions::default()
+            },
+        );
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert!(result.is_ok(), "{result:?}");
+        assert!(
+            files.lock().unwrap().keys().all(|p| !p.starts_with(".speck/tasks")),
+            "no specs should be saved after a timeout"
+        );
+    }
+
+    #[test]
+    fn revise_updates_verification_but_keeps_id_and_dependencies() {
+        let dir = std::env::temp_dir().join("speck_plan_revise");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let ctx = revise_context(&dir);
+        let store = SpecStore::new(&ctx, &dir);
+
+        let original = TaskSpec {
+            requirement: Some("Exports should be fast".to_string()),
+            context: Some(TaskContext {
+                modules: vec!["exporter".to_string()],
+                patterns: None,
+                dependencies: vec!["TASK-A".to_string()],
+            }),
+            acceptance_criteria: vec!["export feels fast".to_string()],
+            signal_type: SignalType::Fuzzy,
+            verification: VerificationStrategy::DirectAssertion {
+                checks: vec![VerificationCheck::Custom {
+                    description: "feels fast".to_string(),
+                    name: None,
+                }],
+                groups: vec![],
+                setup: vec![],
+                teardown: vec![],
+            },
+            created_at: Some(chrono::Utc::now()),
+            created_commit: Some("0ld".to_string()),
+            ..TaskSpec::for_test("TASK-R", "Export is fast")
+        };
+        store.save_task_spec(&original).unwrap();
+
+        run_revise(&ctx, "TASK-R", None, Some(&dir)).unwrap();
+
+        let revised = store.load_task_spec("TASK-R").unwrap();
+        let history = store.list_history("TASK-R").unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert_eq!(revised.id, "TASK-R");
+        assert_eq!(revised.signal_type, SignalType::Clear);
+        assert_eq!(
+            revised.verification,
+            VerificationStrategy::DirectAssertion {
+                checks: vec![VerificationCheck::TestSuite {
+                    command: "cargo test export".to_string(),
+                    expected: "all pass".to_string(),
+                    retries: 0,
+                    retry_backoff_ms: 0,
+                    name: None
+                }],
+                groups: vec![],
+                setup: vec![],
+                teardown: vec![],
+            }
+        );
+        assert_eq!(revised.context, original.context);
+        assert_eq!(revised.created_commit.as_deref(), Some("0ld"));
+        assert_eq!(history.len(), 1);
+    }
+
+    /// Answers survey and decomposition prompts, counting every call.
+    struct PlanningLlm {
+        calls: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+    }
+
+    impl crate::ports::LlmClient for PlanningLlm {
+        fn complete(
+            &self,
+            request: &crate::ports::CompletionRequest,
+        ) -> crate::ports::CompletionFuture<'_> {
+            self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            let text = if request.prompt.contains("decompose") {
+                json!({"items": [{"title": "CSV export", "requirement": "Export as CSV"}]})
+            } else {
+                json!({"routing_table": {"src/export": "Report export"}})
+            };
+            Box::pin(async move {
+                Ok(crate::ports::CompletionResponse {
+                    text: text.to_string(),
+                    prompt_tokens: 1,
+                    completion_tokens: 1,
+                })
+            })
+        }
+    }
+
+    #[test]
+    fn replanning_at_same_commit_reuses_cached_llm_responses() {
+        use crate::adapters::memory::filesystem::MemFileSystem;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let fs = MemFileSystem::new();
+        let map = CodebaseMap {
+            commit_hash: "0000000".into(),
+            generated_at: chrono::Utc::now(),
+            modules: vec![],
+            directory_tree: vec![],
+            test_infrastructure: vec![],
+            unreadable: vec![],
+        };
+        fs.files().lock().unwrap().insert(
+            "/proj/.spec-cache/codebase_map.yaml".into(),
+            serde_yaml::to_string(&map).unwrap(),
+        );
+        let calls = std::sync::Arc::new(AtomicUsize::new(0));
+        let ctx = ServiceContext::testing()
+            .with_fs(fs.clone())
+            .with_llm(PlanningLlm { calls: std::sync::Arc::clone(&calls) });
+        let rt = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+        let budget = Budget::start(&ctx, None);
+        let plan = |no_cache| {
+            survey_and_decompose(&ctx, &rt, &budget, Path::new("/proj"), "Export CSV", no_cache)
+                .unwrap()
+        };
+
+        let (_, _, first) = plan(false);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        assert!(fs
+            .files()
+            .lock()
+            .unwrap()
+            .contains_key(Path::new("/proj/.spec-cache/llm_responses.json")));
+
+        let (survey, _, second) = plan(false);
+        assert_eq!(calls.load(Ordering::SeqCst), 2, "same commit should not call the LLM");
+        assert_eq!(second, first);
+        assert!(survey.routing_table.contains_key("src/export"));
+    }
 }
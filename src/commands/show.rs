@@ -2,9 +2,15 @@
 
 use std::path::{Path, PathBuf};
 
+use chrono::{DateTime, NaiveDateTime, SecondsFormat, Utc};
+
+use crate::cassette::config::CassetteConfig;
 use crate::context::ServiceContext;
-use crate::spec::{SignalType, VerificationCheck, VerificationStrategy};
+use crate::error::SpeckError;
+use crate::linkage::{self, LinkageResult};
+use crate::spec::{SignalType, SpecDiff, TaskSpec, VerificationCheck, VerificationStrategy};
 use crate::store::SpecStore;
+use crate::validate::{self, ValidationResult};
 
 /// Execute the `show` command.
 ///
@@ -13,8 +19,8 @@ use crate::store::SpecStore;
 ///
 /// # Errors
 ///
-/// Returns an error string if spec loading fails.
-pub fn run(id: Option<&str>) -> Result<(), String> {
+/// Returns an error if spec loading fails.
+pub fn run(id: Option<&str>) -> Result<(), SpeckError> {
     run_with_store_root(id, None)
 }
 
@@ -25,8 +31,11 @@ pub fn run(id: Option<&str>) -> Result<(), String> {
 ///
 /// # Errors
 ///
-/// Returns an error string if spec loading fails.
-pub fn run_with_store_root(id: Option<&str>, override_root: Option<&Path>) -> Result<(), String> {
+/// Returns an error if spec loading fails.
+pub fn run_with_store_root(
+    id: Option<&str>,
+    override_root: Option<&Path>,
+) -> Result<(), SpeckError> {
     let ctx = ServiceContext::live();
     let root = match override_root {
         Some(r) => r.to_path_buf(),
@@ -43,19 +52,242 @@ pub fn run_with_store_root(id: Option<&str>, override_root: Option<&Path>) -> Re
         if ids.is_empty() {
             println!("No specs found in store.");
         } else {
-            println!("Available specs:");
-            for id in &ids {
-                println!("  {id}");
-            }
+            println!("{}", format_spec_list(&store, &ids));
             println!("\nUse `speck show <SPEC_ID>` to view details.");
         }
         Ok(())
     }
 }
 
+/// Execute `show <id> --verify`.
+///
+/// Pretty-prints the spec, then runs its verification checks through `ctx`
+/// and appends a compact per-check summary. When `run_checks` is `false`
+/// (replaying a cassette with no recorded shell runs), the summary is
+/// replaced by a note that verification was skipped.
+///
+/// # Errors
+///
+/// Returns an error if spec loading fails.
+pub fn run_verify(
+    ctx: &ServiceContext,
+    id: &str,
+    run_checks: bool,
+    override_root: Option<&Path>,
+) -> Result<(), SpeckError> {
+    let live = ServiceContext::live();
+    let root = override_root.map_or_else(store_root, Path::to_path_buf);
+    let spec = SpecStore::new(&live, &root).load_task_spec(id)?;
+    print_spec(&spec);
+    println!();
+    if run_checks {
+        println!("{}", format_verify_summary(&validate::validate(ctx, &spec)));
+    } else {
+        println!("Verification: skipped (replay cassette has no recorded shell runs)");
+    }
+    Ok(())
+}
+
+/// Execute `show <id> --links`.
+///
+/// Pretty-prints the spec, then resolves its `context.modules` against the
+/// cached codebase map and lists each reference with the module path it
+/// resolves to, or `UNRESOLVED`. With `semantic`, references are matched by
+/// embedding similarity through `ctx.llm`, falling back to name matching
+/// when embeddings are unavailable.
+///
+/// # Errors
+///
+/// Returns an error if spec loading fails or no cached map exists.
+pub fn run_links(
+    ctx: &ServiceContext,
+    id: &str,
+    semantic: bool,
+    override_root: Option<&Path>,
+) -> Result<(), SpeckError> {
+    let root = override_root.map_or_else(store_root, Path::to_path_buf);
+    let spec = SpecStore::new(ctx, &root).load_task_spec(id)?;
+    print_spec(&spec);
+    println!();
+    let project_root = root.parent().unwrap_or(Path::new(""));
+    println!("{}", linkage_report(ctx, &spec, project_root, semantic)?);
+    Ok(())
+}
+
+/// Resolves `spec` against the map cached under `project_root` and renders the links.
+fn linkage_report(
+    ctx: &ServiceContext,
+    spec: &TaskSpec,
+    project_root: &Path,
+    semantic: bool,
+) -> Result<String, String> {
+    let map = validate::load_cached_map(ctx, project_root)
+        .ok_or("No cached codebase map found. Run `speck map` first.")?;
+    let result = if semantic {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| format!("failed to create async runtime: {e}"))?;
+        rt.block_on(linkage::resolve_semantic(
+            ctx.llm.as_ref(),
+            spec,
+            &map,
+            linkage::SEMANTIC_MATCH_THRESHOLD,
+        ))
+    } else {
+        linkage::resolve(spec, &map)
+    };
+    Ok(format_links(&result))
+}
+
+fn format_links(result: &LinkageResult) -> String {
+    if result.links.is_empty() {
+        return "Linkage: no module references".to_string();
+    }
+    let mut lines = vec!["Linkage:".to_string()];
+    for link in &result.links {
+        let target = link.resolved_path.as_deref().unwrap_or("UNRESOLVED");
+        lines.push(format!("  {} -> {target}", link.module_ref));
+    }
+    let unresolved = result.unresolved();
+    if !unresolved.is_empty() {
+        lines.push(format!(
+            "{} of {} references unresolved: {}",
+            unresolved.len(),
+            result.links.len(),
+            unresolved.join(", ")
+        ));
+    }
+    lines.join("\n")
+}
+
+/// Whether the replay cassette at `path` can serve the shell runs that
+/// `show --verify` needs.
+#[must_use]
+pub fn cassette_can_verify(path: &Path) -> bool {
+    CassetteConfig::load_monolithic(path).is_ok_and(|r| r.remaining("shell", "run") > 0)
+}
+
+fn format_verify_summary(result: &ValidationResult) -> String {
+    let passed = result.checks.iter().filter(|c| c.passed).count();
+    let overall = if result.passed() { "PASSED" } else { "FAILED" };
+    let mut lines =
+        vec![format!("Verification: {overall} ({passed}/{} checks)", result.checks.len())];
+    for check in &result.checks {
+        let status = if check.passed { "PASS" } else { "FAIL" };
+        lines.push(format!("  [{status}] {}", check.name));
+    }
+    lines.join("\n")
+}
+
+/// Execute `show --history <id>`.
+///
+/// With neither `at` nor `diff`, lists the spec's history snapshots. With
+/// `at`, pretty-prints the snapshot taken at that time. With `diff`, prints
+/// what changed between the two snapshots. Timestamps are accepted in
+/// RFC 3339 form or as the snapshot filename stamp (`YYYYMMDDTHHMMSSZ`).
+///
+/// # Errors
+///
+/// Returns an error if a timestamp is malformed or a snapshot
+/// cannot be loaded.
+pub fn run_history(
+    id: &str,
+    at: Option<&str>,
+    diff: Option<(&str, &str)>,
+) -> Result<(), SpeckError> {
+    run_history_with_store_root(id, at, diff, None)
+}
+
+/// Execute `show --history` with an optional explicit store root.
+///
+/// # Errors
+///
+/// Returns an error if a timestamp is malformed or a snapshot
+/// cannot be loaded.
+pub fn run_history_with_store_root(
+    id: &str,
+    at: Option<&str>,
+    diff: Option<(&str, &str)>,
+    override_root: Option<&Path>,
+) -> Result<(), SpeckError> {
+    let ctx = ServiceContext::live();
+    let root = override_root.map_or_else(store_root, Path::to_path_buf);
+    let store = SpecStore::new(&ctx, &root);
+
+    if let Some(at) = at {
+        let spec = store.load_history_snapshot(id, parse_timestamp(at)?)?;
+        print_spec(&spec);
+    } else if let Some((t1, t2)) = diff {
+        let before = store.load_history_snapshot(id, parse_timestamp(t1)?)?;
+        let after = store.load_history_snapshot(id, parse_timestamp(t2)?)?;
+        println!("{}", format_diff(&before.diff(&after)));
+    } else {
+        println!("{}", format_history(id, &store.list_history(id)?));
+    }
+    Ok(())
+}
+
+fn format_history(id: &str, stamps: &[DateTime<Utc>]) -> String {
+    if stamps.is_empty() {
+        return format!("No history snapshots for {id}.");
+    }
+    let mut lines = vec![format!("History for {id}:")];
+    for stamp in stamps {
+        lines.push(format!("  {}", stamp.to_rfc3339_opts(SecondsFormat::Secs, true)));
+    }
+    lines.push(String::new());
+    lines.push(format!("Use `speck show --history {id} --at <TIMESTAMP>` to view a snapshot."));
+    lines.join("\n")
+}
+
+/// Renders a [`SpecDiff`] as human-readable lines.
+pub(crate) fn format_diff(diff: &SpecDiff) -> String {
+    if diff.is_empty() {
+        return "No differences.".to_string();
+    }
+    let mut lines = Vec::new();
+    if let Some((before, after)) = &diff.title {
+        lines.push(format!("Title: {before} -> {after}"));
+    }
+    if let Some((before, after)) = &diff.signal {
+        lines.push(format!("Signal: {} -> {}", format_signal(before), format_signal(after)));
+    }
+    if !diff.criteria_added.is_empty() || !diff.criteria_removed.is_empty() {
+        lines.push("Acceptance Criteria:".to_string());
+        lines.extend(diff.criteria_removed.iter().map(|c| format!("  - {c}")));
+        lines.extend(diff.criteria_added.iter().map(|c| format!("  + {c}")));
+    }
+    if diff.verification_changed {
+        lines.push("Verification: changed".to_string());
+    }
+    lines.join("\n")
+}
+
+fn parse_timestamp(text: &str) -> Result<DateTime<Utc>, SpeckError> {
+    DateTime::parse_from_rfc3339(text)
+        .map(|t| t.with_timezone(&Utc))
+        .or_else(|_| NaiveDateTime::parse_from_str(text, "%Y%m%dT%H%M%SZ").map(|t| t.and_utc()))
+        .map_err(|_| {
+            SpeckError::Usage(format!(
+                "invalid timestamp '{text}': expected RFC 3339 or YYYYMMDDTHHMMSSZ"
+            ))
+        })
+}
+
+/// Lists `ids`, marking those whose spec is disabled.
+fn format_spec_list(store: &SpecStore<'_>, ids: &[String]) -> String {
+    let mut lines = vec!["Available specs:".to_string()];
+    for id in ids {
+        let disabled = store.load_task_spec(id).is_ok_and(|spec| spec.disabled);
+        lines.push(if disabled { format!("  {id} (disabled)") } else { format!("  {id}") });
+    }
+    lines.join("\n")
+}
+
 fn print_spec(spec: &crate::spec::TaskSpec) {
     println!("Spec: {}", spec.id);
-    println!("Title: {}", spec.title);
+    println!("Title: {}", spec.display_title());
 
     if let Some(req) = &spec.requirement {
         println!("Requirement: {req}");
@@ -63,6 +295,13 @@ fn print_spec(spec: &crate::spec::TaskSpec) {
 
     println!("Signal: {}", format_signal(&spec.signal_type));
 
+    if let Some(created_at) = &spec.created_at {
+        println!("Created: {}", created_at.to_rfc3339_opts(SecondsFormat::Secs, true));
+    }
+    if let Some(commit) = &spec.created_commit {
+        println!("Created at commit: {commit}");
+    }
+
     if let Some(ctx) = &spec.context {
         if !ctx.modules.is_empty() {
             println!("Modules: {}", ctx.modules.join(", "));
@@ -84,7 +323,7 @@ fn print_spec(spec: &crate::spec::TaskSpec) {
     print_verification(&spec.verification);
 }
 
-fn format_signal(signal: &SignalType) -> &'static str {
+pub(super) fn format_signal(signal: &SignalType) -> &'static str {
     match signal {
         SignalType::Clear => "clear",
         SignalType::Fuzzy => "fuzzy",
@@ -94,11 +333,23 @@ fn format_signal(signal: &SignalType) -> &'static str {
 
 fn print_verification(verification: &VerificationStrategy) {
     match verification {
-        VerificationStrategy::DirectAssertion { checks } => {
+        VerificationStrategy::DirectAssertion { checks, groups, setup, teardown } => {
             println!("  Strategy: direct_assertion");
+            for command in setup {
+                println!("  Setup: {command}");
+            }
             for check in checks {
                 print_check(check);
             }
+            for group in groups {
+                println!("  {}:", group.label);
+                for check in &group.checks {
+                    print_check(check);
+                }
+            }
+            for command in teardown {
+                println!("  Teardown: {command}");
+            }
         }
         VerificationStrategy::RefactorToExpose { decision_point, required_structure, .. } => {
             println!("  Strategy: refactor_to_expose");
@@ -115,19 +366,25 @@ fn print_verification(verification: &VerificationStrategy) {
 
 fn print_check(check: &VerificationCheck) {
     match check {
-        VerificationCheck::TestSuite { command, expected } => {
+        VerificationCheck::TestSuite { command, expected, .. } => {
             println!("  - [test_suite] {command} (expect: {expected})");
         }
-        VerificationCheck::SqlAssertion { query, expected } => {
+        VerificationCheck::SqlAssertion { query, expected, .. } => {
             println!("  - [sql] {query} (expect: {expected})");
         }
-        VerificationCheck::CommandOutput { command, expected } => {
+        VerificationCheck::CommandOutput { command, expected, .. } => {
             println!("  - [command] {command} (expect: {expected})");
         }
-        VerificationCheck::MigrationRollback { description } => {
+        VerificationCheck::Coverage { command, min_percent, .. } => {
+            println!("  - [coverage] {command} (min: {min_percent}%)");
+        }
+        VerificationCheck::GoldenFile { command, golden_path, .. } => {
+            println!("  - [golden_file] {command} (golden: {golden_path})");
+        }
+        VerificationCheck::MigrationRollback { description, .. } => {
             println!("  - [migration_rollback] {description}");
         }
-        VerificationCheck::Custom { description } => {
+        VerificationCheck::Custom { description, .. } => {
             println!("  - [custom] {description}");
         }
     }
@@ -152,7 +409,7 @@ mod tests {
     fn show_command_with_nonexistent_id() {
         let dir = PathBuf::from("/tmp/speck_test_show_empty_nonexistent");
         let result = run_with_store_root(Some("NONEXISTENT"), Some(&dir));
-        assert!(result.is_err());
+        assert!(matches!(result, Err(SpeckError::NotFound(_))), "{result:?}");
     }
 
     #[test]
@@ -164,19 +421,21 @@ mod tests {
         std::fs::create_dir_all(&tasks_dir).unwrap();
 
         let spec = TaskSpec {
-            id: "TASK-1".to_string(),
-            title: "Test task".to_string(),
             requirement: Some("req-1".to_string()),
-            context: None,
             acceptance_criteria: vec!["it works".to_string()],
-            signal_type: SignalType::Clear,
             verification: VerificationStrategy::DirectAssertion {
                 checks: vec![VerificationCheck::TestSuite {
                     command: "cargo test".to_string(),
                     expected: "pass".to_string(),
+                    retries: 0,
+                    retry_backoff_ms: 0,
+                    name: None,
                 }],
+                groups: vec![],
+                setup: vec![],
+                teardown: vec![],
             },
-            affected_globs: None,
+            ..TaskSpec::for_test("TASK-1", "Test task")
         };
 
         let yaml = serde_yaml::to_string(&spec).unwrap();
@@ -188,10 +447,280 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn spec_list_flags_disabled_specs() {
+        use crate::adapters::memory::filesystem::MemFileSystem;
+
+        let ctx = ServiceContext::testing().with_fs(MemFileSystem::new());
+        let store = SpecStore::new(&ctx, Path::new("/store"));
+        let mut shelved = history_spec(&["it works"], SignalType::Clear);
+        shelved.id = "TASK-2".to_string();
+        shelved.disabled = true;
+        store.save_task_spec(&history_spec(&["it works"], SignalType::Clear)).unwrap();
+        store.save_task_spec(&shelved).unwrap();
+
+        let ids = store.list_task_specs().unwrap();
+        assert_eq!(
+            format_spec_list(&store, &ids),
+            "Available specs:\n  TASK-2 (disabled)\n  TASK-H"
+        );
+        assert_eq!(shelved.display_title(), format!("{} (disabled)", shelved.title));
+    }
+
+    fn history_spec(criteria: &[&str], signal: SignalType) -> crate::spec::TaskSpec {
+        crate::spec::TaskSpec {
+            acceptance_criteria: criteria.iter().map(|c| (*c).to_string()).collect(),
+            signal_type: signal,
+            ..TaskSpec::for_test("TASK-H", "Historic task")
+        }
+    }
+
+    #[test]
+    fn show_history_lists_and_renders_snapshots() {
+        let dir = std::env::temp_dir().join("speck_cli_show_history");
+        let _ = std::fs::remove_dir_all(&dir);
+        let ctx = ServiceContext::live();
+        let store = SpecStore::new(&ctx, &dir);
+        let t1 = parse_timestamp("2026-03-01T09:00:00Z").unwrap();
+        let t2 = parse_timestamp("20260302T173015Z").unwrap();
+        store.save_history_snapshot(&history_spec(&["old"], SignalType::Fuzzy), t1).unwrap();
+        store.save_history_snapshot(&history_spec(&["new"], SignalType::Clear), t2).unwrap();
+
+        let stamps = store.list_history("TASK-H").unwrap();
+        let listing = format_history("TASK-H", &stamps);
+        assert!(listing.contains("  2026-03-01T09:00:00Z\n  2026-03-02T17:30:15Z"), "{listing}");
+
+        let listed = run_history_with_store_root("TASK-H", None, None, Some(&dir));
+        let at =
+            run_history_with_store_root("TASK-H", Some("2026-03-01T09:00:00Z"), None, Some(&dir));
+        let diffed = run_history_with_store_root(
+            "TASK-H",
+            None,
+            Some(("20260301T090000Z", "20260302T173015Z")),
+            Some(&dir),
+        );
+        let missing =
+            run_history_with_store_root("TASK-H", Some("20250101T000000Z"), None, Some(&dir));
+
+        let _ = std::fs::remove_dir_all(&dir);
+        assert!(listed.is_ok());
+        assert!(at.is_ok());
+        assert!(diffed.is_ok());
+        assert!(missing.is_err());
+    }
+
+    #[test]
+    fn linkage_report_shows_resolved_and_unresolved_references() {
+        use crate::cassette::format::{Cassette, Interaction};
+        use crate::map::{CodebaseMap, ModuleSummary};
+        use crate::spec::TaskContext;
+        use serde_json::json;
+
+        let map = CodebaseMap {
+            commit_hash: "abc123".to_string(),
+            generated_at: Utc::now(),
+            modules: vec![ModuleSummary {
+                path: "src/metrics".to_string(),
+                public_items: vec!["struct MetricsService".to_string()],
+                dependencies: vec![],
+                external_deps: vec![],
+                locations: std::collections::BTreeMap::new(),
+                crate_name: None,
+            }],
+            directory_tree: vec!["src/metrics/mod.rs".to_string()],
+            test_infrastructure: vec![],
+            unreadable: vec![],
+        };
+        let map_path = "/project/.spec-cache/codebase_map.yaml";
+        let cassette = Cassette {
+            name: "show-links".into(),
+            recorded_at: Utc::now(),
+            commit: "abc123".into(),
+            interactions: vec![
+                Interaction {
+                    seq: 0,
+                    port: "fs".into(),
+                    method: "exists".into(),
+                    input: json!({"path": map_path}),
+                    output: json!(true),
+                },
+                Interaction {
+                    seq: 1,
+                    port: "fs".into(),
+                    method: "read_to_string".into(),
+                    input: json!({"path": map_path}),
+                    output: json!(serde_yaml::to_string(&map).unwrap()),
+                },
+            ],
+        };
+        let dir = std::env::temp_dir().join("speck_cli_show_links");
+        std::fs::create_dir_all(&dir).unwrap();
+        let cassette_path = dir.join("show_links.cassette.yaml");
+        std::fs::write(&cassette_path, serde_yaml::to_string(&cassette).unwrap()).unwrap();
+        let ctx = ServiceContext::replaying(&cassette_path).unwrap();
+
+        let mut spec = history_spec(&["works"], SignalType::Clear);
+        spec.context = Some(TaskContext {
+            modules: vec!["metrics".to_string(), "BillingService".to_string()],
+            patterns: None,
+            dependencies: vec![],
+        });
+        let report = linkage_report(&ctx, &spec, Path::new("/project"), false);
+
+        let _ = std::fs::remove_dir_all(&dir);
+        assert_eq!(
+            report.unwrap(),
+            "Linkage:\n  metrics -> src/metrics\n  BillingService -> UNRESOLVED\n\
+             1 of 2 references unresolved: BillingService"
+        );
+    }
+
+    #[test]
+    fn semantic_linkage_report_matches_by_embedding() {
+        use crate::adapters::memory::filesystem::MemFileSystem;
+        use crate::map::{CodebaseMap, ModuleSummary};
+        use crate::ports::{CompletionFuture, CompletionRequest, EmbeddingFuture, LlmClient};
+        use crate::spec::TaskContext;
+
+        /// Embeds "auth" next to the login module and away from billing.
+        struct AuthNearLogin;
+
+        impl LlmClient for AuthNearLogin {
+            fn complete(&self, _request: &CompletionRequest) -> CompletionFuture<'_> {
+                Box::pin(async { Err("unused".into()) })
+            }
+
+            fn embed(&self, texts: &[String]) -> EmbeddingFuture<'_> {
+                let vectors = texts
+                    .iter()
+                    .map(|t| {
+                        if t == "auth" || t.starts_with("src/login") {
+                            vec![1.0, 0.0]
+                        } else {
+                            vec![0.0, 1.0]
+                        }
+                    })
+                    .collect();
+                Box::pin(async move { Ok(vectors) })
+            }
+        }
+
+        let module = |path: &str| ModuleSummary {
+            path: path.to_string(),
+            public_items: vec![],
+            dependencies: vec![],
+            external_deps: vec![],
+            locations: std::collections::BTreeMap::new(),
+            crate_name: None,
+        };
+        let map = CodebaseMap {
+            commit_hash: "abc123".to_string(),
+            generated_at: Utc::now(),
+            modules: vec![module("src/login"), module("src/billing")],
+            directory_tree: vec![],
+            test_infrastructure: vec![],
+            unreadable: vec![],
+        };
+        let fs = MemFileSystem::new();
+        fs.files().lock().unwrap().insert(
+            PathBuf::from("/project/.spec-cache/codebase_map.yaml"),
+            serde_yaml::to_string(&map).unwrap(),
+        );
+        let ctx = ServiceContext::testing().with_fs(fs).with_llm(AuthNearLogin);
+
+        let mut spec = history_spec(&["works"], SignalType::Clear);
+        spec.context = Some(TaskContext {
+            modules: vec!["auth".to_string()],
+            patterns: None,
+            dependencies: vec![],
+        });
+
+        let by_name = linkage_report(&ctx, &spec, Path::new("/project"), false).unwrap();
+        let semantic = linkage_report(&ctx, &spec, Path::new("/project"), true).unwrap();
+        assert!(by_name.contains("auth -> UNRESOLVED"), "got: {by_name}");
+        assert_eq!(semantic, "Linkage:\n  auth -> src/login");
+    }
+
+    #[test]
+    fn format_diff_lists_changed_fields() {
+        let before = history_spec(&["old"], SignalType::Fuzzy);
+        let after = history_spec(&["new"], SignalType::Clear);
+        let text = format_diff(&before.diff(&after));
+        assert_eq!(text, "Signal: fuzzy -> clear\nAcceptance Criteria:\n  - old\n  + new");
+        assert_eq!(format_diff(&before.diff(&before)), "No differences.");
+    }
+
+    #[test]
+    fn parse_timestamp_rejects_garbage() {
+        let err = parse_timestamp("yesterday").unwrap_err();
+        assert!(matches!(err, SpeckError::Usage(ref msg) if msg.contains("invalid timestamp")));
+    }
+
     #[test]
     fn format_signal_returns_correct_strings() {
         assert_eq!(format_signal(&SignalType::Clear), "clear");
         assert_eq!(format_signal(&SignalType::Fuzzy), "fuzzy");
         assert_eq!(format_signal(&SignalType::InternalLogic), "internal_logic");
     }
+
+    #[test]
+    fn verify_summary_is_appended_from_cassette() {
+        use crate::cassette::format::{Cassette, Interaction};
+        use crate::spec::TaskSpec;
+
+        let dir = std::env::temp_dir().join("speck_show_verify");
+        let _ = std::fs::remove_dir_all(&dir);
+        let spec = TaskSpec {
+            verification: VerificationStrategy::DirectAssertion {
+                checks: vec![
+                    VerificationCheck::TestSuite {
+                        command: "cargo test".to_string(),
+                        expected: "pass".to_string(),
+                        retries: 0,
+                        retry_backoff_ms: 0,
+                        name: None,
+                    },
+                    VerificationCheck::Custom {
+                        description: "looks right".to_string(),
+                        name: None,
+                    },
+                ],
+                groups: vec![],
+                setup: vec![],
+                teardown: vec![],
+            },
+            ..TaskSpec::for_test("TASK-V", "Verified task")
+        };
+        SpecStore::new(&ServiceContext::live(), &dir).save_task_spec(&spec).unwrap();
+
+        let cassette = Cassette {
+            name: "show_verify".into(),
+            recorded_at: Utc::now(),
+            commit: "abc".into(),
+            interactions: vec![Interaction {
+                seq: 0,
+                port: "shell".into(),
+                method: "run".into(),
+                input: serde_json::json!({"command": "cargo test"}),
+                output: serde_json::json!({"Ok": {"exit_code": 0, "stdout": "", "stderr": ""}}),
+            }],
+        };
+        let path = dir.join("verify.cassette.yaml");
+        std::fs::write(&path, serde_yaml::to_string(&cassette).unwrap()).unwrap();
+
+        let can_verify = cassette_can_verify(&path);
+        let ctx = ServiceContext::replaying(&path).unwrap();
+        let summary = format_verify_summary(&validate::validate(&ctx, &spec));
+        let ctx = ServiceContext::replaying(&path).unwrap();
+        let shown = run_verify(&ctx, "TASK-V", can_verify, Some(&dir));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert!(can_verify);
+        assert!(shown.is_ok());
+        assert_eq!(
+            summary,
+            "Verification: FAILED (1/2 checks)\n  [PASS] test-suite: cargo test\n  \
+             [FAIL] custom: looks right"
+        );
+    }
 }
@@ -106,31 +106,34 @@ mod tests {
         std::fs::create_dir_all(&tasks_dir).unwrap();
 
         let spec1 = TaskSpec {
-            id: "TASK-1".to_string(),
-            title: "First task".to_string(),
-            requirement: None,
-            context: None,
             acceptance_criteria: vec!["works".to_string()],
-            signal_type: SignalType::Clear,
             verification: VerificationStrategy::DirectAssertion {
                 checks: vec![VerificationCheck::TestSuite {
                     command: "cargo test".to_string(),
                     expected: "pass".to_string(),
+                    retries: 0,
+                    retry_backoff_ms: 0,
+                    name: None,
                 }],
+                groups: vec![],
+                setup: vec![],
+                teardown: vec![],
             },
-            affected_globs: None,
+            ..TaskSpec::for_test("TASK-1", "First task")
         };
         let spec2 = TaskSpec {
-            id: "TASK-2".to_string(),
-            title: "Second task".to_string(),
-            requirement: None,
-            context: None,
             acceptance_criteria: vec!["also works".to_string()],
             signal_type: SignalType::Fuzzy,
             verification: VerificationStrategy::DirectAssertion {
-                checks: vec![VerificationCheck::Custom { description: "manual check".to_string() }],
+                checks: vec![VerificationCheck::Custom {
+                    description: "manual check".to_string(),
+                    name: None,
+                }],
+                groups: vec![],
+                setup: vec![],
+                teardown: vec![],
             },
-            affected_globs: None,
+            ..TaskSpec::for_test("TASK-2", "Second task")
         };
 
         std::fs::write(tasks_dir.join("TASK-1.yaml"), serde_yaml::to_string(&spec1).unwrap())
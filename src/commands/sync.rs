@@ -3,6 +3,12 @@
 use std::path::{Path, PathBuf};
 
 use crate::context::ServiceContext;
+use crate::error::SpeckError;
+use crate::plan::reconcile::{
+    detect_circular_dependencies, detect_high_confidence_merges, detect_partial_module_overlap,
+};
+use crate::plan::similarity::TokenJaccard;
+use crate::spec::TaskSpec;
 use crate::store::SpecStore;
 use crate::sync::beads;
 
@@ -10,25 +16,41 @@ use crate::sync::beads;
 ///
 /// # Errors
 ///
-/// Returns an error string if sync target is invalid or sync fails.
-pub fn run(target: &str, dry_run: bool) -> Result<(), String> {
+/// Returns an error if sync target is invalid or sync fails.
+pub fn run(
+    target: &str,
+    dry_run: bool,
+    reconcile_first: bool,
+    include_disabled: bool,
+) -> Result<(), SpeckError> {
     let ctx = ServiceContext::live();
-    run_with_context(&ctx, target, dry_run, None)
+    run_with_context(&ctx, target, dry_run, reconcile_first, include_disabled, None)
 }
 
 /// Execute the `sync` command with a provided service context.
 ///
+/// With `reconcile_first`, the local (non-LLM) reconciliation checks run
+/// before anything is synced, and sync is refused if any specs should
+/// confidently be merged.
+///
+/// Disabled specs are left out unless `include_disabled` is set.
+///
 /// # Errors
 ///
-/// Returns an error string if sync target is invalid or sync fails.
+/// Returns an error if sync target is invalid, local reconciliation
+/// finds merges, or sync fails.
 pub fn run_with_context(
     ctx: &ServiceContext,
     target: &str,
     dry_run: bool,
+    reconcile_first: bool,
+    include_disabled: bool,
     override_root: Option<&Path>,
-) -> Result<(), String> {
+) -> Result<(), SpeckError> {
     if target != "beads" {
-        return Err(format!("Unknown sync target: {target}. Supported targets: beads"));
+        return Err(SpeckError::Usage(format!(
+            "Unknown sync target: {target}. Supported targets: beads"
+        )));
     }
 
     let root = match override_root {
@@ -47,11 +69,16 @@ pub fn run_with_context(
     for id in &spec_ids {
         specs.push(store.load_task_spec(id)?);
     }
+    specs.retain(|spec| include_disabled || !spec.disabled);
+
+    if reconcile_first {
+        check_reconciled(&specs)?;
+    }
 
     let existing_issues =
         ctx.issues.list_issues(None).map_err(|e| format!("Failed to list existing issues: {e}"))?;
 
-    let actions = beads::plan_sync(&specs, &existing_issues);
+    let actions = beads::plan_sync(&specs, &existing_issues, ctx.issues.supports_labels());
 
     if dry_run {
         println!("Dry run — would perform:");
@@ -65,6 +92,36 @@ pub fn run_with_context(
     Ok(())
 }
 
+/// Run local reconciliation and refuse to sync on high-confidence merges.
+///
+/// Specs with identical module sets, or rated similar at or above the merge
+/// threshold, block the sync. Partial module overlaps and circular
+/// dependencies are reported but do not.
+fn check_reconciled(specs: &[TaskSpec]) -> Result<(), String> {
+    for cycle in detect_circular_dependencies(specs) {
+        eprintln!("Warning: circular dependency: {}", cycle.join(" -> "));
+    }
+
+    let merges = detect_high_confidence_merges(specs, &TokenJaccard);
+    for m in detect_partial_module_overlap(specs) {
+        let blocking = merges.iter().any(|b| m.task_ids.iter().all(|id| b.task_ids.contains(id)));
+        if !blocking {
+            eprintln!("Warning: {} may overlap ({})", m.task_ids.join(", "), m.reason);
+        }
+    }
+    if merges.is_empty() {
+        return Ok(());
+    }
+    println!("Reconcile found specs that should be merged before syncing:");
+    for m in &merges {
+        println!("  - {} -> \"{}\" ({})", m.task_ids.join(", "), m.merged_title, m.reason);
+    }
+    Err(format!(
+        "Refusing to sync: {} merge suggestion(s) found. Resolve them and re-run sync.",
+        merges.len()
+    ))
+}
+
 fn store_root() -> PathBuf {
     std::env::var("SPECK_STORE").map_or_else(|_| PathBuf::from(".speck"), PathBuf::from)
 }
@@ -84,16 +141,103 @@ mod tests {
     #[test]
     fn sync_rejects_unknown_target() {
         let ctx = test_context();
-        let result = run_with_context(&ctx, "unknown", false, None);
+        let result = run_with_context(&ctx, "unknown", false, false, false, None);
         assert!(result.is_err());
-        assert!(result.unwrap_err().contains("Unknown sync target"));
+        let err = result.unwrap_err();
+        assert!(matches!(err, SpeckError::Usage(ref msg) if msg.contains("Unknown sync target")));
     }
 
     #[test]
     fn sync_dry_run_empty_store() {
         let ctx = test_context();
         let dir = PathBuf::from("/tmp/speck_test_sync_empty_nonexistent");
-        let result = run_with_context(&ctx, "beads", true, Some(&dir));
+        let result = run_with_context(&ctx, "beads", true, false, false, Some(&dir));
         assert!(result.is_ok());
     }
+
+    fn spec_with_modules(id: &str, modules: &[&str]) -> TaskSpec {
+        use crate::spec::TaskContext;
+        TaskSpec {
+            context: Some(TaskContext {
+                modules: modules.iter().map(|m| (*m).to_string()).collect(),
+                patterns: None,
+                dependencies: vec![],
+            }),
+            ..TaskSpec::for_test(id, &format!("Task {id}"))
+        }
+    }
+
+    #[test]
+    fn reconcile_first_aborts_on_local_merge() {
+        let ctx = test_context();
+        let dir = std::env::temp_dir().join("speck_test_sync_reconcile_merge");
+        let _ = std::fs::remove_dir_all(&dir);
+        let store = SpecStore::new(&ctx, &dir);
+        store.save_task_spec(&spec_with_modules("TASK-1", &["exporter"])).unwrap();
+        store.save_task_spec(&spec_with_modules("TASK-2", &["exporter"])).unwrap();
+
+        // The panicking issue tracker proves sync stops before listing issues.
+        let result = run_with_context(&ctx, "beads", true, true, false, Some(&dir));
+
+        let _ = std::fs::remove_dir_all(&dir);
+        let err = result.unwrap_err();
+        assert!(err.to_string().contains("Refusing to sync: 1 merge suggestion(s)"), "{err}");
+    }
+
+    /// Runs `sync --dry-run --reconcile-first` over a store holding `specs`,
+    /// against a tracker with no issues.
+    fn reconcile_first_dry_run(name: &str, specs: &[TaskSpec]) -> Result<(), SpeckError> {
+        let dir = std::env::temp_dir().join(name);
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let cassette = crate::cassette::format::Cassette {
+            name: name.into(),
+            recorded_at: chrono::Utc::now(),
+            commit: "abc".into(),
+            interactions: vec![crate::cassette::format::Interaction {
+                seq: 0,
+                port: "issues".into(),
+                method: "list_issues".into(),
+                input: serde_json::json!({}),
+                output: serde_json::json!({"Ok": []}),
+            }],
+        };
+        let cassette_path = dir.join("sync.cassette.yaml");
+        std::fs::write(&cassette_path, serde_yaml::to_string(&cassette).unwrap()).unwrap();
+        let mut ctx = ServiceContext::replaying(&cassette_path).unwrap();
+        ctx.fs = Box::new(crate::adapters::live::filesystem::LiveFileSystem);
+        let store = SpecStore::new(&ctx, &dir);
+        for spec in specs {
+            store.save_task_spec(spec).unwrap();
+        }
+
+        let result = run_with_context(&ctx, "beads", true, true, false, Some(&dir));
+
+        let _ = std::fs::remove_dir_all(&dir);
+        result
+    }
+
+    #[test]
+    fn reconcile_first_proceeds_without_merges() {
+        let result = reconcile_first_dry_run(
+            "speck_test_sync_reconcile_clean",
+            &[
+                spec_with_modules("TASK-1", &["exporter"]),
+                spec_with_modules("TASK-2", &["importer"]),
+            ],
+        );
+        assert!(result.is_ok(), "{result:?}");
+    }
+
+    #[test]
+    fn reconcile_first_proceeds_when_specs_share_one_of_several_modules() {
+        let result = reconcile_first_dry_run(
+            "speck_test_sync_reconcile_partial",
+            &[
+                spec_with_modules("TASK-1", &["cli", "exporter"]),
+                spec_with_modules("TASK-2", &["cli", "importer"]),
+            ],
+        );
+        assert!(result.is_ok(), "{result:?}");
+    }
 }
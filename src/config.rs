@@ -0,0 +1,164 @@
+//! Project configuration loaded from `.speck/config.toml`.
+//!
+//! Only a flat subset of TOML is understood: `key = "string"` and
+//! `key = true|false` pairs, blank lines, and `#` comments. Settings here
+//! are defaults; explicit CLI flags and environment variables win.
+
+use std::path::{Path, PathBuf};
+
+use crate::cassette::shell_output::ShellRecordMode;
+
+/// Default location of the project config file, relative to the working directory.
+pub const DEFAULT_CONFIG_PATH: &str = ".speck/config.toml";
+
+/// Settings read from a speck config file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SpeckConfig {
+    /// Record port interactions per port under this directory (`--output-dir`).
+    pub output_dir: Option<PathBuf>,
+    /// Replay port interactions from this cassette (`SPECK_REPLAY`).
+    pub replay: Option<PathBuf>,
+    /// Record port interactions to a timestamped session (`SPECK_REC`).
+    pub record: Option<bool>,
+    /// How much shell output recordings keep (`SPECK_REC_SHELL`).
+    pub record_shell: Option<ShellRecordMode>,
+    /// Resolve module references with embeddings (`show --links --semantic`).
+    pub semantic_linkage: Option<bool>,
+}
+
+impl SpeckConfig {
+    /// Load the config for a command invocation.
+    ///
+    /// An explicit `path` must exist. Without one, the default
+    /// `.speck/config.toml` is read if present and ignored otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an explicit path does not exist, or if the file
+    /// cannot be read or parsed.
+    pub fn resolve(path: Option<&Path>) -> Result<Self, String> {
+        if let Some(path) = path {
+            return Self::load(path);
+        }
+        let default = Path::new(DEFAULT_CONFIG_PATH);
+        if default.exists() {
+            Self::load(default)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    /// Load config from the file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file does not exist, cannot be read, or
+    /// contains an unrecognised or malformed setting.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        if !path.exists() {
+            return Err(format!("config file not found: {}", path.display()));
+        }
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("failed to read config file {}: {e}", path.display()))?;
+        Self::parse(&content).map_err(|e| format!("{}: {e}", path.display()))
+    }
+
+    /// Parse config from its text contents.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the offending line for unknown keys or
+    /// malformed values.
+    pub fn parse(content: &str) -> Result<Self, String> {
+        let mut config = Self::default();
+        for (idx, raw) in content.lines().enumerate() {
+            let line = raw.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let lineno = idx + 1;
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| format!("line {lineno}: expected `key = value`"))?;
+            let key = key.trim();
+            let value = value.trim();
+            match key {
+                "output_dir" => {
+                    config.output_dir = Some(PathBuf::from(parse_string(value, lineno)?));
+                }
+                "replay" => config.replay = Some(PathBuf::from(parse_string(value, lineno)?)),
+                "record" => config.record = Some(parse_bool(value, lineno)?),
+                "record_shell" => {
+                    let mode = parse_string(value, lineno)?;
+                    config.record_shell =
+                        Some(mode.parse().map_err(|e| format!("line {lineno}: {e}"))?);
+                }
+                "semantic_linkage" => config.semantic_linkage = Some(parse_bool(value, lineno)?),
+                other => return Err(format!("line {lineno}: unknown setting '{other}'")),
+            }
+        }
+        Ok(config)
+    }
+}
+
+fn parse_string(value: &str, lineno: usize) -> Result<String, String> {
+    value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .map(str::to_string)
+        .ok_or_else(|| format!("line {lineno}: expected a quoted string, got {value}"))
+}
+
+fn parse_bool(value: &str, lineno: usize) -> Result<bool, String> {
+    match value {
+        "true" => Ok(true),
+        "false" => Ok(false),
+        other => Err(format!("line {lineno}: expected true or false, got {other}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_known_settings_and_skips_comments() {
+        let config = SpeckConfig::parse(
+            "# speck config\n\noutput_dir = \"cassettes\"\nreplay = \"a.yaml\"\nrecord = true\n\
+             record_shell = \"minimal\"\nsemantic_linkage = true\n",
+        )
+        .unwrap();
+        assert_eq!(config.output_dir, Some(PathBuf::from("cassettes")));
+        assert_eq!(config.replay, Some(PathBuf::from("a.yaml")));
+        assert_eq!(config.record, Some(true));
+        assert_eq!(config.record_shell, Some(ShellRecordMode::Minimal));
+        assert_eq!(config.semantic_linkage, Some(true));
+    }
+
+    #[test]
+    fn rejects_unknown_setting() {
+        let err = SpeckConfig::parse("colour = \"blue\"\n").unwrap_err();
+        assert!(err.contains("unknown setting 'colour'"), "got: {err}");
+    }
+
+    #[test]
+    fn loads_from_custom_path() {
+        let dir = std::env::temp_dir().join("speck_config_custom_path");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("ci.toml");
+        std::fs::write(&path, "output_dir = \"/tmp/ci-cassettes\"\n").unwrap();
+
+        let config = SpeckConfig::resolve(Some(&path)).unwrap();
+        assert_eq!(config.output_dir, Some(PathBuf::from("/tmp/ci-cassettes")));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn missing_explicit_path_is_an_error() {
+        let path = std::env::temp_dir().join("speck_config_missing/none.toml");
+        let err = SpeckConfig::resolve(Some(&path)).unwrap_err();
+        assert!(err.contains("config file not found"), "got: {err}");
+    }
+}
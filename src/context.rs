@@ -4,21 +4,24 @@ use std::path::Path;
 use std::sync::{Arc, Mutex};
 
 use crate::adapters::live::clock::LiveClock;
-use crate::adapters::live::filesystem::LiveFileSystem;
+use crate::adapters::live::env::LiveEnvironment;
+use crate::adapters::live::filesystem::{LiveAsyncFileSystem, LiveFileSystem};
 use crate::adapters::live::git::LiveGitRepo;
 use crate::adapters::live::id_gen::LiveIdGenerator;
 use crate::adapters::live::issues::LiveIssueTracker;
 use crate::adapters::live::llm::LiveLlmClient;
 use crate::adapters::live::shell::LiveShellExecutor;
 use crate::adapters::recording::clock::RecordingClock;
-use crate::adapters::recording::filesystem::RecordingFileSystem;
+use crate::adapters::recording::env::RecordingEnvironment;
+use crate::adapters::recording::filesystem::{RecordingAsyncFileSystem, RecordingFileSystem};
 use crate::adapters::recording::git::RecordingGitRepo;
 use crate::adapters::recording::id_gen::RecordingIdGenerator;
 use crate::adapters::recording::issues::RecordingIssueTracker;
 use crate::adapters::recording::llm::RecordingLlmClient;
 use crate::adapters::recording::shell::RecordingShellExecutor;
 use crate::adapters::replaying::clock::ReplayingClock;
-use crate::adapters::replaying::filesystem::ReplayingFileSystem;
+use crate::adapters::replaying::env::ReplayingEnvironment;
+use crate::adapters::replaying::filesystem::{ReplayingAsyncFileSystem, ReplayingFileSystem};
 use crate::adapters::replaying::git::ReplayingGitRepo;
 use crate::adapters::replaying::id_gen::ReplayingIdGenerator;
 use crate::adapters::replaying::issues::ReplayingIssueTracker;
@@ -26,8 +29,10 @@ use crate::adapters::replaying::llm::ReplayingLlmClient;
 use crate::adapters::replaying::shell::ReplayingShellExecutor;
 use crate::cassette::config::CassetteConfig;
 use crate::cassette::session::RecordingSession;
+use crate::cassette::shell_output::ShellRecordMode;
 use crate::ports::{
-    Clock, FileSystem, GitRepo, IdGenerator, IssueTracker, LlmClient, ShellExecutor,
+    AsyncFileSystem, Clock, Environment, FileSystem, GitRepo, IdGenerator, IssueTracker, LlmClient,
+    ShellExecutor,
 };
 
 /// Bundles all port trait objects into a single context.
@@ -36,6 +41,8 @@ pub struct ServiceContext {
     pub clock: Box<dyn Clock>,
     /// Filesystem port for file I/O operations.
     pub fs: Box<dyn FileSystem>,
+    /// Asynchronous filesystem port for concurrent file I/O.
+    pub async_fs: Box<dyn AsyncFileSystem>,
     /// Git repository port for version-control queries.
     pub git: Box<dyn GitRepo>,
     /// Shell executor port for running external commands.
@@ -46,6 +53,8 @@ pub struct ServiceContext {
     pub llm: Box<dyn LlmClient>,
     /// Issue tracker port for managing work items.
     pub issues: Box<dyn IssueTracker>,
+    /// Environment port for reading environment variables.
+    pub env: Box<dyn Environment>,
 }
 
 impl ServiceContext {
@@ -55,18 +64,21 @@ impl ServiceContext {
         Self {
             clock: Box::new(LiveClock),
             fs: Box::new(LiveFileSystem),
+            async_fs: Box::new(LiveAsyncFileSystem),
             git: Box::new(LiveGitRepo),
             shell: Box::new(LiveShellExecutor),
             id_gen: Box::new(LiveIdGenerator::new()),
             llm: Box::new(LiveLlmClient::new()),
             issues: Box::new(LiveIssueTracker),
+            env: Box::new(LiveEnvironment),
         }
     }
 
     /// Create a recording context that wraps live adapters with recorders.
     ///
     /// All interactions are recorded to per-port cassette files in a
-    /// timestamped directory under `.speck/cassettes/`.
+    /// timestamped directory under `.speck/cassettes/`, with shell output
+    /// kept according to `shell_mode`.
     ///
     /// Returns both the context and the recording session. The session must
     /// be finished after the context is dropped to write the cassette files.
@@ -74,20 +86,43 @@ impl ServiceContext {
     /// # Errors
     ///
     /// Returns an error if the recording session cannot be initialized.
-    pub fn recording() -> Result<(Self, RecordingSession), String> {
-        let session = RecordingSession::new()?;
+    pub fn recording(shell_mode: ShellRecordMode) -> Result<(Self, RecordingSession), String> {
+        let session = RecordingSession::new()?.with_shell_mode(shell_mode);
+        let ctx = Self::recording_with(&session);
+        Ok((ctx, session))
+    }
+
+    /// Create a recording context that writes each port's cassette directly
+    /// to `dir/<port>/session.yaml`, with shell output kept according to
+    /// `shell_mode`.
+    ///
+    /// No session is returned: each port's recorder flushes when the context
+    /// is dropped. Ports that were never called produce no cassette file.
+    #[must_use]
+    pub fn recording_per_port(dir: &Path, shell_mode: ShellRecordMode) -> Self {
+        Self::recording_with(&RecordingSession::per_port(dir).with_shell_mode(shell_mode))
+    }
 
-        let ctx = Self {
+    /// Wrap live adapters with the given session's recorders.
+    fn recording_with(session: &RecordingSession) -> Self {
+        Self {
             clock: Box::new(RecordingClock::new(Box::new(LiveClock), Arc::clone(&session.clock))),
             fs: Box::new(RecordingFileSystem::new(
                 Box::new(LiveFileSystem),
                 Arc::clone(&session.fs),
             )),
-            git: Box::new(RecordingGitRepo::new(Box::new(LiveGitRepo), Arc::clone(&session.git))),
-            shell: Box::new(RecordingShellExecutor::new(
-                Box::new(LiveShellExecutor),
-                Arc::clone(&session.shell),
+            async_fs: Box::new(RecordingAsyncFileSystem::new(
+                Box::new(LiveAsyncFileSystem),
+                Arc::clone(&session.fs),
             )),
+            git: Box::new(RecordingGitRepo::new(Box::new(LiveGitRepo), Arc::clone(&session.git))),
+            shell: Box::new(
+                RecordingShellExecutor::new(
+                    Box::new(LiveShellExecutor),
+                    Arc::clone(&session.shell),
+                )
+                .with_mode(session.shell_mode),
+            ),
             id_gen: Box::new(RecordingIdGenerator::new(
                 Box::new(LiveIdGenerator::new()),
                 Arc::clone(&session.id_gen),
@@ -100,9 +135,11 @@ impl ServiceContext {
                 Box::new(LiveIssueTracker),
                 Arc::clone(&session.issues),
             )),
-        };
-
-        Ok((ctx, session))
+            env: Box::new(RecordingEnvironment::new(
+                Box::new(LiveEnvironment),
+                Arc::clone(&session.env),
+            )),
+        }
     }
 
     /// Creates a replaying context from a monolithic cassette file.
@@ -118,11 +155,13 @@ impl ServiceContext {
         Ok(Self {
             clock: Box::new(ReplayingClock::new(Arc::clone(&replayer))),
             fs: Box::new(ReplayingFileSystem::new(Arc::clone(&replayer))),
+            async_fs: Box::new(ReplayingAsyncFileSystem::new(Arc::clone(&replayer))),
             git: Box::new(ReplayingGitRepo::new(Arc::clone(&replayer))),
             shell: Box::new(ReplayingShellExecutor::new(Arc::clone(&replayer))),
             id_gen: Box::new(ReplayingIdGenerator::new(Arc::clone(&replayer))),
             llm: Box::new(ReplayingLlmClient::new(Arc::clone(&replayer))),
-            issues: Box::new(ReplayingIssueTracker::new(replayer)),
+            issues: Box::new(ReplayingIssueTracker::new(Arc::clone(&replayer))),
+            env: Box::new(ReplayingEnvironment::new(replayer)),
         })
     }
 
@@ -143,10 +182,17 @@ impl ServiceContext {
             Some(r) => Box::new(ReplayingClock::new(r)),
             None => Box::new(ReplayingClock::unconfigured()),
         };
-        let fs: Box<dyn FileSystem> = match wrap(replayers.fs) {
-            Some(r) => Box::new(ReplayingFileSystem::new(r)),
-            None => Box::new(ReplayingFileSystem::unconfigured()),
-        };
+        let (fs, async_fs): (Box<dyn FileSystem>, Box<dyn AsyncFileSystem>) =
+            match wrap(replayers.fs) {
+                Some(r) => (
+                    Box::new(ReplayingFileSystem::new(Arc::clone(&r))),
+                    Box::new(ReplayingAsyncFileSystem::new(r)),
+                ),
+                None => (
+                    Box::new(ReplayingFileSystem::unconfigured()),
+                    Box::new(ReplayingAsyncFileSystem::unconfigured()),
+                ),
+            };
         let git: Box<dyn GitRepo> = match wrap(replayers.git) {
             Some(r) => Box::new(ReplayingGitRepo::new(r)),
             None => Box::new(ReplayingGitRepo::unconfigured()),
@@ -160,6 +206,9 @@ impl ServiceContext {
             None => Box::new(ReplayingIdGenerator::unconfigured()),
         };
         let llm: Box<dyn LlmClient> = match wrap(replayers.llm) {
+            Some(r) if config.match_llm_prompts => {
+                Box::new(ReplayingLlmClient::new(r).with_prompt_matching())
+            }
             Some(r) => Box::new(ReplayingLlmClient::new(r)),
             None => Box::new(ReplayingLlmClient::unconfigured()),
         };
@@ -168,7 +217,109 @@ impl ServiceContext {
             None => Box::new(ReplayingIssueTracker::unconfigured()),
         };
 
-        Ok(Self { clock, fs, git, shell, id_gen, llm, issues })
+        let env: Box<dyn Environment> = match wrap(replayers.env) {
+            Some(r) => Box::new(ReplayingEnvironment::new(r)),
+            None => Box::new(ReplayingEnvironment::unconfigured()),
+        };
+
+        Ok(Self { clock, fs, async_fs, git, shell, id_gen, llm, issues, env })
+    }
+}
+
+/// Test-only constructor and builders.
+///
+/// Start from [`ServiceContext::testing`] and swap in the adapters a test
+/// exercises, e.g. `ServiceContext::testing().with_shell(FakeShell)`.
+#[cfg(test)]
+impl ServiceContext {
+    /// Create a context with in-memory or no-op adapters for all ports.
+    ///
+    /// The filesystem (shared by the sync and async ports), environment, and
+    /// issue tracker start empty; the clock is fixed at the Unix epoch; git
+    /// reports a clean tree; every shell command succeeds silently; and LLM
+    /// completions fail.
+    #[must_use]
+    pub fn testing() -> Self {
+        use crate::adapters::memory::{
+            clock::FixedClock, env::MemEnvironment, filesystem::MemFileSystem, git::NoopGitRepo,
+            id_gen::SequentialIdGenerator, issues::MemIssueTracker, llm::NoopLlmClient,
+            shell::NoopShellExecutor,
+        };
+        let fs = MemFileSystem::new();
+        Self {
+            clock: Box::new(FixedClock::default()),
+            fs: Box::new(fs.clone()),
+            async_fs: Box::new(fs),
+            git: Box::new(NoopGitRepo),
+            shell: Box::new(NoopShellExecutor),
+            id_gen: Box::new(SequentialIdGenerator::default()),
+            llm: Box::new(NoopLlmClient),
+            issues: Box::new(MemIssueTracker::default()),
+            env: Box::new(MemEnvironment::default()),
+        }
+    }
+
+    /// Replace the clock port.
+    #[must_use]
+    pub fn with_clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Box::new(clock);
+        self
+    }
+
+    /// Replace the filesystem port.
+    #[must_use]
+    pub fn with_fs(mut self, fs: impl FileSystem + 'static) -> Self {
+        self.fs = Box::new(fs);
+        self
+    }
+
+    /// Replace the async filesystem port.
+    #[must_use]
+    pub fn with_async_fs(mut self, async_fs: impl AsyncFileSystem + 'static) -> Self {
+        self.async_fs = Box::new(async_fs);
+        self
+    }
+
+    /// Replace the git port.
+    #[must_use]
+    pub fn with_git(mut self, git: impl GitRepo + 'static) -> Self {
+        self.git = Box::new(git);
+        self
+    }
+
+    /// Replace the shell port.
+    #[must_use]
+    pub fn with_shell(mut self, shell: impl ShellExecutor + 'static) -> Self {
+        self.shell = Box::new(shell);
+        self
+    }
+
+    /// Replace the ID generator port.
+    #[must_use]
+    pub fn with_id_gen(mut self, id_gen: impl IdGenerator + 'static) -> Self {
+        self.id_gen = Box::new(id_gen);
+        self
+    }
+
+    /// Replace the LLM port.
+    #[must_use]
+    pub fn with_llm(mut self, llm: impl LlmClient + 'static) -> Self {
+        self.llm = Box::new(llm);
+        self
+    }
+
+    /// Replace the issue tracker port.
+    #[must_use]
+    pub fn with_issues(mut self, issues: impl IssueTracker + 'static) -> Self {
+        self.issues = Box::new(issues);
+        self
+    }
+
+    /// Replace the environment port.
+    #[must_use]
+    pub fn with_env(mut self, env: impl Environment + 'static) -> Self {
+        self.env = Box::new(env);
+        self
     }
 }
 
@@ -226,6 +377,28 @@ mod tests {
         let _ = std::fs::remove_dir_all(&dir);
     }
 
+    #[test]
+    #[should_panic(expected = "method=\"create_dir_all\"")]
+    fn replaying_unrecorded_create_dir_all_panics() {
+        let dir = std::env::temp_dir().join("speck_ctx_replaying_create_dir");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("test.cassette.yaml");
+
+        write_cassette_file(
+            &path,
+            vec![Interaction {
+                seq: 0,
+                port: "fs".into(),
+                method: "write".into(),
+                input: json!(null),
+                output: json!(null),
+            }],
+        );
+
+        let ctx = ServiceContext::replaying(&path).unwrap();
+        let _ = ctx.fs.create_dir_all(Path::new("/project/.spec-cache"));
+    }
+
     #[test]
     fn replaying_from_with_per_port_cassettes() {
         let dir = std::env::temp_dir().join("speck_ctx_replaying_from");
@@ -252,6 +425,33 @@ mod tests {
         let _ = std::fs::remove_dir_all(&dir);
     }
 
+    #[test]
+    fn recording_per_port_writes_one_file_per_used_port() {
+        let dir = std::env::temp_dir().join("speck_ctx_recording_per_port");
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let ctx = ServiceContext::recording_per_port(&dir, ShellRecordMode::Full);
+        let _ = ctx.clock.now();
+        let id = ctx.id_gen.generate_id();
+        drop(ctx);
+
+        let mut ports: Vec<String> = std::fs::read_dir(&dir)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        ports.sort();
+        assert_eq!(ports, vec!["clock", "id_gen"]);
+
+        let replay = CassetteConfig {
+            id_gen: Some(dir.join("id_gen/session.yaml")),
+            ..CassetteConfig::default()
+        };
+        let replayed = ServiceContext::replaying_from(&replay).unwrap();
+        assert_eq!(replayed.id_gen.generate_id(), id);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
     #[test]
     #[should_panic(expected = "no cassette configured for port")]
     fn replaying_from_panics_on_unconfigured_port() {
@@ -259,4 +459,30 @@ mod tests {
         let ctx = ServiceContext::replaying_from(&config).unwrap();
         let _ = ctx.clock.now();
     }
+
+    #[test]
+    fn testing_context_uses_in_memory_adapters() {
+        struct FailingShell;
+        impl ShellExecutor for FailingShell {
+            fn run(
+                &self,
+                _command: &str,
+            ) -> Result<crate::ports::ShellOutput, Box<dyn std::error::Error + Send + Sync>>
+            {
+                Err("boom".into())
+            }
+        }
+
+        let ctx = ServiceContext::testing();
+        ctx.fs.write(Path::new("/mem/a.txt"), "hello").unwrap();
+        assert_eq!(ctx.fs.read_to_string(Path::new("/mem/a.txt")).unwrap(), "hello");
+        assert_eq!(ctx.shell.run("anything").unwrap().exit_code, 0);
+        assert_eq!(ctx.env.var("HOME"), None);
+        assert_ne!(ctx.id_gen.generate_id(), ctx.id_gen.generate_id());
+        let issue = ctx.issues.create_issue("t", "b", &[]).unwrap();
+        assert_eq!(ctx.issues.get_issue(&issue.id).unwrap().title, "t");
+
+        let ctx = ctx.with_shell(FailingShell);
+        assert!(ctx.shell.run("anything").is_err());
+    }
 }
@@ -0,0 +1,112 @@
+//! Crate-level error type.
+
+use std::fmt;
+
+/// An error raised by a `speck` operation, categorized by its source.
+///
+/// Each variant carries the human-readable message shown to the user, so
+/// [`Display`](fmt::Display) output matches the plain-string errors used
+/// elsewhere in the crate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SpeckError {
+    /// Reading or writing a file failed.
+    Io(String),
+    /// A document could not be parsed or serialized.
+    Parse(String),
+    /// An LLM request failed.
+    Llm(String),
+    /// A git operation failed.
+    Git(String),
+    /// A shell command could not be run.
+    Shell(String),
+    /// The spec store could not complete an operation.
+    Store(String),
+    /// Verification ran and did not pass.
+    Validation(String),
+    /// The command line or configuration was invalid.
+    Usage(String),
+    /// A spec, snapshot, or other requested item does not exist.
+    NotFound(String),
+    /// A command failed for any other reason.
+    Internal(String),
+}
+
+/// Exit code when verification ran and did not pass.
+pub const EXIT_VALIDATION_FAILED: u8 = 1;
+/// Exit code for an invalid command line, configuration, or input: unknown
+/// IDs and malformed spec documents included.
+pub const EXIT_USAGE: u8 = 2;
+/// Exit code for I/O, LLM, tracker, and other internal failures.
+pub const EXIT_INTERNAL: u8 = 3;
+
+impl SpeckError {
+    /// The message carried by this error.
+    #[must_use]
+    pub fn message(&self) -> &str {
+        match self {
+            Self::Io(msg)
+            | Self::Parse(msg)
+            | Self::Llm(msg)
+            | Self::Git(msg)
+            | Self::Shell(msg)
+            | Self::Store(msg)
+            | Self::Validation(msg)
+            | Self::Usage(msg)
+            | Self::NotFound(msg)
+            | Self::Internal(msg) => msg,
+        }
+    }
+
+    /// The process exit code `speck` uses for this error.
+    #[must_use]
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            Self::Validation(_) => EXIT_VALIDATION_FAILED,
+            Self::Usage(_) | Self::NotFound(_) | Self::Parse(_) => EXIT_USAGE,
+            _ => EXIT_INTERNAL,
+        }
+    }
+}
+
+impl fmt::Display for SpeckError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+impl std::error::Error for SpeckError {}
+
+/// Uncategorized string errors from command helpers are internal errors.
+impl From<String> for SpeckError {
+    fn from(msg: String) -> Self {
+        Self::Internal(msg)
+    }
+}
+
+impl From<SpeckError> for String {
+    fn from(err: SpeckError) -> Self {
+        err.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_is_the_bare_message() {
+        let err = SpeckError::Store("Failed to promote task spec T-1: busy".to_string());
+        assert_eq!(err.to_string(), "Failed to promote task spec T-1: busy");
+        assert_eq!(String::from(err), "Failed to promote task spec T-1: busy");
+    }
+
+    #[test]
+    fn exit_codes_distinguish_validation_user_and_internal_errors() {
+        assert_eq!(SpeckError::Validation("failed".into()).exit_code(), 1);
+        assert_eq!(SpeckError::Usage("bad flag".into()).exit_code(), 2);
+        assert_eq!(SpeckError::NotFound("no spec T-9".into()).exit_code(), 2);
+        assert_eq!(SpeckError::Parse("bad yaml".into()).exit_code(), 2);
+        assert_eq!(SpeckError::Llm("rate limited".into()).exit_code(), 3);
+        assert_eq!(SpeckError::Internal("boom".into()).exit_code(), 3);
+    }
+}
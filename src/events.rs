@@ -0,0 +1,152 @@
+//! Structured events for embedding `speck` as a library.
+//!
+//! Commands that support it report what they do as [`Event`]s sent to an
+//! [`EventSink`] instead of printing. [`ConsoleSink`] renders events as the
+//! CLI's normal output; a `Vec<Event>` collects them for inspection.
+
+use crate::validate::{self, CheckResult, OutputFormat, PlannedCheck, ValidationResult};
+
+/// Something a command did, reported as it happens.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum Event {
+    /// One check of a spec finished, or was served from the verification cache.
+    CheckFinished {
+        /// The spec the check belongs to.
+        spec_id: String,
+        /// The check's outcome.
+        check: CheckResult,
+    },
+    /// A spec finished validating; follows the spec's [`Event::CheckFinished`] events.
+    SpecValidated {
+        /// The full result, including every check.
+        result: ValidationResult,
+        /// Whether the result came from the verification cache.
+        cached: bool,
+    },
+    /// A dry run resolved the checks a spec would run.
+    ChecksPlanned {
+        /// The spec the checks belong to.
+        spec_id: String,
+        /// The resolved checks.
+        checks: Vec<PlannedCheck>,
+    },
+    /// A spec was not validated.
+    SpecSkipped {
+        /// The skipped spec.
+        spec_id: String,
+        /// Why it was skipped.
+        reason: String,
+    },
+    /// A run over every spec found the store empty.
+    NoSpecsFound,
+    /// A validation run finished.
+    ValidationFinished {
+        /// Whether every validated spec passed.
+        passed: bool,
+        /// Whether the run was a dry run.
+        dry_run: bool,
+    },
+}
+
+/// Receives the [`Event`]s a command emits, in order.
+pub trait EventSink {
+    /// Handles one event.
+    fn emit(&mut self, event: Event);
+}
+
+/// Collects every event, for callers that inspect them afterwards.
+impl EventSink for Vec<Event> {
+    fn emit(&mut self, event: Event) {
+        self.push(event);
+    }
+}
+
+/// The default sink: prints events as the `speck` CLI does.
+///
+/// JUnit results are buffered and printed as one document when the run
+/// finishes.
+#[derive(Debug)]
+pub struct ConsoleSink {
+    format: OutputFormat,
+    junit_results: Vec<ValidationResult>,
+}
+
+impl ConsoleSink {
+    /// A sink printing in `format`.
+    #[must_use]
+    pub fn new(format: OutputFormat) -> Self {
+        Self { format, junit_results: Vec::new() }
+    }
+}
+
+impl EventSink for ConsoleSink {
+    fn emit(&mut self, event: Event) {
+        let format = self.format;
+        match event {
+            Event::CheckFinished { .. } => {}
+            Event::SpecValidated { result, .. } if format == OutputFormat::Junit => {
+                self.junit_results.push(result);
+            }
+            Event::SpecValidated { result, cached: true } if format == OutputFormat::Text => {
+                println!(
+                    "Spec: {}\n\n  [CACHED PASS] unchanged since its last passing run \
+                     (--force to re-run)\n",
+                    result.spec_id
+                );
+            }
+            Event::SpecValidated { result, cached } => print_result(&result, format, cached),
+            Event::ChecksPlanned { spec_id, checks } => print_plan(&spec_id, &checks, format),
+            Event::SpecSkipped { spec_id, reason } => {
+                if matches!(format, OutputFormat::Text | OutputFormat::Junit) {
+                    eprintln!("Skipping {spec_id}: {reason}");
+                } else {
+                    println!(
+                        "{}",
+                        serde_json::json!({ "spec_id": spec_id, "skipped": true, "reason": reason })
+                    );
+                }
+            }
+            Event::NoSpecsFound => {
+                if format == OutputFormat::Text {
+                    println!("No specs found in store.");
+                } else if format != OutputFormat::Junit {
+                    eprintln!("No specs found in store.");
+                }
+            }
+            Event::ValidationFinished { dry_run, .. } => {
+                if format == OutputFormat::Junit && !dry_run {
+                    let results = std::mem::take(&mut self.junit_results);
+                    println!("{}", validate::format_result_junit(&results));
+                }
+            }
+        }
+    }
+}
+
+/// Print one validation result in the requested format.
+fn print_result(result: &ValidationResult, format: OutputFormat, cached: bool) {
+    match format {
+        OutputFormat::Text => println!("{}", validate::format_report(result)),
+        OutputFormat::Json => println!("{}", validate::format_json(result, cached)),
+        OutputFormat::JsonLines => println!("{}", validate::format_json_line(result, cached)),
+        OutputFormat::Junit => {
+            println!("{}", validate::format_result_junit(std::slice::from_ref(result)));
+        }
+    }
+}
+
+/// Print the checks a dry run would execute in the requested format.
+fn print_plan(spec_id: &str, planned: &[PlannedCheck], format: OutputFormat) {
+    let json = serde_json::json!({ "spec_id": spec_id, "dry_run": true, "checks": planned });
+    match format {
+        // JUnit describes results, so a dry run falls back to the text plan.
+        OutputFormat::Text | OutputFormat::Junit => {
+            println!("{}", validate::format_plan(spec_id, planned));
+        }
+        OutputFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(&json).unwrap_or_default());
+        }
+        OutputFormat::JsonLines => println!("{json}"),
+    }
+}
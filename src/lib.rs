@@ -4,7 +4,10 @@ pub mod adapters;
 pub mod cassette;
 pub mod cli;
 pub mod commands;
+pub mod config;
 pub mod context;
+pub mod error;
+pub mod events;
 pub mod linkage;
 pub mod map;
 pub mod plan;
@@ -16,26 +19,45 @@ pub mod validate;
 
 use clap::Parser;
 
+use crate::error::SpeckError;
+
 /// Run the CLI with the provided arguments.
 ///
 /// # Errors
 ///
-/// Returns an error string when argument parsing fails or command execution fails.
-pub fn run<I, T>(args: I) -> Result<(), String>
+/// Returns [`SpeckError::Usage`] when argument parsing fails, and the
+/// command's error otherwise; [`SpeckError::exit_code`] gives the matching
+/// process exit code.
+pub fn run<I, T>(args: I) -> Result<(), SpeckError>
 where
     I: IntoIterator<Item = T>,
     T: Into<std::ffi::OsString> + Clone,
 {
     let cli = match cli::Cli::try_parse_from(args) {
         Ok(cli) => cli,
-        Err(err) if err.use_stderr() => return Err(err.to_string()),
+        Err(err) if err.use_stderr() => return Err(SpeckError::Usage(err.to_string())),
         Err(err) => {
             // --help or --version: print to stdout and succeed.
             let _ = err.print();
             return Ok(());
         }
     };
-    commands::dispatch(&cli.command)
+    commands::dispatch(&cli)
+}
+
+/// Run a parsed command line, sending structured [`events::Event`]s to `sink`.
+///
+/// For embedding `speck` as a library: commands that report events (currently
+/// `validate`) send them to `sink` instead of printing, so a caller can render
+/// or collect per-check results itself. Passing an [`events::ConsoleSink`]
+/// reproduces the CLI's output.
+///
+/// # Errors
+///
+/// Returns the command's error; [`SpeckError::exit_code`] gives the matching
+/// process exit code.
+pub fn run_with_sink(cli: &cli::Cli, sink: &mut dyn events::EventSink) -> Result<(), SpeckError> {
+    commands::dispatch_with_sink(cli, sink)
 }
 
 #[cfg(test)]
@@ -50,7 +72,7 @@ mod tests {
 
     #[test]
     fn run_errors_on_unknown_subcommand() {
-        let result = run(["speck", "unknown"]);
-        assert!(result.is_err());
+        let err = run(["speck", "unknown"]).unwrap_err();
+        assert_eq!(err.exit_code(), crate::error::EXIT_USAGE);
     }
 }
@@ -1,6 +1,12 @@
 //! Drift detection: identifies specs whose referenced modules have changed.
 
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+
 use crate::map::CodebaseMap;
+use crate::ports::GitRepo;
 use crate::spec::TaskSpec;
 
 use super::resolve::{resolve, LinkageResult};
@@ -92,10 +98,10 @@ fn check_spec_drift(
             (Some(_old), None) => {
                 removed_modules.push(path.clone());
             }
-            (Some(old), Some(new)) => {
-                if old.public_items != new.public_items || old.dependencies != new.dependencies {
-                    changed_modules.push(path.clone());
-                }
+            (Some(old), Some(new))
+                if old.public_items != new.public_items || old.dependencies != new.dependencies =>
+            {
+                changed_modules.push(path.clone());
             }
             _ => {}
         }
@@ -115,9 +121,40 @@ fn check_spec_drift(
     })
 }
 
+/// Last-change information per module path: `(author timestamp, author name)`.
+pub type ModuleBlame = BTreeMap<String, (DateTime<Utc>, String)>;
+
+/// Looks up when and by whom each changed or removed module in `report`
+/// was last modified.
+///
+/// Paths whose history cannot be read are omitted rather than failing the
+/// whole report.
+#[must_use]
+pub fn blame_drift(report: &DriftReport, git: &dyn GitRepo) -> ModuleBlame {
+    let mut blame = ModuleBlame::new();
+    for entry in &report.entries {
+        for path in entry.changed_modules.iter().chain(&entry.removed_modules) {
+            if blame.contains_key(path) {
+                continue;
+            }
+            if let Ok(info) = git.last_modified(Path::new(path)) {
+                blame.insert(path.clone(), info);
+            }
+        }
+    }
+    blame
+}
+
 /// Formats a drift report as a human-readable string.
 #[must_use]
 pub fn format_drift_report(report: &DriftReport) -> String {
+    format_drift_report_with_blame(report, &ModuleBlame::new())
+}
+
+/// Formats a drift report, annotating each changed or removed module found
+/// in `blame` with its last-modified time and author.
+#[must_use]
+pub fn format_drift_report_with_blame(report: &DriftReport, blame: &ModuleBlame) -> String {
     if report.is_clean() {
         return format!(
             "No drift detected between {} and {}.",
@@ -132,10 +169,10 @@ pub fn format_drift_report(report: &DriftReport) -> String {
     for entry in &report.entries {
         lines.push(format!("  Spec: {}", entry.spec_id));
         for path in &entry.changed_modules {
-            lines.push(format!("    [CHANGED] {path}"));
+            lines.push(format!("    [CHANGED] {path}{}", blame_suffix(blame, path)));
         }
         for path in &entry.removed_modules {
-            lines.push(format!("    [REMOVED] {path}"));
+            lines.push(format!("    [REMOVED] {path}{}", blame_suffix(blame, path)));
         }
         if entry.replan_recommended {
             lines.push("    -> Re-planning recommended".to_string());
@@ -149,11 +186,17 @@ pub fn format_drift_report(report: &DriftReport) -> String {
     lines.join("\n")
 }
 
+fn blame_suffix(blame: &ModuleBlame, path: &str) -> String {
+    blame.get(path).map_or_else(String::new, |(at, author)| {
+        format!(" (last modified {} by {author})", at.format("%Y-%m-%d"))
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::map::{CodebaseMap, ModuleSummary};
-    use crate::spec::{SignalType, TaskContext, TaskSpec, VerificationCheck, VerificationStrategy};
+    use crate::spec::{TaskContext, TaskSpec, VerificationCheck, VerificationStrategy};
     use chrono::Utc;
 
     fn make_map(commit: &str, modules: Vec<ModuleSummary>) -> CodebaseMap {
@@ -163,6 +206,7 @@ mod tests {
             modules,
             directory_tree: vec![],
             test_infrastructure: vec![],
+            unreadable: vec![],
         }
     }
 
@@ -171,25 +215,30 @@ mod tests {
             path: path.to_string(),
             public_items: items.into_iter().map(String::from).collect(),
             dependencies: deps.into_iter().map(String::from).collect(),
+            external_deps: vec![],
+            locations: BTreeMap::new(),
+            crate_name: None,
         }
     }
 
     fn make_spec(id: &str, modules: Vec<&str>) -> TaskSpec {
         TaskSpec {
-            id: id.to_string(),
-            title: format!("Task {id}"),
-            requirement: None,
             context: Some(TaskContext {
                 modules: modules.into_iter().map(String::from).collect(),
                 patterns: None,
                 dependencies: vec![],
             }),
             acceptance_criteria: vec!["done".to_string()],
-            signal_type: SignalType::Clear,
             verification: VerificationStrategy::DirectAssertion {
-                checks: vec![VerificationCheck::Custom { description: "check".to_string() }],
+                checks: vec![VerificationCheck::Custom {
+                    description: "check".to_string(),
+                    name: None,
+                }],
+                groups: vec![],
+                setup: vec![],
+                teardown: vec![],
             },
-            affected_globs: None,
+            ..TaskSpec::for_test(id, &format!("Task {id}"))
         }
     }
 
@@ -263,14 +312,8 @@ mod tests {
             make_map("aaa", vec![make_module("src/service.rs", vec!["MyService"], vec![])]);
         let new_map = make_map("bbb", vec![]);
         let spec = TaskSpec {
-            id: "T-NONE".to_string(),
-            title: "No context".to_string(),
-            requirement: None,
-            context: None,
             acceptance_criteria: vec!["done".to_string()],
-            signal_type: SignalType::Clear,
-            verification: VerificationStrategy::DirectAssertion { checks: vec![] },
-            affected_globs: None,
+            ..TaskSpec::for_test("T-NONE", "No context")
         };
 
         let report = detect_drift(&[spec], &old_map, &new_map);
@@ -329,4 +372,45 @@ mod tests {
         assert!(text.contains("Re-planning recommended"));
         assert!(text.contains("1 spec affected"));
     }
+
+    #[test]
+    fn blame_annotates_changed_module_from_cassette() {
+        use crate::adapters::replaying::git::ReplayingGitRepo;
+        use crate::cassette::format::{Cassette, Interaction};
+        use crate::cassette::replayer::CassetteReplayer;
+        use serde_json::json;
+        use std::sync::{Arc, Mutex};
+
+        let old_map =
+            make_map("aaa", vec![make_module("src/service.rs", vec!["MyService"], vec![])]);
+        let new_map = make_map(
+            "bbb",
+            vec![make_module("src/service.rs", vec!["MyService", "NewHelper"], vec![])],
+        );
+        let specs = vec![make_spec("T-1", vec!["MyService"])];
+        let report = detect_drift(&specs, &old_map, &new_map);
+
+        let cassette = Cassette {
+            name: "blame".into(),
+            recorded_at: Utc::now(),
+            commit: "bbb".into(),
+            interactions: vec![Interaction {
+                seq: 0,
+                port: "git".into(),
+                method: "last_modified".into(),
+                input: json!({"path": "src/service.rs"}),
+                output: json!({"Ok": ["2025-04-02T09:30:00Z", "Ada Lovelace"]}),
+            }],
+        };
+        let git = ReplayingGitRepo::new(Arc::new(Mutex::new(CassetteReplayer::new(&cassette))));
+
+        let blame = blame_drift(&report, &git);
+        let text = format_drift_report_with_blame(&report, &blame);
+
+        assert!(
+            text.contains("[CHANGED] src/service.rs (last modified 2025-04-02 by Ada Lovelace)"),
+            "{text}"
+        );
+        assert!(!format_drift_report(&report).contains("last modified"));
+    }
 }
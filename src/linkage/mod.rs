@@ -6,6 +6,11 @@
 
 pub mod drift;
 pub mod resolve;
+pub mod semantic;
 
-pub use drift::{detect_drift, format_drift_report, DriftEntry, DriftReport};
+pub use drift::{
+    blame_drift, detect_drift, format_drift_report, format_drift_report_with_blame, DriftEntry,
+    DriftReport, ModuleBlame,
+};
 pub use resolve::{derive_globs, resolve, LinkageResult, ResolvedLink};
+pub use semantic::{resolve_semantic, SEMANTIC_MATCH_THRESHOLD};
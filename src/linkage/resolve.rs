@@ -130,7 +130,7 @@ pub fn resolve(spec: &TaskSpec, codebase_map: &CodebaseMap) -> LinkageResult {
 /// 1. Exact match in public items (case-insensitive)
 /// 2. Substring match in module path (case-insensitive)
 /// 3. Substring match in public items (case-insensitive)
-fn find_matching_module(module_ref: &str, modules: &[ModuleSummary]) -> Option<String> {
+pub(super) fn find_matching_module(module_ref: &str, modules: &[ModuleSummary]) -> Option<String> {
     let needle = module_ref.to_lowercase();
 
     // Priority 1: exact match in public items
@@ -159,9 +159,11 @@ fn find_matching_module(module_ref: &str, modules: &[ModuleSummary]) -> Option<S
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
+
     use super::*;
     use crate::map::CodebaseMap;
-    use crate::spec::{SignalType, TaskContext, VerificationCheck, VerificationStrategy};
+    use crate::spec::{TaskContext, VerificationCheck, VerificationStrategy};
     use chrono::Utc;
 
     fn sample_map() -> CodebaseMap {
@@ -173,16 +175,25 @@ mod tests {
                     path: "src/services/metrics.rs".to_string(),
                     public_items: vec!["MetricsService".to_string(), "Counter".to_string()],
                     dependencies: vec![],
+                    external_deps: vec![],
+                    locations: BTreeMap::new(),
+                    crate_name: None,
                 },
                 ModuleSummary {
                     path: "src/handlers/api.rs".to_string(),
                     public_items: vec!["ApiHandler".to_string(), "Router".to_string()],
                     dependencies: vec!["metrics".to_string()],
+                    external_deps: vec![],
+                    locations: BTreeMap::new(),
+                    crate_name: None,
                 },
                 ModuleSummary {
                     path: "src/db/connection.rs".to_string(),
                     public_items: vec!["ConnectionPool".to_string()],
                     dependencies: vec![],
+                    external_deps: vec![],
+                    locations: BTreeMap::new(),
+                    crate_name: None,
                 },
             ],
             directory_tree: vec![
@@ -191,21 +202,24 @@ mod tests {
                 "src/db/connection.rs".to_string(),
             ],
             test_infrastructure: vec![],
+            unreadable: vec![],
         }
     }
 
     fn sample_spec_with_modules(id: &str, modules: Vec<String>) -> TaskSpec {
         TaskSpec {
-            id: id.to_string(),
-            title: format!("Task {id}"),
-            requirement: None,
             context: Some(TaskContext { modules, patterns: None, dependencies: vec![] }),
             acceptance_criteria: vec!["done".to_string()],
-            signal_type: SignalType::Clear,
             verification: VerificationStrategy::DirectAssertion {
-                checks: vec![VerificationCheck::Custom { description: "manual check".to_string() }],
+                checks: vec![VerificationCheck::Custom {
+                    description: "manual check".to_string(),
+                    name: None,
+                }],
+                groups: vec![],
+                setup: vec![],
+                teardown: vec![],
             },
-            affected_globs: None,
+            ..TaskSpec::for_test(id, &format!("Task {id}"))
         }
     }
 
@@ -297,16 +311,7 @@ mod tests {
     #[test]
     fn derive_globs_empty_links() {
         let map = sample_map();
-        let spec = TaskSpec {
-            id: "T-G4".to_string(),
-            title: "No context".to_string(),
-            requirement: None,
-            context: None,
-            acceptance_criteria: vec![],
-            signal_type: SignalType::Clear,
-            verification: VerificationStrategy::DirectAssertion { checks: vec![] },
-            affected_globs: None,
-        };
+        let spec = TaskSpec::for_test("T-G4", "No context");
         let linkage = resolve(&spec, &map);
         let (globs, unresolved) = derive_globs(&linkage);
 
@@ -349,14 +354,8 @@ mod tests {
     fn spec_without_context_returns_empty_links() {
         let map = sample_map();
         let spec = TaskSpec {
-            id: "T-6".to_string(),
-            title: "No context".to_string(),
-            requirement: None,
-            context: None,
             acceptance_criteria: vec!["done".to_string()],
-            signal_type: SignalType::Clear,
-            verification: VerificationStrategy::DirectAssertion { checks: vec![] },
-            affected_globs: None,
+            ..TaskSpec::for_test("T-6", "No context")
         };
         let result = resolve(&spec, &map);
 
@@ -0,0 +1,194 @@
+//! Embedding-based linkage resolution.
+//!
+//! An opt-in alternative to [`resolve`](super::resolve) that matches module
+//! references by meaning rather than spelling, so a spec mentioning "auth"
+//! can resolve to a module named `login`.
+
+use crate::map::{CodebaseMap, ModuleSummary};
+use crate::ports::LlmClient;
+use crate::spec::TaskSpec;
+
+use super::resolve::{find_matching_module, resolve, LinkageResult, ResolvedLink};
+
+/// Minimum cosine similarity for a semantic match.
+pub const SEMANTIC_MATCH_THRESHOLD: f32 = 0.8;
+
+/// Resolves a spec's module references using embeddings from `llm`.
+///
+/// Each reference and each module summary is embedded in a single request.
+/// A reference resolves to the module with the highest cosine similarity at
+/// or above `threshold`; references with no such module fall back to the
+/// string strategies of [`resolve`]. If embeddings are unavailable, the
+/// whole spec is resolved with [`resolve`].
+pub async fn resolve_semantic(
+    llm: &dyn LlmClient,
+    spec: &TaskSpec,
+    codebase_map: &CodebaseMap,
+    threshold: f32,
+) -> LinkageResult {
+    let refs = spec.context.as_ref().map(|ctx| ctx.modules.as_slice()).unwrap_or_default();
+    if refs.is_empty() || codebase_map.modules.is_empty() {
+        return resolve(spec, codebase_map);
+    }
+
+    let mut texts = refs.to_vec();
+    texts.extend(codebase_map.modules.iter().map(module_text));
+    let vectors = match llm.embed(&texts).await {
+        Ok(vectors) if vectors.len() == texts.len() => vectors,
+        _ => return resolve(spec, codebase_map),
+    };
+    let (ref_vectors, module_vectors) = vectors.split_at(refs.len());
+
+    let links = refs
+        .iter()
+        .zip(ref_vectors)
+        .map(|(module_ref, vector)| {
+            let resolved_path = best_match(vector, module_vectors, threshold)
+                .map(|i| codebase_map.modules[i].path.clone())
+                .or_else(|| find_matching_module(module_ref, &codebase_map.modules));
+            ResolvedLink { module_ref: module_ref.clone(), resolved_path }
+        })
+        .collect();
+
+    LinkageResult { spec_id: spec.id.clone(), links }
+}
+
+/// Text embedded for a module: its path followed by its public items.
+fn module_text(module: &ModuleSummary) -> String {
+    format!("{}: {}", module.path, module.public_items.join(", "))
+}
+
+/// Index of the candidate most similar to `target`, if any reaches `threshold`.
+fn best_match(target: &[f32], candidates: &[Vec<f32>], threshold: f32) -> Option<usize> {
+    candidates
+        .iter()
+        .map(|candidate| cosine_similarity(target, candidate))
+        .enumerate()
+        .filter(|(_, score)| *score >= threshold)
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(i, _)| i)
+}
+
+/// Cosine similarity of two vectors; `0.0` if either has zero length.
+fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a * norm_b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+    use crate::cassette::format::{Cassette, Interaction};
+    use crate::context::ServiceContext;
+    use crate::spec::TaskContext;
+    use chrono::Utc;
+    use serde_json::json;
+
+    fn module(path: &str, items: &[&str]) -> ModuleSummary {
+        ModuleSummary {
+            path: path.to_string(),
+            public_items: items.iter().map(|i| (*i).to_string()).collect(),
+            dependencies: vec![],
+            external_deps: vec![],
+            locations: BTreeMap::new(),
+            crate_name: None,
+        }
+    }
+
+    fn map() -> CodebaseMap {
+        CodebaseMap {
+            commit_hash: "abc".to_string(),
+            generated_at: Utc::now(),
+            modules: vec![
+                module("src/login", &["fn sign_in", "struct Session"]),
+                module("src/billing", &["fn charge"]),
+            ],
+            directory_tree: vec![],
+            test_infrastructure: vec![],
+            unreadable: vec![],
+        }
+    }
+
+    fn spec(modules: &[&str]) -> TaskSpec {
+        TaskSpec {
+            context: Some(TaskContext {
+                modules: modules.iter().map(|m| (*m).to_string()).collect(),
+                patterns: None,
+                dependencies: vec![],
+            }),
+            ..TaskSpec::for_test("T-SEM", "Semantic")
+        }
+    }
+
+    fn context_with_embed_output(name: &str, output: serde_json::Value) -> ServiceContext {
+        let cassette = Cassette {
+            name: name.into(),
+            recorded_at: Utc::now(),
+            commit: "abc".into(),
+            interactions: vec![Interaction {
+                seq: 0,
+                port: "llm".into(),
+                method: "embed".into(),
+                input: json!({}),
+                output,
+            }],
+        };
+        let dir = std::env::temp_dir().join(format!("speck_{name}"));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("embed.cassette.yaml");
+        std::fs::write(&path, serde_yaml::to_string(&cassette).unwrap()).unwrap();
+        let ctx = ServiceContext::replaying(&path).unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+        ctx
+    }
+
+    fn block_on<F: std::future::Future>(future: F) -> F::Output {
+        tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap().block_on(future)
+    }
+
+    #[test]
+    fn recorded_embeddings_match_auth_to_login() {
+        // Texts embedded: "auth", then "src/login: ...", then "src/billing: ...".
+        let ctx = context_with_embed_output(
+            "linkage_semantic_match",
+            json!({"Ok": [[0.9, 0.1], [1.0, 0.0], [0.0, 1.0]]}),
+        );
+        let result = block_on(resolve_semantic(
+            ctx.llm.as_ref(),
+            &spec(&["auth"]),
+            &map(),
+            SEMANTIC_MATCH_THRESHOLD,
+        ));
+        assert_eq!(result.links[0].resolved_path.as_deref(), Some("src/login"));
+    }
+
+    #[test]
+    fn unavailable_embeddings_fall_back_to_string_matching() {
+        let ctx = context_with_embed_output(
+            "linkage_semantic_fallback",
+            json!({"Err": "embeddings are not supported"}),
+        );
+        let result = block_on(resolve_semantic(
+            ctx.llm.as_ref(),
+            &spec(&["billing", "auth"]),
+            &map(),
+            SEMANTIC_MATCH_THRESHOLD,
+        ));
+        assert_eq!(result.links[0].resolved_path.as_deref(), Some("src/billing"));
+        assert_eq!(result.unresolved(), ["auth"]);
+    }
+
+    #[test]
+    fn cosine_similarity_of_orthogonal_vectors_is_zero() {
+        assert!(cosine_similarity(&[1.0, 0.0], &[0.0, 2.0]).abs() < f32::EPSILON);
+        assert!((cosine_similarity(&[1.0, 1.0], &[2.0, 2.0]) - 1.0).abs() < 1e-6);
+    }
+}
@@ -5,12 +5,12 @@ use std::process::ExitCode;
 fn main() -> ExitCode {
     // Load .env file if present (missing file is fine).
     dotenvy::dotenv().ok();
-    // Recording is handled in commands::dispatch via SPECK_REC=true.
+    // Recording is handled in commands::dispatch via SPECK_REC=true or --output-dir.
     match speck::run(std::env::args()) {
         Ok(()) => ExitCode::SUCCESS,
         Err(err) => {
             eprintln!("{err}");
-            ExitCode::FAILURE
+            ExitCode::from(err.exit_code())
         }
     }
 }
@@ -0,0 +1,110 @@
+//! Structural metrics derived from a [`CodebaseMap`].
+
+use std::collections::BTreeSet;
+
+use crate::map::{CodebaseMap, ModuleSummary};
+
+/// Computes fan-in and fan-out for every module in `map`.
+///
+/// Returns `(module_path, in_degree, out_degree)` for each module, where the
+/// in-degree counts how many other modules depend on it and the out-degree
+/// counts how many other modules it depends on. A dependency resolves to a
+/// module whose path ends in that name (e.g. `context` -> `src/context`);
+/// dependencies matching no module are ignored. Sorted by in-degree
+/// descending, then by path.
+#[must_use]
+pub fn fan_metrics(map: &CodebaseMap) -> Vec<(String, usize, usize)> {
+    let targets: Vec<BTreeSet<usize>> =
+        map.modules.iter().enumerate().map(|(i, m)| resolve_targets(map, i, m)).collect();
+
+    let mut metrics: Vec<(String, usize, usize)> = map
+        .modules
+        .iter()
+        .enumerate()
+        .map(|(i, m)| {
+            let in_degree = targets.iter().filter(|t| t.contains(&i)).count();
+            (m.path.clone(), in_degree, targets[i].len())
+        })
+        .collect();
+    metrics.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    metrics
+}
+
+/// Formats fan metrics as an aligned table.
+#[must_use]
+pub fn format_fan_metrics(metrics: &[(String, usize, usize)]) -> String {
+    if metrics.is_empty() {
+        return "No modules found.".to_string();
+    }
+    let width = metrics.iter().map(|(path, _, _)| path.len()).max().unwrap_or(0).max(6);
+    let mut lines = vec![format!("{:<width$}  {:>6}  {:>7}", "Module", "Fan-in", "Fan-out")];
+    for (path, fan_in, fan_out) in metrics {
+        lines.push(format!("{path:<width$}  {fan_in:>6}  {fan_out:>7}"));
+    }
+    lines.join("\n")
+}
+
+/// Indices of the other modules that `module` depends on.
+fn resolve_targets(map: &CodebaseMap, index: usize, module: &ModuleSummary) -> BTreeSet<usize> {
+    module
+        .dependencies
+        .iter()
+        .filter_map(|dep| {
+            map.modules.iter().position(|m| m.path.rsplit('/').next() == Some(dep.as_str()))
+        })
+        .filter(|&target| target != index)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+    use chrono::Utc;
+
+    fn module(path: &str, deps: &[&str]) -> ModuleSummary {
+        ModuleSummary {
+            path: path.to_string(),
+            public_items: vec![],
+            dependencies: deps.iter().map(|d| (*d).to_string()).collect(),
+            external_deps: vec![],
+            locations: BTreeMap::new(),
+            crate_name: None,
+        }
+    }
+
+    #[test]
+    fn fan_metrics_counts_in_and_out_degree() {
+        let map = CodebaseMap {
+            commit_hash: "abc".to_string(),
+            generated_at: Utc::now(),
+            modules: vec![
+                module("src", &["commands", "context"]),
+                module("src/commands", &["context", "store", "missing"]),
+                module("src/context", &["ports"]),
+                module("src/ports", &[]),
+                module("src/store", &["context", "ports"]),
+            ],
+            directory_tree: vec![],
+            test_infrastructure: vec![],
+            unreadable: vec![],
+        };
+
+        let metrics = fan_metrics(&map);
+        assert_eq!(
+            metrics,
+            vec![
+                ("src/context".to_string(), 3, 1),
+                ("src/ports".to_string(), 2, 0),
+                ("src/commands".to_string(), 1, 2),
+                ("src/store".to_string(), 1, 2),
+                ("src".to_string(), 0, 2),
+            ]
+        );
+
+        let table = format_fan_metrics(&metrics);
+        assert!(table.starts_with("Module"));
+        assert!(table.contains("src/context        3        1"), "{table}");
+    }
+}
@@ -1,7 +1,13 @@
 //! Diffing logic for codebase maps.
 
+use std::collections::BTreeSet;
+
 use crate::map::{CodebaseMap, ModuleSummary};
 
+/// Share of public items (intersection over union, in percent) a removed and
+/// an added module must have in common to be reported as a move.
+const MOVE_MIN_SHARED_PERCENT: usize = 80;
+
 /// Differences between two codebase maps.
 #[derive(Debug, PartialEq)]
 pub struct MapDiff {
@@ -9,6 +15,9 @@ pub struct MapDiff {
     pub added_modules: Vec<String>,
     /// Modules present in old but not new.
     pub removed_modules: Vec<String>,
+    /// Modules whose path changed but whose public items did not (or barely
+    /// did), as `(old_path, new_path)`. These are not listed as added or removed.
+    pub moved_modules: Vec<(String, String)>,
     /// Modules whose public items or dependencies changed.
     pub changed_modules: Vec<ModuleChange>,
 }
@@ -34,13 +43,13 @@ pub fn diff_maps(old: &CodebaseMap, new: &CodebaseMap) -> MapDiff {
     let old_paths: Vec<&str> = old.modules.iter().map(|m| m.path.as_str()).collect();
     let new_paths: Vec<&str> = new.modules.iter().map(|m| m.path.as_str()).collect();
 
-    let added_modules: Vec<String> = new_paths
+    let mut added_modules: Vec<String> = new_paths
         .iter()
         .filter(|p| !old_paths.contains(p))
         .map(std::string::ToString::to_string)
         .collect();
 
-    let removed_modules: Vec<String> = old_paths
+    let mut removed_modules: Vec<String> = old_paths
         .iter()
         .filter(|p| !new_paths.contains(p))
         .map(std::string::ToString::to_string)
@@ -55,7 +64,52 @@ pub fn diff_maps(old: &CodebaseMap, new: &CodebaseMap) -> MapDiff {
         }
     }
 
-    MapDiff { added_modules, removed_modules, changed_modules }
+    let moved_modules = detect_moves(old, new, &mut removed_modules, &mut added_modules);
+
+    MapDiff { added_modules, removed_modules, moved_modules, changed_modules }
+}
+
+/// Pair each removed module with the most similar added module, if any is
+/// similar enough, and take both out of `removed` and `added`.
+fn detect_moves(
+    old: &CodebaseMap,
+    new: &CodebaseMap,
+    removed: &mut Vec<String>,
+    added: &mut Vec<String>,
+) -> Vec<(String, String)> {
+    let items = |map: &CodebaseMap, path: &str| -> BTreeSet<String> {
+        map.modules
+            .iter()
+            .find(|m| m.path == path)
+            .map(|m| m.public_items.iter().cloned().collect())
+            .unwrap_or_default()
+    };
+
+    let mut moves = Vec::new();
+    for old_path in removed.iter() {
+        let old_items = items(old, old_path);
+        if old_items.is_empty() {
+            continue;
+        }
+        let best = added
+            .iter()
+            .filter(|new_path| !moves.iter().any(|(_, taken)| taken == *new_path))
+            .filter_map(|new_path| {
+                let new_items = items(new, new_path);
+                let shared = old_items.intersection(&new_items).count();
+                let union = old_items.union(&new_items).count();
+                let percent = shared * 100 / union;
+                (percent >= MOVE_MIN_SHARED_PERCENT).then_some((percent, new_path))
+            })
+            .max_by_key(|(percent, _)| *percent);
+        if let Some((_, new_path)) = best {
+            moves.push((old_path.clone(), new_path.clone()));
+        }
+    }
+
+    removed.retain(|p| !moves.iter().any(|(from, _)| from == p));
+    added.retain(|p| !moves.iter().any(|(_, to)| to == p));
+    moves
 }
 
 /// Compare two module summaries, returning `Some(change)` if they differ.
@@ -91,6 +145,7 @@ fn diff_module(old: &ModuleSummary, new: &ModuleSummary) -> Option<ModuleChange>
 pub fn format_diff(diff: &MapDiff) -> String {
     if diff.added_modules.is_empty()
         && diff.removed_modules.is_empty()
+        && diff.moved_modules.is_empty()
         && diff.changed_modules.is_empty()
     {
         return "No changes since last map.".to_string();
@@ -110,6 +165,12 @@ pub fn format_diff(diff: &MapDiff) -> String {
             lines.push(format!("  - {m}"));
         }
     }
+    if !diff.moved_modules.is_empty() {
+        lines.push("Moved modules:".to_string());
+        for (from, to) in &diff.moved_modules {
+            lines.push(format!("  {from} -> {to}"));
+        }
+    }
     for change in &diff.changed_modules {
         lines.push(format!("Changed: {}", change.path));
         for item in &change.added_items {
@@ -131,6 +192,8 @@ pub fn format_diff(diff: &MapDiff) -> String {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
+
     use super::*;
     use chrono::Utc;
 
@@ -141,6 +204,7 @@ mod tests {
             modules,
             directory_tree: vec![],
             test_infrastructure: vec![],
+            unreadable: vec![],
         }
     }
 
@@ -149,6 +213,9 @@ mod tests {
             path: path.to_string(),
             public_items: items.into_iter().map(String::from).collect(),
             dependencies: deps.into_iter().map(String::from).collect(),
+            external_deps: vec![],
+            locations: BTreeMap::new(),
+            crate_name: None,
         }
     }
 
@@ -177,6 +244,27 @@ mod tests {
         assert_eq!(d.removed_modules, vec!["src/old"]);
     }
 
+    #[test]
+    fn diff_reports_relocated_module_as_move() {
+        let old = make_map(vec![
+            make_module("src", vec!["fn run"], vec![]),
+            make_module("src/auth", vec!["fn login", "struct Session"], vec![]),
+        ]);
+        let new = make_map(vec![
+            make_module("src", vec!["fn run"], vec![]),
+            make_module("src/identity/auth", vec!["fn login", "struct Session"], vec![]),
+            make_module("src/billing", vec!["fn charge"], vec![]),
+        ]);
+        let d = diff_maps(&old, &new);
+        assert_eq!(
+            d.moved_modules,
+            vec![("src/auth".to_string(), "src/identity/auth".to_string())]
+        );
+        assert!(d.removed_modules.is_empty());
+        assert_eq!(d.added_modules, vec!["src/billing"]);
+        assert!(format_diff(&d).contains("Moved modules:\n  src/auth -> src/identity/auth"));
+    }
+
     #[test]
     fn diff_detects_changed_items() {
         let old = make_map(vec![make_module("src", vec!["fn run", "struct App"], vec!["config"])]);
@@ -205,7 +293,12 @@ mod tests {
 
     #[test]
     fn format_diff_no_changes() {
-        let d = MapDiff { added_modules: vec![], removed_modules: vec![], changed_modules: vec![] };
+        let d = MapDiff {
+            added_modules: vec![],
+            removed_modules: vec![],
+            moved_modules: vec![],
+            changed_modules: vec![],
+        };
         assert_eq!(format_diff(&d), "No changes since last map.");
     }
 
@@ -214,6 +307,7 @@ mod tests {
         let d = MapDiff {
             added_modules: vec!["src/new".to_string()],
             removed_modules: vec!["src/old".to_string()],
+            moved_modules: vec![],
             changed_modules: vec![ModuleChange {
                 path: "src".to_string(),
                 added_items: vec!["fn foo".to_string()],
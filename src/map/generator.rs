@@ -1,59 +1,231 @@
 //! Map generation logic: walks project files via `ServiceContext` ports.
 
-use std::path::Path;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use futures_util::stream::{self, StreamExt as _};
 
 use crate::context::ServiceContext;
-use crate::map::{CodebaseMap, ModuleSummary};
+use crate::map::{CodebaseMap, ModuleSummary, SymbolLocation};
 
 /// Output path for the generated codebase map (relative to project root).
 const MAP_OUTPUT_PATH: &str = ".spec-cache/codebase_map.yaml";
 
+/// Most files [`generate_async`] reads at once, to stay within open-file limits.
+const MAX_CONCURRENT_READS: usize = 64;
+
 /// Generates a [`CodebaseMap`] for the project rooted at `root`.
 ///
 /// Walks the file tree via `ctx.git.list_files`, identifies module boundaries
 /// (directories containing `mod.rs` or `lib.rs`), extracts public items from
-/// Rust source files, and writes the result as YAML.
+/// Rust source files, and writes the result as YAML to `output` (relative to
+/// `root`), or to `.spec-cache/codebase_map.yaml` when `output` is `None`.
+/// Each module is tagged with the workspace crate whose `Cargo.toml` encloses
+/// it. Files that cannot be read are listed in [`CodebaseMap::unreadable`]
+/// rather than failing the map.
 ///
 /// # Errors
 ///
 /// Returns an error if git queries, file reads, or YAML serialization fail.
-pub fn generate(ctx: &ServiceContext, root: &Path) -> Result<CodebaseMap, String> {
-    let commit_hash =
-        ctx.git.current_commit().map_err(|e| format!("failed to get current commit: {e}"))?;
+pub fn generate(
+    ctx: &ServiceContext,
+    root: &Path,
+    output: Option<&Path>,
+) -> Result<CodebaseMap, String> {
+    let (commit_hash, generated_at, files) = survey_tree(ctx, root)?;
+    let module_roots = find_module_roots(&files);
+
+    let sources: Sources = source_files(&files, &module_roots)
+        .into_iter()
+        .map(|file| {
+            let content = ctx.fs.read_to_string(&root.join(file)).map_err(|e| e.to_string());
+            (file.clone(), content)
+        })
+        .collect();
+
+    let map = assemble(commit_hash, generated_at, files, &module_roots, &sources);
+    let (output, yaml) = serialize(&map, root, output)?;
+    if let Some(parent) = output.parent() {
+        ctx.fs
+            .create_dir_all(parent)
+            .map_err(|e| format!("failed to create {}: {e}", parent.display()))?;
+    }
+    ctx.fs
+        .write(&output, &yaml)
+        .map_err(|e| format!("failed to write map to {}: {e}", output.display()))?;
+
+    Ok(map)
+}
+
+/// Generates a [`CodebaseMap`] like [`generate`], reading files concurrently.
+///
+/// Every `Cargo.toml` and module source file is read through
+/// `ctx.async_fs`, up to [`MAX_CONCURRENT_READS`] at a time, and the map is
+/// written through it too. The result is identical to the one [`generate`]
+/// produces for the same tree.
+///
+/// # Errors
+///
+/// Returns an error if git queries, file reads, or YAML serialization fail.
+pub async fn generate_async(
+    ctx: &ServiceContext,
+    root: &Path,
+    output: Option<&Path>,
+) -> Result<CodebaseMap, String> {
+    generate_async_with_limit(ctx, root, output, MAX_CONCURRENT_READS).await
+}
+
+/// [`generate_async`] with at most `max_reads` reads in flight.
+async fn generate_async_with_limit(
+    ctx: &ServiceContext,
+    root: &Path,
+    output: Option<&Path>,
+    max_reads: usize,
+) -> Result<CodebaseMap, String> {
+    let (commit_hash, generated_at, files) = survey_tree(ctx, root)?;
+    let module_roots = find_module_roots(&files);
+
+    let reads = source_files(&files, &module_roots).into_iter().map(|file| async move {
+        let path = root.join(file);
+        let content = ctx.async_fs.read_to_string(&path).await.map_err(|e| e.to_string());
+        (file.clone(), content)
+    });
+    let sources: Sources = stream::iter(reads).buffer_unordered(max_reads).collect().await;
+
+    let map = assemble(commit_hash, generated_at, files, &module_roots, &sources);
+    let (output, yaml) = serialize(&map, root, output)?;
+    if let Some(parent) = output.parent() {
+        ctx.async_fs
+            .create_dir_all(parent)
+            .await
+            .map_err(|e| format!("failed to create {}: {e}", parent.display()))?;
+    }
+    ctx.async_fs
+        .write(&output, &yaml)
+        .await
+        .map_err(|e| format!("failed to write map to {}: {e}", output.display()))?;
+
+    Ok(map)
+}
 
+/// Generates a [`CodebaseMap`] of the tree at `commit` without touching the working tree.
+///
+/// Files are listed via `ctx.git.list_files_at` and read via
+/// `ctx.git.read_file_at`; otherwise the map is built as in [`generate`].
+/// Nothing is written.
+///
+/// # Errors
+///
+/// Returns an error if the files at `commit` cannot be listed.
+pub fn generate_at_commit(ctx: &ServiceContext, commit: &str) -> Result<CodebaseMap, String> {
     let generated_at = ctx.clock.now();
+    let files = ctx
+        .git
+        .list_files_at(commit)
+        .map_err(|e| format!("failed to list files at {commit}: {e}"))?;
+    let module_roots = find_module_roots(&files);
+
+    let sources: Sources = source_files(&files, &module_roots)
+        .into_iter()
+        .map(|file| {
+            let content = ctx.git.read_file_at(commit, Path::new(file)).map_err(|e| e.to_string());
+            (file.clone(), content)
+        })
+        .collect();
 
+    Ok(assemble(commit.to_string(), generated_at, files, &module_roots, &sources))
+}
+
+/// File contents keyed by path relative to the project root, or the read error.
+type Sources = HashMap<String, Result<String, String>>;
+
+/// Reads the current commit, the generation time, and the tracked files.
+fn survey_tree(
+    ctx: &ServiceContext,
+    root: &Path,
+) -> Result<(String, DateTime<Utc>, Vec<String>), String> {
+    let commit_hash =
+        ctx.git.current_commit().map_err(|e| format!("failed to get current commit: {e}"))?;
+    let generated_at = ctx.clock.now();
     let files = ctx.git.list_files(root).map_err(|e| format!("failed to list files: {e}"))?;
+    Ok((commit_hash, generated_at, files))
+}
 
-    let directory_tree: Vec<String> = files.clone();
+/// Lists the files map generation reads: every `Cargo.toml`, then the Rust
+/// sources of each module in `module_roots` order.
+fn source_files<'a>(files: &'a [String], module_roots: &[String]) -> Vec<&'a String> {
+    let mut sources: Vec<&String> = files.iter().filter(|f| manifest_dir(f).is_some()).collect();
+    for module_path in module_roots {
+        sources.extend(module_files(module_path, files));
+    }
+    sources
+}
 
+/// Builds the map from the tree listing and the contents read from it.
+fn assemble(
+    commit_hash: String,
+    generated_at: DateTime<Utc>,
+    files: Vec<String>,
+    module_roots: &[String],
+    sources: &Sources,
+) -> CodebaseMap {
     // Identify test infrastructure files.
     let test_infrastructure: Vec<String> =
         files.iter().filter(|f| is_test_file(f)).cloned().collect();
 
-    // Identify module boundaries: directories containing mod.rs or lib.rs.
-    let module_roots = find_module_roots(&files);
+    // Identify workspace members: directories containing Cargo.toml.
+    let mut unreadable = Vec::new();
+    let crates = find_crates(&files, sources, &mut unreadable);
 
     // Build module summaries.
     let mut modules = Vec::new();
-    for module_path in &module_roots {
-        modules.push(build_module_summary(ctx, root, module_path, &files));
+    for module_path in module_roots {
+        let mut summary = build_module_summary(module_path, &files, sources, &mut unreadable);
+        summary.crate_name = owning_crate(module_path, &crates);
+        modules.push(summary);
     }
 
-    let map =
-        CodebaseMap { commit_hash, generated_at, modules, directory_tree, test_infrastructure };
-
-    // Serialize and write to .spec-cache/codebase_map.yaml.
-    let yaml = serde_yaml::to_string(&map).map_err(|e| format!("failed to serialize map: {e}"))?;
-    let output = root.join(MAP_OUTPUT_PATH);
-    ctx.fs
-        .write(&output, &yaml)
-        .map_err(|e| format!("failed to write map to {}: {e}", output.display()))?;
+    CodebaseMap {
+        commit_hash,
+        generated_at,
+        modules,
+        directory_tree: files,
+        test_infrastructure,
+        unreadable,
+    }
+}
 
-    Ok(map)
+/// Serializes `map` and resolves the path it is written to.
+fn serialize(
+    map: &CodebaseMap,
+    root: &Path,
+    output: Option<&Path>,
+) -> Result<(PathBuf, String), String> {
+    let yaml = serde_yaml::to_string(map).map_err(|e| format!("failed to serialize map: {e}"))?;
+    Ok((root.join(output.unwrap_or(Path::new(MAP_OUTPUT_PATH))), yaml))
 }
 
-/// Returns `true` if the file path looks like a test file.
+/// Test runner configuration files, matched by exact file name.
+const TEST_CONFIG_FILES: &[&str] = &[
+    "conftest.py",
+    "pytest.ini",
+    "jest.config.js",
+    "jest.config.ts",
+    "jest.config.mjs",
+    "jest.config.cjs",
+    "vitest.config.js",
+    "vitest.config.ts",
+];
+
+/// Extensions of JavaScript and TypeScript sources.
+const JS_EXTENSIONS: &[&str] = &["js", "jsx", "mjs", "cjs", "ts", "tsx"];
+
+/// Returns `true` if the file path looks like a test file or test config.
+///
+/// Recognizes Rust (`_test.rs`, `tests/`), Python (`test_*.py`,
+/// `conftest.py`), and JavaScript/TypeScript (`*.test.js`, `*.spec.ts`,
+/// `__tests__/`) conventions, plus common test runner config files.
 fn is_test_file(path: &str) -> bool {
     let name = path.rsplit('/').next().unwrap_or(path);
     name.starts_with("test_")
@@ -62,6 +234,19 @@ fn is_test_file(path: &str) -> bool {
         || path.contains("/tests/")
         || path.contains("/test/")
         || path.starts_with("tests/")
+        || path.contains("/__tests__/")
+        || path.starts_with("__tests__/")
+        || TEST_CONFIG_FILES.contains(&name)
+        || is_js_test_name(name)
+}
+
+/// Matches `<name>.test.<ext>` and `<name>.spec.<ext>` for JS/TS extensions.
+fn is_js_test_name(name: &str) -> bool {
+    let mut parts = name.rsplitn(3, '.');
+    let (Some(ext), Some(kind), Some(_)) = (parts.next(), parts.next(), parts.next()) else {
+        return false;
+    };
+    matches!(kind, "test" | "spec") && JS_EXTENSIONS.contains(&ext)
 }
 
 /// Finds directories that contain `mod.rs` or `lib.rs`, indicating module boundaries.
@@ -81,72 +266,185 @@ fn find_module_roots(files: &[String]) -> Vec<String> {
     roots
 }
 
-/// Builds a [`ModuleSummary`] by reading Rust source files in the module directory.
-fn build_module_summary(
-    ctx: &ServiceContext,
-    root: &Path,
-    module_path: &str,
-    all_files: &[String],
-) -> ModuleSummary {
+/// Finds packages in the tree as `(directory, crate name)` pairs.
+///
+/// Every `Cargo.toml` with a `[package]` name is a crate; virtual workspace
+/// manifests are skipped. The root package's directory is `""`. Sorted
+/// deepest-first so the first prefix match is the innermost crate.
+fn find_crates(
+    files: &[String],
+    sources: &Sources,
+    unreadable: &mut Vec<String>,
+) -> Vec<(String, String)> {
+    let mut crates = Vec::new();
+    for file in files {
+        let Some(dir) = manifest_dir(file) else {
+            continue;
+        };
+        match &sources[file] {
+            Ok(content) => {
+                if let Some(name) = parse_package_name(content) {
+                    crates.push((dir.to_string(), name));
+                }
+            }
+            Err(e) => unreadable.push(format!("{file}: {e}")),
+        }
+    }
+    crates.sort_by(|a, b| b.0.len().cmp(&a.0.len()).then_with(|| a.0.cmp(&b.0)));
+    crates
+}
+
+/// The directory of a `Cargo.toml` path (`""` at the root), or `None` for other files.
+fn manifest_dir(file: &str) -> Option<&str> {
+    match file.rsplit_once('/') {
+        Some((dir, "Cargo.toml")) => Some(dir),
+        None if file == "Cargo.toml" => Some(""),
+        _ => None,
+    }
+}
+
+/// Reads `name` from the `[package]` table of a `Cargo.toml`.
+fn parse_package_name(manifest: &str) -> Option<String> {
+    let mut in_package = false;
+    for line in manifest.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_package = line == "[package]";
+        } else if let Some(value) = line.strip_prefix("name").map(str::trim_start) {
+            if in_package {
+                if let Some(value) = value.strip_prefix('=') {
+                    return Some(value.trim().trim_matches('"').to_string());
+                }
+            }
+        }
+    }
+    None
+}
+
+/// The innermost crate whose directory contains `module_path`.
+fn owning_crate(module_path: &str, crates: &[(String, String)]) -> Option<String> {
+    crates
+        .iter()
+        .find(|(dir, _)| {
+            dir.is_empty()
+                || module_path == dir
+                || module_path.strip_prefix(dir.as_str()).is_some_and(|rest| rest.starts_with('/'))
+        })
+        .map(|(_, name)| name.clone())
+}
+
+/// The Rust source files directly inside `module_path`.
+fn module_files<'a>(module_path: &str, all_files: &'a [String]) -> Vec<&'a String> {
     let prefix = format!("{module_path}/");
-    let module_files: Vec<&String> = all_files
+    all_files
         .iter()
         .filter(|f| {
             f.starts_with(&prefix)
                 && Path::new(f).extension().is_some_and(|ext| ext.eq_ignore_ascii_case("rs"))
                 && !f[prefix.len()..].contains('/')
         })
-        .collect();
+        .collect()
+}
 
+/// Builds a [`ModuleSummary`] from the Rust source files in the module directory.
+///
+/// Files that failed to read are appended to `unreadable` with the error reason.
+fn build_module_summary(
+    module_path: &str,
+    all_files: &[String],
+    sources: &Sources,
+    unreadable: &mut Vec<String>,
+) -> ModuleSummary {
     let mut public_items = Vec::new();
     let mut dependencies = Vec::new();
-
-    for file in &module_files {
-        let full_path = root.join(file);
-        let Ok(content) = ctx.fs.read_to_string(&full_path) else {
-            continue;
+    let mut external_deps = Vec::new();
+    let mut locations = BTreeMap::new();
+
+    for file in module_files(module_path, all_files) {
+        let content = match &sources[file] {
+            Ok(content) => content,
+            Err(e) => {
+                unreadable.push(format!("{file}: {e}"));
+                continue;
+            }
         };
-        extract_public_items(&content, &mut public_items);
-        extract_dependencies(&content, &mut dependencies);
+        for (item, line) in extract_public_items(content) {
+            locations.entry(item.clone()).or_insert(SymbolLocation { file: file.clone(), line });
+            public_items.push(item);
+        }
+        extract_dependencies(content, &mut dependencies, &mut external_deps);
     }
 
     public_items.sort();
     public_items.dedup();
     dependencies.sort();
     dependencies.dedup();
-
-    ModuleSummary { path: module_path.to_string(), public_items, dependencies }
+    external_deps.sort();
+    external_deps.dedup();
+
+    ModuleSummary {
+        path: module_path.to_string(),
+        public_items,
+        dependencies,
+        external_deps,
+        locations,
+        crate_name: None,
+    }
 }
 
-/// Extracts `pub fn`, `pub struct`, and `pub trait` names from Rust source.
-fn extract_public_items(content: &str, items: &mut Vec<String>) {
-    for line in content.lines() {
+/// Extracts `pub fn`, `pub struct`, and `pub trait` names from Rust source,
+/// each paired with the 1-based line it is declared on.
+fn extract_public_items(content: &str) -> Vec<(String, usize)> {
+    let mut items = Vec::new();
+    for (index, line) in content.lines().enumerate() {
         let trimmed = line.trim();
+        let line_number = index + 1;
         if let Some(rest) = trimmed.strip_prefix("pub fn ") {
             if let Some(name) = rest.split('(').next() {
-                items.push(format!("fn {name}"));
+                items.push((format!("fn {name}"), line_number));
             }
         } else if let Some(rest) = trimmed.strip_prefix("pub struct ") {
             if let Some(name) = rest.split([' ', '{', '(', '<']).next() {
-                items.push(format!("struct {name}"));
+                items.push((format!("struct {name}"), line_number));
             }
         } else if let Some(rest) = trimmed.strip_prefix("pub trait ") {
             if let Some(name) = rest.split([' ', '{', '<', ':']).next() {
-                items.push(format!("trait {name}"));
+                items.push((format!("trait {name}"), line_number));
             }
         }
     }
+    items
 }
 
-/// Extracts `use crate::` dependency paths from Rust source.
-fn extract_dependencies(content: &str, deps: &mut Vec<String>) {
+/// Crates that ship with the toolchain and are not recorded as external deps.
+const BUILTIN_CRATES: &[&str] = &["std", "core", "alloc"];
+
+/// Extracts `use` dependencies from Rust source.
+///
+/// `use crate::<module>` paths are pushed to `deps`; `use <crate>::` paths
+/// naming another crate are pushed to `external` by crate name.
+fn extract_dependencies(content: &str, deps: &mut Vec<String>, external: &mut Vec<String>) {
     for line in content.lines() {
         let trimmed = line.trim();
-        if let Some(rest) = trimmed.strip_prefix("use crate::") {
-            if let Some(module) = rest.split("::").next() {
-                let module = module.trim_end_matches(';');
-                deps.push(module.to_string());
+        let Some(path) = trimmed.strip_prefix("use ") else {
+            continue;
+        };
+        let path = path.trim_start_matches("::");
+        let Some((root, rest)) = path.split_once("::") else {
+            continue;
+        };
+        match root {
+            "crate" => {
+                if let Some(module) = rest.split("::").next() {
+                    let module = module.trim_end_matches(';');
+                    deps.push(module.to_string());
+                }
+            }
+            "self" | "super" => {}
+            name if BUILTIN_CRATES.contains(&name) => {}
+            name if name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') => {
+                external.push(name.to_string());
             }
+            _ => {}
         }
     }
 }
@@ -154,9 +452,13 @@ fn extract_dependencies(content: &str, deps: &mut Vec<String>) {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::adapters::live::filesystem::LiveAsyncFileSystem;
     use crate::cassette::format::{Cassette, Interaction};
+    use crate::ports::{AsyncFileSystem, FsFuture};
     use chrono::Utc;
     use serde_json::json;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
 
     fn make_cassette() -> Cassette {
         // Simulate a small project:
@@ -226,10 +528,17 @@ mod tests {
                     input: json!({"path": "/project/src/map/utils.rs"}),
                     output: json!("fn helper() {}\n"),
                 },
-                // fs.write — .spec-cache/codebase_map.yaml
                 Interaction {
                     seq: 6,
                     port: "fs".into(),
+                    method: "create_dir_all".into(),
+                    input: json!({"path": "/project/.spec-cache"}),
+                    output: json!(null),
+                },
+                // fs.write — .spec-cache/codebase_map.yaml
+                Interaction {
+                    seq: 7,
+                    port: "fs".into(),
                     method: "write".into(),
                     input: json!({"path": "/project/.spec-cache/codebase_map.yaml"}),
                     output: json!(null),
@@ -248,7 +557,7 @@ mod tests {
         std::fs::write(&cassette_path, &yaml).unwrap();
 
         let ctx = ServiceContext::replaying(&cassette_path).unwrap();
-        let map = generate(&ctx, Path::new("/project")).unwrap();
+        let map = generate(&ctx, Path::new("/project"), None).unwrap();
 
         assert_eq!(map.commit_hash, "abc123def");
         assert_eq!(map.directory_tree.len(), 4);
@@ -265,6 +574,21 @@ mod tests {
         assert!(map_module.public_items.contains(&"fn generate".to_string()));
         assert!(map_module.public_items.contains(&"trait Generator".to_string()));
         assert!(map_module.dependencies.contains(&"context".to_string()));
+        assert!(map_module.external_deps.is_empty());
+        assert!(map.unreadable.is_empty());
+
+        assert_eq!(
+            src_module.locations["struct App"],
+            SymbolLocation { file: "src/lib.rs".to_string(), line: 1 }
+        );
+        assert_eq!(
+            src_module.locations["fn run"],
+            SymbolLocation { file: "src/lib.rs".to_string(), line: 5 }
+        );
+        assert_eq!(
+            map_module.locations["trait Generator"],
+            SymbolLocation { file: "src/map/mod.rs".to_string(), line: 4 }
+        );
 
         // Verify YAML serialization works
         let map_yaml = serde_yaml::to_string(&map).unwrap();
@@ -274,6 +598,214 @@ mod tests {
         let _ = std::fs::remove_dir_all(&dir);
     }
 
+    /// Counts reads in flight, yielding once per read so concurrent reads overlap.
+    struct ConcurrencyProbe {
+        inner: Box<dyn AsyncFileSystem>,
+        in_flight: Arc<AtomicUsize>,
+        max_in_flight: Arc<AtomicUsize>,
+    }
+
+    impl AsyncFileSystem for ConcurrencyProbe {
+        fn read_to_string<'a>(&'a self, path: &'a Path) -> FsFuture<'a, String> {
+            Box::pin(async move {
+                let now = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                self.max_in_flight.fetch_max(now, Ordering::SeqCst);
+                tokio::task::yield_now().await;
+                let result = self.inner.read_to_string(path).await;
+                self.in_flight.fetch_sub(1, Ordering::SeqCst);
+                result
+            })
+        }
+
+        fn write<'a>(&'a self, path: &'a Path, contents: &'a str) -> FsFuture<'a, ()> {
+            self.inner.write(path, contents)
+        }
+
+        fn create_dir_all<'a>(&'a self, path: &'a Path) -> FsFuture<'a, ()> {
+            self.inner.create_dir_all(path)
+        }
+
+        fn list_dir<'a>(&'a self, path: &'a Path) -> FsFuture<'a, Vec<String>> {
+            self.inner.list_dir(path)
+        }
+    }
+
+    #[tokio::test]
+    async fn generate_async_reads_files_concurrently() {
+        let cassette = make_cassette();
+        let yaml = serde_yaml::to_string(&cassette).unwrap();
+        let dir = std::env::temp_dir().join("speck_map_gen_async_test");
+        std::fs::create_dir_all(&dir).unwrap();
+        let cassette_path = dir.join("map_gen.cassette.yaml");
+        std::fs::write(&cassette_path, &yaml).unwrap();
+
+        let expected = generate(
+            &ServiceContext::replaying(&cassette_path).unwrap(),
+            Path::new("/project"),
+            None,
+        )
+        .unwrap();
+
+        let mut ctx = ServiceContext::replaying(&cassette_path).unwrap();
+        let max_in_flight = Arc::new(AtomicUsize::new(0));
+        let inner = std::mem::replace(&mut ctx.async_fs, Box::new(LiveAsyncFileSystem));
+        ctx.async_fs = Box::new(ConcurrencyProbe {
+            inner,
+            in_flight: Arc::new(AtomicUsize::new(0)),
+            max_in_flight: Arc::clone(&max_in_flight),
+        });
+
+        let map = generate_async(&ctx, Path::new("/project"), None).await.unwrap();
+
+        // All three source files were read at once, not one after another.
+        assert_eq!(max_in_flight.load(Ordering::SeqCst), 3);
+        assert_eq!(map, expected);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn generate_async_caps_reads_in_flight() {
+        let dir = std::env::temp_dir().join("speck_map_gen_async_limit_test");
+        std::fs::create_dir_all(&dir).unwrap();
+        let cassette_path = dir.join("map_gen.cassette.yaml");
+        std::fs::write(&cassette_path, serde_yaml::to_string(&make_cassette()).unwrap()).unwrap();
+        let mut ctx = ServiceContext::replaying(&cassette_path).unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+        let max_in_flight = Arc::new(AtomicUsize::new(0));
+        let inner = std::mem::replace(&mut ctx.async_fs, Box::new(LiveAsyncFileSystem));
+        ctx.async_fs = Box::new(ConcurrencyProbe {
+            inner,
+            in_flight: Arc::new(AtomicUsize::new(0)),
+            max_in_flight: Arc::clone(&max_in_flight),
+        });
+
+        let map = generate_async_with_limit(&ctx, Path::new("/project"), None, 2).await.unwrap();
+
+        assert_eq!(max_in_flight.load(Ordering::SeqCst), 2);
+        assert_eq!(map.modules.len(), 2);
+    }
+
+    fn read(seq: u64, path: &str, content: &str) -> Interaction {
+        Interaction {
+            seq,
+            port: "fs".into(),
+            method: "read_to_string".into(),
+            input: json!({ "path": format!("/ws/{path}") }),
+            output: json!(content),
+        }
+    }
+
+    #[test]
+    fn modules_are_attributed_to_their_workspace_crate() {
+        let files = [
+            "Cargo.toml",
+            "crates/cli/Cargo.toml",
+            "crates/cli/src/lib.rs",
+            "crates/core/Cargo.toml",
+            "crates/core/src/lib.rs",
+        ];
+        let cassette = Cassette {
+            name: "map-workspace".into(),
+            recorded_at: Utc::now(),
+            commit: "abc".into(),
+            interactions: vec![
+                Interaction {
+                    seq: 0,
+                    port: "clock".into(),
+                    method: "now".into(),
+                    input: json!({}),
+                    output: json!("2025-06-15T10:00:00Z"),
+                },
+                Interaction {
+                    seq: 1,
+                    port: "git".into(),
+                    method: "current_commit".into(),
+                    input: json!({}),
+                    output: json!("abc"),
+                },
+                Interaction {
+                    seq: 2,
+                    port: "git".into(),
+                    method: "list_files".into(),
+                    input: json!({"path": "/ws"}),
+                    output: json!(files),
+                },
+                read(3, "Cargo.toml", "[workspace]\nmembers = [\"crates/*\"]\n"),
+                read(4, "crates/cli/Cargo.toml", "[package]\nname = \"ws-cli\"\n"),
+                read(5, "crates/core/Cargo.toml", "[package]\nname = \"ws-core\"\n"),
+                read(6, "crates/cli/src/lib.rs", "pub fn main() {}\n"),
+                read(7, "crates/core/src/lib.rs", "pub fn engine() {}\n"),
+                Interaction {
+                    seq: 8,
+                    port: "fs".into(),
+                    method: "create_dir_all".into(),
+                    input: json!({"path": "/ws/.spec-cache"}),
+                    output: json!(null),
+                },
+                Interaction {
+                    seq: 9,
+                    port: "fs".into(),
+                    method: "write".into(),
+                    input: json!({"path": "/ws/.spec-cache/codebase_map.yaml"}),
+                    output: json!(null),
+                },
+            ],
+        };
+        let dir = std::env::temp_dir().join("speck_map_gen_workspace");
+        std::fs::create_dir_all(&dir).unwrap();
+        let cassette_path = dir.join("workspace.cassette.yaml");
+        std::fs::write(&cassette_path, serde_yaml::to_string(&cassette).unwrap()).unwrap();
+
+        let ctx = ServiceContext::replaying(&cassette_path).unwrap();
+        let map = generate(&ctx, Path::new("/ws"), None).unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let crate_of = |path: &str| {
+            map.modules.iter().find(|m| m.path == path).and_then(|m| m.crate_name.clone())
+        };
+        assert_eq!(crate_of("crates/cli/src").as_deref(), Some("ws-cli"));
+        assert_eq!(crate_of("crates/core/src").as_deref(), Some("ws-core"));
+        assert!(map.unreadable.is_empty());
+    }
+
+    #[test]
+    fn unreadable_file_is_recorded_with_reason() {
+        let mut cassette = make_cassette();
+        // src/map/utils.rs contains invalid UTF-8.
+        cassette.interactions[5].output = json!({"Err": "stream did not contain valid UTF-8"});
+        let yaml = serde_yaml::to_string(&cassette).unwrap();
+        let dir = std::env::temp_dir().join("speck_map_gen_unreadable");
+        std::fs::create_dir_all(&dir).unwrap();
+        let cassette_path = dir.join("map_gen.cassette.yaml");
+        std::fs::write(&cassette_path, &yaml).unwrap();
+
+        let ctx = ServiceContext::replaying(&cassette_path).unwrap();
+        let map = generate(&ctx, Path::new("/project"), None).unwrap();
+
+        assert_eq!(map.unreadable, vec!["src/map/utils.rs: stream did not contain valid UTF-8"]);
+        let map_module = map.modules.iter().find(|m| m.path == "src/map").unwrap();
+        assert!(map_module.public_items.contains(&"fn generate".to_string()));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn unreadable_defaults_to_empty_for_older_maps() {
+        let yaml = "commit_hash: abc\ngenerated_at: 2025-06-15T10:00:00Z\nmodules: []\n\
+                    directory_tree: []\ntest_infrastructure: []\n";
+        let map: CodebaseMap = serde_yaml::from_str(yaml).unwrap();
+        assert!(map.unreadable.is_empty());
+    }
+
+    #[test]
+    fn locations_and_external_deps_default_to_empty_for_older_maps() {
+        let yaml = "path: src\npublic_items: [fn run]\ndependencies: []\n";
+        let module: ModuleSummary = serde_yaml::from_str(yaml).unwrap();
+        assert!(module.locations.is_empty());
+        assert!(module.external_deps.is_empty());
+    }
+
     #[test]
     fn is_test_file_detects_test_patterns() {
         assert!(is_test_file("tests/integration.rs"));
@@ -284,6 +816,42 @@ mod tests {
         assert!(!is_test_file("src/map/mod.rs"));
     }
 
+    #[test]
+    fn is_test_file_detects_js_test_suffixes() {
+        assert!(is_test_file("src/app.test.js"));
+        assert!(is_test_file("src/components/Button.spec.tsx"));
+        assert!(is_test_file("lib/api.spec.ts"));
+        assert!(is_test_file("util.test.mjs"));
+        assert!(!is_test_file("src/app.js"));
+        assert!(!is_test_file("src/testing.ts"));
+        assert!(!is_test_file("docs/release.test.md"));
+    }
+
+    #[test]
+    fn is_test_file_detects_jest_tests_dirs() {
+        assert!(is_test_file("src/__tests__/app.js"));
+        assert!(is_test_file("__tests__/setup.ts"));
+        assert!(!is_test_file("src/__mocks__/api.js"));
+    }
+
+    #[test]
+    fn is_test_file_detects_python_tests() {
+        assert!(is_test_file("pkg/test_models.py"));
+        assert!(is_test_file("conftest.py"));
+        assert!(is_test_file("pkg/conftest.py"));
+        assert!(!is_test_file("pkg/models.py"));
+    }
+
+    #[test]
+    fn is_test_file_detects_test_runner_configs() {
+        assert!(is_test_file("jest.config.js"));
+        assert!(is_test_file("web/jest.config.ts"));
+        assert!(is_test_file("vitest.config.ts"));
+        assert!(is_test_file("pytest.ini"));
+        assert!(!is_test_file("webpack.config.js"));
+        assert!(!is_test_file("setup.cfg"));
+    }
+
     #[test]
     fn find_module_roots_identifies_boundaries() {
         let files = vec![
@@ -307,9 +875,15 @@ pub struct Foo {
 pub trait Bar {}
 struct Hidden;
 ";
-        let mut items = Vec::new();
-        extract_public_items(code, &mut items);
-        assert_eq!(items, vec!["fn hello", "struct Foo", "trait Bar"]);
+        let items = extract_public_items(code);
+        assert_eq!(
+            items,
+            vec![
+                ("fn hello".to_string(), 2),
+                ("struct Foo".to_string(), 4),
+                ("trait Bar".to_string(), 7)
+            ]
+        );
     }
 
     #[test]
@@ -320,7 +894,43 @@ use crate::ports::filesystem;
 use std::path::Path;
 ";
         let mut deps = Vec::new();
-        extract_dependencies(code, &mut deps);
+        let mut external = Vec::new();
+        extract_dependencies(code, &mut deps, &mut external);
         assert_eq!(deps, vec!["context", "ports"]);
+        assert!(external.is_empty());
+    }
+
+    #[test]
+    fn extract_dependencies_separates_external_crates() {
+        let code = r"
+use crate::context::ServiceContext;
+use serde::{Deserialize, Serialize};
+use tokio::runtime::Builder;
+use std::collections::BTreeMap;
+use core::fmt;
+use alloc::vec::Vec;
+use super::ModuleSummary;
+";
+        let mut deps = Vec::new();
+        let mut external = Vec::new();
+        extract_dependencies(code, &mut deps, &mut external);
+        assert_eq!(deps, vec!["context"]);
+        assert_eq!(external, vec!["serde", "tokio"]);
+    }
+
+    #[test]
+    fn generate_writes_map_to_provided_output_path() {
+        use crate::adapters::memory::filesystem::MemFileSystem;
+
+        let fs = MemFileSystem::new();
+        let files = fs.files();
+        let ctx = ServiceContext::testing().with_fs(fs);
+
+        let map = generate(&ctx, Path::new("/project"), Some(Path::new("before.yaml"))).unwrap();
+
+        let files = files.lock().unwrap();
+        let written = &files[Path::new("/project/before.yaml")];
+        assert_eq!(serde_yaml::from_str::<CodebaseMap>(written).unwrap(), map);
+        assert!(!files.contains_key(&Path::new("/project").join(MAP_OUTPUT_PATH)));
     }
 }
@@ -1,8 +1,11 @@
 //! Codebase map generation: structural snapshot of a target project.
 
+pub mod analysis;
 pub mod diff;
 pub mod generator;
 
+use std::collections::BTreeMap;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
@@ -19,6 +22,11 @@ pub struct CodebaseMap {
     pub directory_tree: Vec<String>,
     /// Paths to test files / test infrastructure found.
     pub test_infrastructure: Vec<String>,
+    /// Files that could not be read during generation, as `"<path>: <reason>"`.
+    ///
+    /// Their public items and dependencies are missing from `modules`.
+    #[serde(default)]
+    pub unreadable: Vec<String>,
 }
 
 /// Summary of a single module boundary.
@@ -28,6 +36,28 @@ pub struct ModuleSummary {
     pub path: String,
     /// Public items (functions, structs, traits) found in the module.
     pub public_items: Vec<String>,
-    /// Inferred dependencies (modules or crates referenced).
+    /// Internal crate modules referenced via `use crate::<module>`.
     pub dependencies: Vec<String>,
+    /// External crates referenced via `use <crate>::`, excluding `std`, `core`,
+    /// and `alloc`.
+    #[serde(default)]
+    pub external_deps: Vec<String>,
+    /// Where each public item is declared, keyed by the item as it appears in
+    /// `public_items`. Empty for maps generated before locations were recorded.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub locations: BTreeMap<String, SymbolLocation>,
+    /// Name of the workspace crate that owns this module, taken from the
+    /// nearest enclosing `Cargo.toml`. `None` outside any package, and for
+    /// maps generated before crates were detected.
+    #[serde(default, rename = "crate", skip_serializing_if = "Option::is_none")]
+    pub crate_name: Option<String>,
+}
+
+/// Source location of a public item.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SymbolLocation {
+    /// File path relative to the project root.
+    pub file: String,
+    /// 1-based line number of the declaration.
+    pub line: usize,
 }
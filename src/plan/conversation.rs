@@ -9,8 +9,8 @@ use std::fmt::Write as _;
 use serde::{Deserialize, Serialize};
 
 use crate::context::ServiceContext;
-use crate::ports::llm::CompletionRequest;
-use crate::spec::TaskSpec;
+use crate::ports::llm::{CompletionRequest, LlmClient};
+use crate::spec::{SignalType, SpecDiff, TaskSpec};
 
 /// A question the planner needs answered before specs are fully resolved.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -32,6 +32,22 @@ pub struct AnalysisResult {
     pub summary: String,
     /// Questions requiring user input (empty when all specs are resolved).
     pub questions: Vec<PushbackQuestion>,
+    /// Changes the LLM proposes to make to existing specs.
+    #[serde(default)]
+    pub updates: Vec<SpecUpdate>,
+}
+
+/// A change to a spec proposed by the LLM during analysis.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SpecUpdate {
+    /// ID of the spec to update, or its title if it has no ID yet.
+    pub task_id: String,
+    /// Replacement title, if any.
+    #[serde(default)]
+    pub title: Option<String>,
+    /// Replacement signal type, if any.
+    #[serde(default)]
+    pub signal_type: Option<SignalType>,
 }
 
 /// A single requirement item extracted from a multi-item PRD.
@@ -63,7 +79,7 @@ pub struct DecompositionResult {
 ///
 /// Returns an error if the LLM call fails or the response cannot be parsed.
 pub async fn decompose_prd(
-    ctx: &ServiceContext,
+    llm: &dyn LlmClient,
     requirement_text: &str,
 ) -> Result<DecompositionResult, String> {
     let prompt = build_decomposition_prompt(requirement_text);
@@ -71,7 +87,7 @@ pub async fn decompose_prd(
         CompletionRequest { model: "claude-sonnet-4-20250514".into(), prompt, max_tokens: 4096 };
 
     let response =
-        ctx.llm.complete(&request).await.map_err(|e| format!("LLM decomposition failed: {e}"))?;
+        llm.complete(&request).await.map_err(|e| format!("LLM decomposition failed: {e}"))?;
 
     parse_decomposition_response(&response.text)
 }
@@ -178,12 +194,16 @@ fn build_analysis_prompt(specs: &[TaskSpec]) -> String {
                \"options\": [\"option a description\", \"option b description\"],\n      \
                \"recommended\": 0\n    \
              }\n  \
+           ],\n  \
+           \"updates\": [\n    \
+             {\"task_id\": \"TASK-ID\", \"title\": \"Better title\", \"signal_type\": \"clear\"}\n  \
            ]\n\
          }\n\n\
          - If all specs have clear verification strategies, return an empty questions array.\n\
          - Each question should offer 2-3 concrete options.\n\
          - Focus on verification strategy gaps and ambiguous acceptance criteria.\n\
-         - Set \"recommended\" to the 0-indexed option you recommend (or null if no preference).\n",
+         - Set \"recommended\" to the 0-indexed option you recommend (or null if no preference).\n\
+         - Only propose updates for a clearly wrong title or signal type; omit unchanged fields.\n",
     );
 
     prompt
@@ -196,6 +216,8 @@ fn parse_analysis_response(response: &str) -> Result<AnalysisResult, String> {
         summary: String,
         #[serde(default)]
         questions: Vec<QuestionResponse>,
+        #[serde(default)]
+        updates: Vec<SpecUpdate>,
     }
 
     #[derive(Deserialize)]
@@ -222,7 +244,48 @@ fn parse_analysis_response(response: &str) -> Result<AnalysisResult, String> {
         })
         .collect();
 
-    Ok(AnalysisResult { summary: parsed.summary, questions })
+    Ok(AnalysisResult { summary: parsed.summary, questions, updates: parsed.updates })
+}
+
+/// Applies LLM-proposed updates to `specs`, returning how many were applied.
+///
+/// Each update is matched to a spec by ID, or by title for specs that have
+/// not been assigned an ID yet. `confirm` sees the spec and the change the
+/// update would make; updates it rejects are skipped. Updates that match no
+/// spec or change nothing are ignored.
+pub fn apply_llm_updates(
+    specs: &mut [TaskSpec],
+    updates: &[SpecUpdate],
+    confirm: &mut dyn FnMut(&TaskSpec, &SpecDiff) -> bool,
+) -> usize {
+    let mut applied = 0;
+    for update in updates {
+        let Some(spec) = specs.iter_mut().find(|s| {
+            if s.id.is_empty() {
+                s.title == update.task_id
+            } else {
+                s.id == update.task_id
+            }
+        }) else {
+            continue;
+        };
+
+        let mut proposed = spec.clone();
+        if let Some(title) = &update.title {
+            proposed.title.clone_from(title);
+        }
+        if let Some(signal_type) = &update.signal_type {
+            proposed.signal_type = signal_type.clone();
+        }
+
+        let diff = spec.diff(&proposed);
+        if diff.is_empty() || !confirm(spec, &diff) {
+            continue;
+        }
+        *spec = proposed;
+        applied += 1;
+    }
+    applied
 }
 
 #[cfg(test)]
@@ -259,21 +322,28 @@ mod tests {
                 checks: vec![VerificationCheck::TestSuite {
                     command: "cargo test".into(),
                     expected: "all pass".into(),
+                    retries: 0,
+                    retry_backoff_ms: 0,
+                    name: None,
                 }],
+                groups: vec![],
+                setup: vec![],
+                teardown: vec![],
             }
         } else {
-            VerificationStrategy::DirectAssertion { checks: vec![] }
+            VerificationStrategy::DirectAssertion {
+                checks: vec![],
+                groups: vec![],
+                setup: vec![],
+                teardown: vec![],
+            }
         };
 
         TaskSpec {
-            id: id.into(),
-            title: title.into(),
             requirement: Some("req-1".into()),
-            context: None,
             acceptance_criteria: vec!["it works".into()],
-            signal_type: SignalType::Clear,
             verification,
-            affected_globs: None,
+            ..TaskSpec::for_test(id, title)
         }
     }
 
@@ -322,6 +392,56 @@ mod tests {
         assert!(result.unwrap_err().contains("failed to parse"));
     }
 
+    #[test]
+    fn parse_analysis_with_updates() {
+        let response = serde_json::to_string(&json!({
+            "summary": "Task 1 is misclassified",
+            "questions": [],
+            "updates": [{"task_id": "TASK-1", "signal_type": "fuzzy"}]
+        }))
+        .unwrap();
+
+        let result = parse_analysis_response(&response).unwrap();
+        assert_eq!(
+            result.updates,
+            vec![SpecUpdate {
+                task_id: "TASK-1".into(),
+                title: None,
+                signal_type: Some(SignalType::Fuzzy),
+            }]
+        );
+    }
+
+    #[test]
+    fn apply_llm_updates_skips_rejected_and_unmatched() {
+        let mut specs =
+            vec![sample_spec("TASK-1", "Build UI", true), sample_spec("TASK-2", "Add API", true)];
+        let updates = vec![
+            SpecUpdate {
+                task_id: "TASK-1".into(),
+                title: Some("Build web UI".into()),
+                signal_type: None,
+            },
+            SpecUpdate {
+                task_id: "TASK-2".into(),
+                title: Some("Add REST API".into()),
+                signal_type: None,
+            },
+            SpecUpdate { task_id: "TASK-9".into(), title: Some("Nope".into()), signal_type: None },
+        ];
+
+        let mut seen = Vec::new();
+        let applied = apply_llm_updates(&mut specs, &updates, &mut |spec, _| {
+            seen.push(spec.id.clone());
+            spec.id == "TASK-2"
+        });
+
+        assert_eq!(applied, 1);
+        assert_eq!(seen, ["TASK-1", "TASK-2"]);
+        assert_eq!(specs[0].title, "Build UI");
+        assert_eq!(specs[1].title, "Add REST API");
+    }
+
     #[test]
     fn parse_analysis_without_recommended() {
         let response = serde_json::to_string(&json!({
@@ -169,7 +169,12 @@ mod tests {
     use crate::validate::{CheckCategory, CheckResult, ValidationResult};
 
     fn make_result(checks: Vec<CheckResult>) -> ValidationResult {
-        ValidationResult { spec_id: "TASK-1".to_string(), checks }
+        ValidationResult {
+            spec_id: "TASK-1".to_string(),
+            checks,
+            known_failures: vec![],
+            teardown_failures: vec![],
+        }
     }
 
     fn exec_pass(name: &str) -> CheckResult {
@@ -180,6 +185,8 @@ mod tests {
             expected: "all pass".to_string(),
             actual: "exit code 0".to_string(),
             category: CheckCategory::Executable,
+            group: None,
+            output: None,
         }
     }
 
@@ -191,6 +198,8 @@ mod tests {
             expected: "all pass".to_string(),
             actual: "exit code 1".to_string(),
             category: CheckCategory::Executable,
+            group: None,
+            output: None,
         }
     }
 
@@ -202,6 +211,8 @@ mod tests {
             expected: "module unchanged since spec creation".to_string(),
             actual: "module has been modified".to_string(),
             category: CheckCategory::Drift,
+            group: None,
+            output: None,
         }
     }
 
@@ -213,6 +224,8 @@ mod tests {
             expected: "manual review completed".to_string(),
             actual: "not yet reviewed".to_string(),
             category: CheckCategory::ManualReview,
+            group: None,
+            output: None,
         }
     }
 
@@ -293,8 +306,12 @@ mod tests {
 
     #[test]
     fn feedback_classification_preserves_spec_id() {
-        let result =
-            ValidationResult { spec_id: "MY-SPEC-42".to_string(), checks: vec![exec_fail("test")] };
+        let result = ValidationResult {
+            spec_id: "MY-SPEC-42".to_string(),
+            checks: vec![exec_fail("test")],
+            known_failures: vec![],
+            teardown_failures: vec![],
+        };
         let classification = classify_failures(&result);
         assert_eq!(classification.spec_id, "MY-SPEC-42");
     }
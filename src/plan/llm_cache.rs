@@ -0,0 +1,68 @@
+//! On-disk cache of planning-pass LLM responses.
+//!
+//! Survey and decomposition responses are cached per codebase commit (see
+//! [`CachingLlmClient::at_commit`]) and saved to
+//! `.spec-cache/llm_responses.json`, so re-planning at an unchanged commit
+//! reuses them instead of calling the model again. Only the latest commit's
+//! entries are kept.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::adapters::middleware::llm::CachingLlmClient;
+use crate::context::ServiceContext;
+use crate::ports::CompletionResponse;
+
+/// Cache file, relative to the project root.
+pub const LLM_CACHE_PATH: &str = ".spec-cache/llm_responses.json";
+
+#[derive(Serialize, Deserialize)]
+struct CacheFile {
+    commit: String,
+    entries: HashMap<String, CompletionResponse>,
+}
+
+/// A caching client around `ctx.llm`, seeded with the responses saved for `commit`.
+///
+/// A missing or unreadable cache file, or one saved at another commit,
+/// starts the cache empty.
+#[must_use]
+pub fn load<'a>(ctx: &'a ServiceContext, root: &Path, commit: &str) -> CachingLlmClient<'a> {
+    let path = root.join(LLM_CACHE_PATH);
+    let entries = if ctx.fs.exists(&path) {
+        ctx.fs
+            .read_to_string(&path)
+            .ok()
+            .and_then(|json| serde_json::from_str::<CacheFile>(&json).ok())
+            .filter(|file| file.commit == commit)
+            .map(|file| file.entries)
+    } else {
+        None
+    };
+    CachingLlmClient::with_entries(Box::new(ctx.llm.as_ref()), entries.unwrap_or_default())
+}
+
+/// Saves the responses `cache` holds for `commit`, replacing any earlier file.
+///
+/// # Errors
+///
+/// Returns an error if the cache file cannot be serialized or written.
+pub fn save(
+    ctx: &ServiceContext,
+    root: &Path,
+    commit: &str,
+    cache: &CachingLlmClient<'_>,
+) -> Result<(), String> {
+    let file = CacheFile { commit: commit.to_string(), entries: cache.entries() };
+    let json = serde_json::to_string_pretty(&file)
+        .map_err(|e| format!("failed to serialize LLM cache: {e}"))?;
+    let path = root.join(LLM_CACHE_PATH);
+    if let Some(parent) = path.parent() {
+        ctx.fs
+            .create_dir_all(parent)
+            .map_err(|e| format!("failed to create {}: {e}", parent.display()))?;
+    }
+    ctx.fs.write(&path, &json).map_err(|e| format!("failed to write {}: {e}", path.display()))
+}
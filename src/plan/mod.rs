@@ -2,9 +2,11 @@
 
 pub mod conversation;
 pub mod feedback;
+pub mod llm_cache;
 pub mod reconcile;
 pub mod score;
 pub mod signal;
+pub mod similarity;
 pub mod survey;
 
 /// Extract a JSON object from LLM text that may include markdown fences or trailing prose.
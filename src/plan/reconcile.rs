@@ -4,14 +4,18 @@
 //! duplicated efforts, shared abstractions that should be extracted, and
 //! dependency ordering issues. This is cheap — it reads task specs, not code.
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::fmt::Write as _;
 
 use serde::{Deserialize, Serialize};
 
 use crate::context::ServiceContext;
+use crate::error::SpeckError;
 use crate::ports::llm::{CompletionRequest, CompletionResponse};
-use crate::spec::TaskSpec;
+use crate::spec::{SignalType, TaskContext, TaskSpec, VerificationCheck, VerificationStrategy};
+use crate::store::SpecStore;
+
+use super::similarity::{SimilarityScorer, MERGE_THRESHOLD};
 
 /// A suggestion to merge two or more tasks that duplicate effort.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -72,28 +76,83 @@ pub struct PlanDiff {
 
 /// Match `new_specs` against `existing` on-disk specs by normalized title.
 ///
-/// Modifies `new_specs` in-place: matched specs receive the existing spec's ID,
-/// unmatched specs receive a fresh ID from `id_gen`.
+/// Modifies `new_specs` in-place: matched specs receive the existing spec's ID
+/// and provenance, unmatched specs receive a fresh ID from `id_gen`.
 ///
 /// Returns a `PlanDiff` describing what changed.
 pub fn match_to_existing(
     new_specs: &mut [TaskSpec],
     existing: &[TaskSpec],
     id_gen: &dyn crate::ports::IdGenerator,
+) -> PlanDiff {
+    match_specs(new_specs, existing, id_gen, None)
+}
+
+/// Like [`match_to_existing`], but also folds specs that titles missed into
+/// similar existing specs.
+///
+/// Each spec left unmatched by title is checked against the existing specs
+/// that are still unmatched; if `scorer` rates the two at or above
+/// [`MERGE_THRESHOLD`], the new spec takes over that spec's ID and provenance and the action is
+/// [`SpecMatchAction::Updated`]. Only specs that stay unmatched draw an ID
+/// from `id_gen`.
+pub fn match_and_merge_existing(
+    new_specs: &mut [TaskSpec],
+    existing: &[TaskSpec],
+    id_gen: &dyn crate::ports::IdGenerator,
+    scorer: &dyn SimilarityScorer,
+) -> PlanDiff {
+    match_specs(new_specs, existing, id_gen, Some(scorer))
+}
+
+/// Title matching for every spec first, then (with a `scorer`) merging, then
+/// fresh IDs for whatever is left.
+fn match_specs(
+    new_specs: &mut [TaskSpec],
+    existing: &[TaskSpec],
+    id_gen: &dyn crate::ports::IdGenerator,
+    scorer: Option<&dyn SimilarityScorer>,
 ) -> PlanDiff {
     let mut matched_idx: HashSet<usize> = HashSet::new();
-    let mut actions = Vec::with_capacity(new_specs.len());
+    let mut matches: Vec<Option<usize>> = Vec::with_capacity(new_specs.len());
 
-    for new_spec in new_specs.iter_mut() {
+    for new_spec in new_specs.iter() {
         let norm_new = normalize_title(&new_spec.title);
         let found = existing
             .iter()
             .enumerate()
-            .find(|(i, e)| !matched_idx.contains(i) && normalize_title(&e.title) == norm_new);
-
-        if let Some((idx, existing_spec)) = found {
+            .find(|(i, e)| !matched_idx.contains(i) && normalize_title(&e.title) == norm_new)
+            .map(|(i, _)| i);
+        if let Some(idx) = found {
             matched_idx.insert(idx);
+        }
+        matches.push(found);
+    }
+
+    if let Some(scorer) = scorer {
+        for (new_spec, found) in new_specs.iter().zip(matches.iter_mut()) {
+            if found.is_some() {
+                continue;
+            }
+            *found = existing
+                .iter()
+                .enumerate()
+                .find(|(i, e)| {
+                    !matched_idx.contains(i) && scorer.score(e, new_spec) >= MERGE_THRESHOLD
+                })
+                .map(|(i, _)| i);
+            if let Some(idx) = *found {
+                matched_idx.insert(idx);
+            }
+        }
+    }
+
+    let mut actions = Vec::with_capacity(new_specs.len());
+    for (new_spec, found) in new_specs.iter_mut().zip(matches) {
+        if let Some(existing_spec) = found.map(|idx| &existing[idx]) {
             new_spec.id.clone_from(&existing_spec.id);
+            new_spec.created_at = existing_spec.created_at;
+            new_spec.created_commit.clone_from(&existing_spec.created_commit);
             actions.push(SpecMatchAction::Updated { id: existing_spec.id.clone() });
         } else {
             let id = id_gen.generate_id();
@@ -112,6 +171,119 @@ pub fn match_to_existing(
     PlanDiff { actions, orphaned }
 }
 
+/// Applies an accepted extraction: creates its foundational task and rewires deps.
+///
+/// The foundational spec gets an ID from `ctx.id_gen`, the suggested title,
+/// the union of the dependent tasks' modules, and a custom check describing
+/// the abstraction. If a stored spec already has that title (compared as in
+/// [`match_to_existing`]), it is reused instead of creating a duplicate; since
+/// it may have dependencies of its own, the rewired store is first checked
+/// with [`detect_circular_dependencies`]. The foundational spec is then added
+/// to `context.dependencies` of each of `extraction.task_ids`, and every
+/// affected spec is saved in one [`SpecStore::save_many`] batch.
+///
+/// Returns the ID of the foundational spec.
+///
+/// # Errors
+///
+/// Returns an error if a listed task does not exist, the store cannot be read
+/// or written, or depending on a reused foundational spec would create a
+/// dependency cycle; nothing is saved in those cases.
+pub fn apply_extraction(
+    ctx: &ServiceContext,
+    store: &SpecStore<'_>,
+    extraction: &ExtractionSuggestion,
+) -> Result<String, SpeckError> {
+    let mut dependents = extraction
+        .task_ids
+        .iter()
+        .map(|id| store.load_task_spec(id))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let title = normalize_title(&extraction.suggested_task_title);
+    let stored = store
+        .list_task_specs()?
+        .iter()
+        .map(|id| store.load_task_spec(id))
+        .collect::<Result<Vec<_>, _>>()?;
+    let existing = stored.iter().find(|spec| normalize_title(&spec.title) == title);
+    let (foundation_id, mut affected) = if let Some(spec) = existing {
+        (spec.id.clone(), Vec::new())
+    } else {
+        let spec = foundational_spec(ctx, extraction, &dependents);
+        (spec.id.clone(), vec![spec])
+    };
+
+    for spec in &mut dependents {
+        if spec.id == foundation_id {
+            continue;
+        }
+        let context = spec.context.get_or_insert_with(|| TaskContext {
+            modules: vec![],
+            patterns: None,
+            dependencies: vec![],
+        });
+        if !context.dependencies.contains(&foundation_id) {
+            context.dependencies.push(foundation_id.clone());
+            affected.push(spec.clone());
+        }
+    }
+
+    if existing.is_some() {
+        let rewired: Vec<TaskSpec> = stored
+            .into_iter()
+            .map(|spec| affected.iter().find(|a| a.id == spec.id).cloned().unwrap_or(spec))
+            .collect();
+        if let Some(cycle) = detect_circular_dependencies(&rewired)
+            .into_iter()
+            .find(|cycle| cycle.contains(&foundation_id))
+        {
+            return Err(SpeckError::Store(format!(
+                "extracting {foundation_id} would create a dependency cycle: {}",
+                cycle.join(" -> ")
+            )));
+        }
+    }
+
+    store.save_many(&affected)?;
+    Ok(foundation_id)
+}
+
+/// A new foundational spec for `extraction`, covering the modules of `dependents`.
+fn foundational_spec(
+    ctx: &ServiceContext,
+    extraction: &ExtractionSuggestion,
+    dependents: &[TaskSpec],
+) -> TaskSpec {
+    let modules: BTreeSet<&String> =
+        dependents.iter().filter_map(|s| s.context.as_ref()).flat_map(|c| &c.modules).collect();
+    TaskSpec {
+        id: ctx.id_gen.generate_id(),
+        title: extraction.suggested_task_title.clone(),
+        requirement: dependents.iter().find_map(|s| s.requirement.clone()),
+        context: Some(TaskContext {
+            modules: modules.into_iter().cloned().collect(),
+            patterns: None,
+            dependencies: vec![],
+        }),
+        acceptance_criteria: vec![extraction.abstraction.clone()],
+        signal_type: SignalType::Clear,
+        verification: VerificationStrategy::DirectAssertion {
+            checks: vec![VerificationCheck::Custom {
+                description: extraction.abstraction.clone(),
+                name: None,
+            }],
+            groups: vec![],
+            setup: vec![],
+            teardown: vec![],
+        },
+        affected_globs: None,
+        created_at: Some(ctx.clock.now()),
+        created_commit: ctx.git.current_commit().ok(),
+        disabled: false,
+    }
+}
+
 /// Normalize a spec title for matching purposes.
 fn normalize_title(title: &str) -> String {
     title.to_lowercase()
@@ -137,18 +309,23 @@ pub struct ReconciliationResult {
 /// - Shared abstractions that multiple tasks touch similarly
 /// - Dependency ordering issues (circular deps, foundational tasks not first)
 ///
+/// Merges found locally via [`detect_local_merges`] with `scorer` are passed
+/// to the LLM and listed first in the result.
+///
 /// # Errors
 ///
 /// Returns an error if LLM analysis fails or the response cannot be parsed.
 pub async fn reconcile(
     ctx: &ServiceContext,
     task_specs: &[TaskSpec],
+    scorer: &dyn SimilarityScorer,
 ) -> Result<ReconciliationResult, String> {
-    // First, detect circular dependencies locally (no LLM needed).
+    // First, detect circular dependencies and local merges (no LLM needed).
     let circular = detect_circular_dependencies(task_specs);
+    let local_merges = detect_local_merges(task_specs, scorer);
 
     // Then ask the LLM to analyze for duplicates, shared abstractions, and ordering.
-    let prompt = build_reconciliation_prompt(task_specs, &circular);
+    let prompt = build_reconciliation_prompt(task_specs, &circular, &local_merges);
     let request =
         CompletionRequest { model: "claude-sonnet-4-20250514".into(), prompt, max_tokens: 4096 };
 
@@ -156,12 +333,176 @@ pub async fn reconcile(
         ctx.llm.complete(&request).await.map_err(|e| format!("LLM reconciliation failed: {e}"))?;
 
     let mut result = parse_reconciliation_response(&response.text, circular)?;
+    result.suggested_merges = seed_merges(local_merges, result.suggested_merges);
     result.suggested_reorders = filter_reorders(result.suggested_reorders, task_specs);
     Ok(result)
 }
 
+/// Detects specs whose `context.modules` overlap.
+///
+/// Combines [`detect_identical_modules`] with
+/// [`detect_partial_module_overlap`], identical groups first.
+#[must_use]
+pub fn detect_module_overlap(specs: &[TaskSpec]) -> Vec<MergeSuggestion> {
+    let mut merges = detect_identical_modules(specs);
+    merges.extend(detect_partial_module_overlap(specs));
+    merges
+}
+
+/// Groups specs with identical, non-empty module sets into high-confidence
+/// merge suggestions, in input order.
+///
+/// The merged title is taken from the first spec in each group.
+#[must_use]
+pub fn detect_identical_modules(specs: &[TaskSpec]) -> Vec<MergeSuggestion> {
+    let module_sets = module_sets(specs);
+    let mut groups: Vec<(&BTreeSet<&str>, Vec<&TaskSpec>)> = Vec::new();
+    for (spec, modules) in &module_sets {
+        match groups.iter_mut().find(|(m, _)| *m == modules) {
+            Some((_, members)) => members.push(spec),
+            None => groups.push((modules, vec![spec])),
+        }
+    }
+    groups
+        .into_iter()
+        .filter(|(_, members)| members.len() > 1)
+        .map(|(modules, members)| {
+            let subject = match members.len() {
+                2 => "both specs".to_string(),
+                n => format!("all {n} specs"),
+            };
+            MergeSuggestion {
+                task_ids: members.iter().map(|s| s.id.clone()).collect(),
+                reason: format!("{subject} target {}", describe_modules(modules)),
+                merged_title: members[0].title.clone(),
+            }
+        })
+        .collect()
+}
+
+/// Suggests merging each pair of specs whose module sets differ but share
+/// at least one module, naming the shared modules.
+///
+/// These are weaker signals than [`detect_identical_modules`]: two specs may
+/// both touch a common module such as `cli` without duplicating effort. The
+/// merged title is taken from the first spec in each pair.
+#[must_use]
+pub fn detect_partial_module_overlap(specs: &[TaskSpec]) -> Vec<MergeSuggestion> {
+    let module_sets = module_sets(specs);
+    let mut merges = Vec::new();
+    for (i, (a, a_modules)) in module_sets.iter().enumerate() {
+        for (b, b_modules) in &module_sets[i + 1..] {
+            if a_modules == b_modules {
+                continue;
+            }
+            let shared: BTreeSet<&str> = a_modules.intersection(b_modules).copied().collect();
+            if !shared.is_empty() {
+                merges.push(MergeSuggestion {
+                    task_ids: vec![a.id.clone(), b.id.clone()],
+                    reason: format!("both specs touch {}", describe_modules(&shared)),
+                    merged_title: a.title.clone(),
+                });
+            }
+        }
+    }
+    merges
+}
+
+/// Each spec with a non-empty `context.modules`, paired with its module set.
+fn module_sets(specs: &[TaskSpec]) -> Vec<(&TaskSpec, BTreeSet<&str>)> {
+    specs
+        .iter()
+        .map(|spec| {
+            let modules = spec
+                .context
+                .as_ref()
+                .map(|c| c.modules.iter().map(String::as_str).collect())
+                .unwrap_or_default();
+            (spec, modules)
+        })
+        .filter(|(_, modules): &(_, BTreeSet<&str>)| !modules.is_empty())
+        .collect()
+}
+
+/// `module a` or `modules a, b`.
+fn describe_modules(modules: &BTreeSet<&str>) -> String {
+    let noun = if modules.len() == 1 { "module" } else { "modules" };
+    format!("{noun} {}", modules.iter().copied().collect::<Vec<_>>().join(", "))
+}
+
+/// Detects merges without the LLM: shared module sets plus similar specs.
+///
+/// Starts from [`detect_module_overlap`], then adds a pairwise suggestion for
+/// every two specs that `scorer` rates at or above [`MERGE_THRESHOLD`] and
+/// that are not already paired by module overlap.
+#[must_use]
+pub fn detect_local_merges(
+    specs: &[TaskSpec],
+    scorer: &dyn SimilarityScorer,
+) -> Vec<MergeSuggestion> {
+    let mut merges = detect_module_overlap(specs);
+    add_similar_pairs(&mut merges, specs, scorer);
+    merges
+}
+
+/// Detects only the local merges confident enough to act on unreviewed.
+///
+/// Like [`detect_local_merges`], but without
+/// [`detect_partial_module_overlap`]'s pairs: identical module sets plus
+/// pairs that `scorer` rates at or above [`MERGE_THRESHOLD`].
+#[must_use]
+pub fn detect_high_confidence_merges(
+    specs: &[TaskSpec],
+    scorer: &dyn SimilarityScorer,
+) -> Vec<MergeSuggestion> {
+    let mut merges = detect_identical_modules(specs);
+    add_similar_pairs(&mut merges, specs, scorer);
+    merges
+}
+
+/// Appends a suggestion for each pair of specs `scorer` rates at or above
+/// [`MERGE_THRESHOLD`], skipping pairs `merges` already covers.
+fn add_similar_pairs(
+    merges: &mut Vec<MergeSuggestion>,
+    specs: &[TaskSpec],
+    scorer: &dyn SimilarityScorer,
+) {
+    for (i, a) in specs.iter().enumerate() {
+        for b in &specs[i + 1..] {
+            let covered =
+                merges.iter().any(|m| m.task_ids.contains(&a.id) && m.task_ids.contains(&b.id));
+            if covered {
+                continue;
+            }
+            let score = scorer.score(a, b);
+            if score >= MERGE_THRESHOLD {
+                merges.push(MergeSuggestion {
+                    task_ids: vec![a.id.clone(), b.id.clone()],
+                    reason: format!("acceptance criteria overlap (similarity {score:.2})"),
+                    merged_title: a.title.clone(),
+                });
+            }
+        }
+    }
+}
+
+/// Puts locally detected merges first, dropping LLM merges over the same task set.
+fn seed_merges(local: Vec<MergeSuggestion>, llm: Vec<MergeSuggestion>) -> Vec<MergeSuggestion> {
+    let task_set =
+        |m: &MergeSuggestion| -> BTreeSet<String> { m.task_ids.iter().cloned().collect() };
+    let seen: HashSet<BTreeSet<String>> = local.iter().map(task_set).collect();
+    let mut merges = local;
+    merges.extend(llm.into_iter().filter(|m| !seen.contains(&task_set(m))));
+    merges
+}
+
 /// Detects circular dependencies among task specs using their context.dependencies.
-fn detect_circular_dependencies(specs: &[TaskSpec]) -> Vec<Vec<String>> {
+///
+/// Each returned cycle lists spec IDs in dependency order, starting from the
+/// first spec (in input order) reached on the cycle. Dependencies on IDs
+/// outside `specs` are ignored.
+#[must_use]
+pub fn detect_circular_dependencies(specs: &[TaskSpec]) -> Vec<Vec<String>> {
     // Build adjacency map from task dependencies.
     let mut graph: HashMap<&str, Vec<&str>> = HashMap::new();
     let spec_ids: HashSet<&str> = specs.iter().map(|s| s.id.as_str()).collect();
@@ -183,7 +524,8 @@ fn detect_circular_dependencies(specs: &[TaskSpec]) -> Vec<Vec<String>> {
     let mut on_stack: HashSet<&str> = HashSet::new();
     let mut stack: Vec<&str> = Vec::new();
 
-    for id in &spec_ids {
+    for spec in specs {
+        let id = spec.id.as_str();
         if !visited.contains(id) {
             dfs_find_cycles(id, &graph, &mut visited, &mut on_stack, &mut stack, &mut cycles);
         }
@@ -224,7 +566,11 @@ fn dfs_find_cycles<'a>(
 }
 
 /// Builds the LLM prompt for reconciliation analysis.
-fn build_reconciliation_prompt(specs: &[TaskSpec], circular: &[Vec<String>]) -> String {
+fn build_reconciliation_prompt(
+    specs: &[TaskSpec],
+    circular: &[Vec<String>],
+    local_merges: &[MergeSuggestion],
+) -> String {
     let mut prompt = String::new();
 
     prompt.push_str(
@@ -260,6 +606,18 @@ fn build_reconciliation_prompt(specs: &[TaskSpec], circular: &[Vec<String>]) ->
         prompt.push('\n');
     }
 
+    if !local_merges.is_empty() {
+        prompt.push_str(
+            "## Already Identified Merges\n\n\
+             Locally detected merge candidates (shared modules or similar criteria); \
+             do not repeat them.\n\n",
+        );
+        for merge in local_merges {
+            let _ = writeln!(prompt, "- {}: {}", merge.task_ids.join(", "), merge.reason);
+        }
+        prompt.push('\n');
+    }
+
     prompt.push_str(
         "## Instructions\n\n\
          Respond with JSON (no markdown fences):\n\
@@ -363,8 +721,9 @@ mod tests {
     use super::*;
     use crate::cassette::format::{Cassette, Interaction};
     use crate::context::ServiceContext;
+    use crate::plan::similarity::TokenJaccard;
     use crate::ports::IdGenerator;
-    use crate::spec::{SignalType, TaskContext, VerificationCheck, VerificationStrategy};
+    use crate::spec::{TaskContext, VerificationCheck, VerificationStrategy};
     use chrono::Utc;
     use serde_json::json;
     use std::path::Path;
@@ -386,16 +745,7 @@ mod tests {
     }
 
     fn bare_spec(id: &str, title: &str) -> TaskSpec {
-        TaskSpec {
-            id: id.into(),
-            title: title.into(),
-            requirement: None,
-            context: None,
-            acceptance_criteria: vec![],
-            signal_type: SignalType::Clear,
-            verification: VerificationStrategy::DirectAssertion { checks: vec![] },
-            affected_globs: None,
-        }
+        TaskSpec::for_test(id, title)
     }
 
     // --- match_to_existing tests ---
@@ -504,23 +854,26 @@ mod tests {
 
     fn sample_spec(id: &str, title: &str, modules: &[&str], deps: &[&str]) -> TaskSpec {
         TaskSpec {
-            id: id.into(),
-            title: title.into(),
             requirement: Some("req-1".into()),
             context: Some(TaskContext {
                 modules: modules.iter().map(|s| (*s).to_string()).collect(),
                 patterns: None,
                 dependencies: deps.iter().map(|s| (*s).to_string()).collect(),
             }),
-            acceptance_criteria: vec!["it works".into()],
-            signal_type: SignalType::Clear,
+            acceptance_criteria: vec![format!("{title} works")],
             verification: VerificationStrategy::DirectAssertion {
                 checks: vec![VerificationCheck::TestSuite {
                     command: "cargo test".into(),
                     expected: "all pass".into(),
+                    retries: 0,
+                    retry_backoff_ms: 0,
+                    name: None,
                 }],
+                groups: vec![],
+                setup: vec![],
+                teardown: vec![],
             },
-            affected_globs: None,
+            ..TaskSpec::for_test(id, title)
         }
     }
 
@@ -577,6 +930,120 @@ mod tests {
         assert!(cycles.is_empty());
     }
 
+    // --- detect_module_overlap tests ---
+
+    #[test]
+    fn specs_sharing_module_set_get_local_merge() {
+        let specs = vec![
+            sample_spec("T1", "Auth tokens", &["auth"], &[]),
+            sample_spec("T2", "UI shell", &["ui"], &[]),
+            sample_spec("T3", "Auth sessions", &["auth"], &[]),
+            sample_spec("T4", "Auth + UI", &["auth", "ui"], &[]),
+            sample_spec("T5", "No context", &[], &[]),
+        ];
+
+        let merges = detect_module_overlap(&specs);
+        let suggestion = |ids: [&str; 2], reason: &str, title: &str| MergeSuggestion {
+            task_ids: ids.map(String::from).to_vec(),
+            reason: reason.into(),
+            merged_title: title.into(),
+        };
+        assert_eq!(
+            merges,
+            vec![
+                suggestion(["T1", "T3"], "both specs target module auth", "Auth tokens"),
+                suggestion(["T1", "T4"], "both specs touch module auth", "Auth tokens"),
+                suggestion(["T2", "T4"], "both specs touch module ui", "UI shell"),
+                suggestion(["T3", "T4"], "both specs touch module auth", "Auth sessions"),
+            ]
+        );
+    }
+
+    #[test]
+    fn partially_overlapping_specs_name_their_shared_modules() {
+        let specs = vec![
+            sample_spec("T1", "Export CSV", &["export", "csv", "cli"], &[]),
+            sample_spec("T2", "Export JSON", &["export", "json", "cli"], &[]),
+            sample_spec("T3", "Import CSV", &["import", "csv"], &[]),
+            sample_spec("T4", "Export JSON", &["export", "json", "cli"], &[]),
+            sample_spec("T5", "Export JSON", &["export", "json", "cli"], &[]),
+        ];
+
+        let merges = detect_module_overlap(&specs);
+        let reasons: Vec<(Vec<&str>, &str)> = merges
+            .iter()
+            .map(|m| (m.task_ids.iter().map(String::as_str).collect(), m.reason.as_str()))
+            .collect();
+        assert_eq!(
+            reasons,
+            vec![
+                (vec!["T2", "T4", "T5"], "all 3 specs target modules cli, export, json"),
+                (vec!["T1", "T2"], "both specs touch modules cli, export"),
+                (vec!["T1", "T3"], "both specs touch module csv"),
+                (vec!["T1", "T4"], "both specs touch modules cli, export"),
+                (vec!["T1", "T5"], "both specs touch modules cli, export"),
+            ]
+        );
+    }
+
+    #[test]
+    fn local_merges_add_similar_specs_not_grouped_by_module() {
+        let mut specs = vec![
+            sample_spec("T1", "Auth tokens", &["auth"], &[]),
+            sample_spec("T2", "Auth sessions", &["auth"], &[]),
+            sample_spec("T3", "CSV export", &[], &[]),
+            sample_spec("T4", "Export to CSV", &[], &[]),
+        ];
+        for spec in &mut specs {
+            spec.acceptance_criteria = vec!["export rows as csv".into()];
+        }
+        specs[0].acceptance_criteria = vec!["tokens expire".into()];
+        specs[1].acceptance_criteria = vec!["tokens expire".into()];
+
+        let merges = detect_local_merges(&specs, &TokenJaccard);
+        assert_eq!(merges.len(), 2);
+        assert_eq!(merges[0].reason, "both specs target module auth");
+        assert_eq!(merges[1].task_ids, vec!["T3", "T4"]);
+        assert_eq!(merges[1].reason, "acceptance criteria overlap (similarity 1.00)");
+    }
+
+    #[test]
+    fn seeded_merges_drop_llm_duplicates() {
+        let local = vec![MergeSuggestion {
+            task_ids: vec!["T1".into(), "T2".into()],
+            reason: "both specs target module auth".into(),
+            merged_title: "Auth".into(),
+        }];
+        let llm = vec![
+            MergeSuggestion {
+                task_ids: vec!["T2".into(), "T1".into()],
+                reason: "same work".into(),
+                merged_title: "Unified auth".into(),
+            },
+            MergeSuggestion {
+                task_ids: vec!["T3".into(), "T4".into()],
+                reason: "similar criteria".into(),
+                merged_title: "Reports".into(),
+            },
+        ];
+
+        let merges = seed_merges(local, llm);
+        assert_eq!(merges.len(), 2);
+        assert_eq!(merges[0].reason, "both specs target module auth");
+        assert_eq!(merges[1].task_ids, vec!["T3", "T4"]);
+    }
+
+    #[test]
+    fn prompt_lists_local_merges() {
+        let specs =
+            vec![sample_spec("T1", "A", &["auth"], &[]), sample_spec("T2", "B", &["auth"], &[])];
+        let prompt = build_reconciliation_prompt(&specs, &[], &detect_module_overlap(&specs));
+        assert!(prompt.contains("Already Identified Merges"));
+        assert!(prompt.contains("Locally detected merge candidates"));
+        assert!(!prompt.contains("identical modules"));
+        assert!(prompt.contains("- T1, T2: both specs target module auth"));
+    }
+
     // --- build_reconciliation_prompt tests ---
 
     #[test]
@@ -587,7 +1054,7 @@ mod tests {
         ];
         let circular = vec![vec!["T1".into(), "T2".into()]];
 
-        let prompt = build_reconciliation_prompt(&specs, &circular);
+        let prompt = build_reconciliation_prompt(&specs, &circular, &[]);
         assert!(prompt.contains("T1 — Auth module"));
         assert!(prompt.contains("T2 — Login UI"));
         assert!(prompt.contains("Modules: auth"));
@@ -601,7 +1068,7 @@ mod tests {
     #[test]
     fn prompt_omits_circular_section_when_empty() {
         let specs = vec![sample_spec("T1", "Task 1", &["mod_a"], &[])];
-        let prompt = build_reconciliation_prompt(&specs, &[]);
+        let prompt = build_reconciliation_prompt(&specs, &[], &[]);
         assert!(!prompt.contains("Circular Dependencies"));
     }
 
@@ -676,6 +1143,85 @@ mod tests {
         assert!(result.unwrap_err().contains("failed to parse"));
     }
 
+    // --- apply_extraction tests ---
+
+    #[test]
+    fn apply_extraction_creates_foundation_and_rewires_dependencies() {
+        let ctx = ServiceContext::testing();
+        let store = SpecStore::new(&ctx, Path::new("/store"));
+        store
+            .save_many(&[
+                sample_spec("T1", "CSV export", &["src/export"], &[]),
+                sample_spec("T2", "JSON export", &["src/json"], &["T1"]),
+                sample_spec("T3", "Unrelated", &["src/other"], &[]),
+            ])
+            .unwrap();
+        let extraction = ExtractionSuggestion {
+            task_ids: vec!["T1".into(), "T2".into()],
+            abstraction: "Shared serializer trait".into(),
+            suggested_task_title: "Add serializer trait".into(),
+        };
+
+        let id = apply_extraction(&ctx, &store, &extraction).unwrap();
+
+        let foundation = store.load_task_spec(&id).unwrap();
+        assert_eq!(foundation.title, "Add serializer trait");
+        assert_eq!(foundation.requirement.as_deref(), Some("req-1"));
+        assert_eq!(foundation.context.unwrap().modules, ["src/export", "src/json"]);
+        let deps = |id: &str| store.load_task_spec(id).unwrap().context.unwrap().dependencies;
+        assert_eq!(deps("T1"), [id.as_str()]);
+        assert_eq!(deps("T2"), ["T1", id.as_str()]);
+        assert!(deps("T3").is_empty());
+
+        // Re-applying reuses the foundational spec instead of creating another.
+        let again = ExtractionSuggestion {
+            task_ids: vec!["T3".into()],
+            suggested_task_title: "add SERIALIZER trait".into(),
+            ..extraction
+        };
+        assert_eq!(apply_extraction(&ctx, &store, &again).unwrap(), id);
+        assert_eq!(store.list_task_specs().unwrap().len(), 4);
+        assert_eq!(deps("T3"), [id]);
+    }
+
+    #[test]
+    fn apply_extraction_with_unknown_task_saves_nothing() {
+        let ctx = ServiceContext::testing();
+        let store = SpecStore::new(&ctx, Path::new("/store"));
+        store.save_task_spec(&sample_spec("T1", "CSV export", &["src/export"], &[])).unwrap();
+        let extraction = ExtractionSuggestion {
+            task_ids: vec!["T1".into(), "MISSING".into()],
+            abstraction: "Shared serializer trait".into(),
+            suggested_task_title: "Add serializer trait".into(),
+        };
+
+        assert!(apply_extraction(&ctx, &store, &extraction).is_err());
+        assert_eq!(store.list_task_specs().unwrap(), ["T1"]);
+    }
+
+    #[test]
+    fn apply_extraction_refuses_reused_foundation_that_would_cycle() {
+        let ctx = ServiceContext::testing();
+        let store = SpecStore::new(&ctx, Path::new("/store"));
+        store
+            .save_many(&[
+                sample_spec("T1", "CSV export", &["src/export"], &[]),
+                sample_spec("T2", "Add serializer trait", &["src/serde"], &["T1"]),
+            ])
+            .unwrap();
+        let extraction = ExtractionSuggestion {
+            task_ids: vec!["T1".into()],
+            abstraction: "Shared serializer trait".into(),
+            suggested_task_title: "Add serializer trait".into(),
+        };
+
+        let err = apply_extraction(&ctx, &store, &extraction).unwrap_err();
+
+        assert!(err.to_string().contains("cycle: T1 -> T2"), "{err}");
+        let t1 = store.load_task_spec("T1").unwrap();
+        assert!(t1.context.unwrap().dependencies.is_empty());
+    }
+
     // --- reconcile integration tests ---
 
     #[tokio::test]
@@ -725,8 +1271,10 @@ mod tests {
             sample_spec("T3", "Error types", &["errors"], &[]),
         ];
 
-        let result = reconcile(&ctx, &specs).await.unwrap();
+        let result = reconcile(&ctx, &specs, &TokenJaccard).await.unwrap();
+        // T1 and T2 share the auth module; the local suggestion replaces the LLM's.
         assert_eq!(result.suggested_merges.len(), 1);
+        assert_eq!(result.suggested_merges[0].reason, "both specs target module auth");
         assert_eq!(result.suggested_extractions.len(), 1);
         assert_eq!(result.suggested_reorders.len(), 1);
         assert!(result.circular_dependencies.is_empty());
@@ -768,7 +1316,7 @@ mod tests {
             sample_spec("T2", "UI components", &["ui"], &["T1"]),
         ];
 
-        let result = reconcile(&ctx, &specs).await.unwrap();
+        let result = reconcile(&ctx, &specs, &TokenJaccard).await.unwrap();
         assert!(result.suggested_merges.is_empty());
         assert!(result.suggested_extractions.is_empty());
         assert!(result.suggested_reorders.is_empty());
@@ -815,7 +1363,7 @@ mod tests {
             sample_spec("T2", "Module B", &["mod_b"], &["T1"]),
         ];
 
-        let result = reconcile(&ctx, &specs).await.unwrap();
+        let result = reconcile(&ctx, &specs, &TokenJaccard).await.unwrap();
         assert!(!result.circular_dependencies.is_empty());
         assert_eq!(result.suggested_reorders.len(), 1);
 
@@ -925,7 +1473,7 @@ mod tests {
             sample_spec("T2", "Post-integration hook", &["db"], &[]),
         ];
 
-        let result = reconcile(&ctx, &specs).await.unwrap();
+        let result = reconcile(&ctx, &specs, &TokenJaccard).await.unwrap();
         assert!(
             result.suggested_reorders.is_empty(),
             "independent items sharing a subsystem should not be reordered"
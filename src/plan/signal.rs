@@ -84,7 +84,7 @@ pub enum VerificationStrategy {
 }
 
 /// Result of classifying a requirement's signal type.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ClassificationResult {
     /// Successfully classified with a proposed verification strategy.
     Classified {
@@ -92,6 +92,10 @@ pub enum ClassificationResult {
         signal_type: SignalType,
         /// The proposed verification strategy.
         strategy: VerificationStrategy,
+        /// The model's confidence in the classification (0.0–1.0), if reported.
+        confidence: Option<f64>,
+        /// Concrete acceptance criteria derived from the requirement.
+        acceptance_criteria: Vec<String>,
     },
     /// Requirement is under-specified; pushback needed before it enters the system.
     PushbackRequired {
@@ -150,6 +154,8 @@ Response formats:
 - For "internal": {{"type": "internal", "approach": "refactor"|"trace", "description": "..."}}
 - For "pushback": {{"type": "pushback", "reason": "..."}}
 
+Every response except "pushback" must also include a "confidence" field: a number from 0.0 to 1.0 stating how confident you are in the classification, and an "acceptance_criteria" field: an array of short, concrete, testable statements derived from the requirement that together define when it is done.
+
 Respond ONLY with the JSON object, no other text."#
     )
 }
@@ -165,6 +171,22 @@ fn parse_classification_response(
         .and_then(|t| t.as_str())
         .ok_or("missing 'type' field in classification response")?;
 
+    let confidence =
+        value.get("confidence").and_then(serde_json::Value::as_f64).map(|c| c.clamp(0.0, 1.0));
+    let acceptance_criteria: Vec<String> = value
+        .get("acceptance_criteria")
+        .and_then(serde_json::Value::as_array)
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(serde_json::Value::as_str)
+                .map(str::trim)
+                .filter(|c| !c.is_empty())
+                .map(String::from)
+                .collect()
+        })
+        .unwrap_or_default();
+
     match signal_type {
         "clear" => {
             let checks = value
@@ -186,6 +208,8 @@ fn parse_classification_response(
             Ok(ClassificationResult::Classified {
                 signal_type: SignalType::Clear,
                 strategy: VerificationStrategy::DirectAssertion { checks },
+                confidence,
+                acceptance_criteria,
             })
         }
         "fuzzy" => {
@@ -211,6 +235,8 @@ fn parse_classification_response(
             Ok(ClassificationResult::Classified {
                 signal_type: SignalType::FuzzyButConstrainable,
                 strategy: VerificationStrategy::StructuralDecomposition { sub_assertions },
+                confidence,
+                acceptance_criteria,
             })
         }
         "internal" => {
@@ -225,6 +251,8 @@ fn parse_classification_response(
             Ok(ClassificationResult::Classified {
                 signal_type: SignalType::InternalLogic,
                 strategy,
+                confidence,
+                acceptance_criteria,
             })
         }
         "pushback" => {
@@ -303,6 +331,8 @@ mod tests {
         assert_eq!(
             result,
             ClassificationResult::Classified {
+                confidence: None,
+                acceptance_criteria: vec![],
                 signal_type: SignalType::Clear,
                 strategy: VerificationStrategy::DirectAssertion {
                     checks: vec![
@@ -336,8 +366,10 @@ mod tests {
 
         match &result {
             ClassificationResult::Classified {
+                confidence: None,
                 signal_type,
                 strategy: VerificationStrategy::StructuralDecomposition { sub_assertions },
+                ..
             } => {
                 assert_eq!(*signal_type, SignalType::FuzzyButConstrainable);
                 assert_eq!(sub_assertions.len(), 2);
@@ -377,6 +409,8 @@ mod tests {
         assert_eq!(
             result,
             ClassificationResult::Classified {
+                confidence: None,
+                acceptance_criteria: vec![],
                 signal_type: SignalType::InternalLogic,
                 strategy: VerificationStrategy::RefactorToExpose {
                     description:
@@ -404,6 +438,8 @@ mod tests {
         assert_eq!(
             result,
             ClassificationResult::Classified {
+                confidence: None,
+                acceptance_criteria: vec![],
                 signal_type: SignalType::InternalLogic,
                 strategy: VerificationStrategy::TraceAssertion {
                     description:
@@ -466,6 +502,8 @@ mod tests {
         assert_eq!(
             result,
             ClassificationResult::Classified {
+                confidence: None,
+                acceptance_criteria: vec![],
                 signal_type: SignalType::Clear,
                 strategy: VerificationStrategy::DirectAssertion {
                     checks: vec![
@@ -490,6 +528,8 @@ mod tests {
         assert_eq!(
             result,
             ClassificationResult::Classified {
+                confidence: None,
+                acceptance_criteria: vec![],
                 signal_type: SignalType::Clear,
                 strategy: VerificationStrategy::DirectAssertion {
                     checks: vec![
@@ -501,6 +541,17 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_acceptance_criteria_when_present() {
+        let json = r#"{"type": "internal", "approach": "trace", "description": "d", "acceptance_criteria": ["retries three times", "", 7]}"#;
+        let result = parse_classification_response(json).unwrap();
+        assert!(matches!(
+            result,
+            ClassificationResult::Classified { ref acceptance_criteria, .. }
+                if acceptance_criteria == &["retries three times"]
+        ));
+    }
+
     #[test]
     fn parse_pushback_signal() {
         let json = r#"{"type": "pushback", "reason": "under-specified"}"#;
@@ -510,4 +561,28 @@ mod tests {
             ClassificationResult::PushbackRequired { reason: "under-specified".into() }
         );
     }
+
+    #[test]
+    fn parse_confidence_when_present() {
+        let json = r#"{"type": "clear", "confidence": 0.42, "checks": ["check1"]}"#;
+        let result = parse_classification_response(json).unwrap();
+        assert!(matches!(
+            result,
+            ClassificationResult::Classified { confidence: Some(c), .. } if (c - 0.42).abs() < f64::EPSILON
+        ));
+    }
+
+    #[test]
+    fn confidence_absent_is_none_and_out_of_range_is_clamped() {
+        let absent = parse_classification_response(r#"{"type": "clear", "checks": []}"#).unwrap();
+        assert!(matches!(absent, ClassificationResult::Classified { confidence: None, .. }));
+
+        let high = parse_classification_response(
+            r#"{"type": "internal", "confidence": 7, "description": "d"}"#,
+        )
+        .unwrap();
+        assert!(
+            matches!(high, ClassificationResult::Classified { confidence: Some(c), .. } if c >= 1.0)
+        );
+    }
 }
@@ -0,0 +1,79 @@
+//! Pairwise similarity scoring for local merge detection.
+//!
+//! The reconciliation pass uses a [`SimilarityScorer`] to flag specs whose
+//! acceptance criteria overlap enough to be merged without asking the LLM.
+//! [`TokenJaccard`] is the dependency-free default; smarter scorers (e.g.
+//! embedding-based) can be plugged in by implementing the trait.
+
+use std::collections::HashSet;
+
+use crate::spec::TaskSpec;
+
+/// Score at or above which two specs are suggested for a local merge.
+pub const MERGE_THRESHOLD: f64 = 0.6;
+
+/// Scores how similar two task specs are.
+pub trait SimilarityScorer {
+    /// Returns a similarity in `0.0..=1.0`, where `1.0` means identical.
+    fn score(&self, a: &TaskSpec, b: &TaskSpec) -> f64;
+}
+
+/// Jaccard similarity of the word sets of two specs' acceptance criteria.
+///
+/// Words are lowercased runs of alphanumeric characters. Specs without any
+/// criteria score `0.0` against everything.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TokenJaccard;
+
+impl SimilarityScorer for TokenJaccard {
+    #[allow(clippy::cast_precision_loss)]
+    fn score(&self, a: &TaskSpec, b: &TaskSpec) -> f64 {
+        let a = criteria_tokens(a);
+        let b = criteria_tokens(b);
+        let union = a.union(&b).count();
+        if union == 0 {
+            return 0.0;
+        }
+        let shared = a.intersection(&b).count();
+        shared as f64 / union as f64
+    }
+}
+
+/// Lowercased alphanumeric words across all acceptance criteria.
+fn criteria_tokens(spec: &TaskSpec) -> HashSet<String> {
+    spec.acceptance_criteria
+        .iter()
+        .flat_map(|c| c.split(|ch: char| !ch.is_alphanumeric()))
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(criteria: &[&str]) -> TaskSpec {
+        TaskSpec {
+            acceptance_criteria: criteria.iter().map(|c| (*c).to_string()).collect(),
+            ..TaskSpec::for_test("T", "Task")
+        }
+    }
+
+    #[test]
+    fn jaccard_scores_overlapping_criteria() {
+        let a = spec(&["Export rows as CSV", "include header row"]);
+        let b = spec(&["export rows as csv"]);
+        // {export, rows, as, csv, include, header, row} vs {export, rows, as, csv}
+        let score = TokenJaccard.score(&a, &b);
+        assert!((score - 4.0 / 7.0).abs() < f64::EPSILON, "score was {score}");
+        assert!((TokenJaccard.score(&b, &b) - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn jaccard_is_zero_without_criteria_or_overlap() {
+        assert!(TokenJaccard.score(&spec(&[]), &spec(&[])).abs() < f64::EPSILON);
+        let score = TokenJaccard.score(&spec(&["login works"]), &spec(&["export csv"]));
+        assert!(score.abs() < f64::EPSILON);
+    }
+}
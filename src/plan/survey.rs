@@ -13,7 +13,7 @@ use serde::{Deserialize, Serialize};
 use crate::context::ServiceContext;
 use crate::map::generator;
 use crate::map::CodebaseMap;
-use crate::ports::llm::{CompletionRequest, CompletionResponse};
+use crate::ports::llm::{CompletionRequest, CompletionResponse, LlmClient};
 
 /// Path where the cached codebase map is stored relative to project root.
 const CACHE_PATH: &str = ".spec-cache/codebase_map.yaml";
@@ -48,6 +48,7 @@ pub struct SurveyResult {
 /// Reads the codebase structure, identifies module boundaries, key interfaces,
 /// cross-cutting concerns, and builds a routing table. Uses a cached codebase
 /// map from `.spec-cache/` if the commit hash matches, otherwise regenerates.
+/// With `no_cache`, the cache is never read and the map is always regenerated.
 ///
 /// Returns both the survey result and the underlying codebase map so callers
 /// can reuse the map (e.g., for linkage resolution) without a second load.
@@ -59,18 +60,52 @@ pub async fn broad_survey(
     ctx: &ServiceContext,
     root: &Path,
     requirement: &str,
+    no_cache: bool,
 ) -> Result<(SurveyResult, CodebaseMap), String> {
-    let map = load_or_generate_map(ctx, root)?;
+    let map = survey_map(ctx, root, no_cache).await?;
+    let survey = survey_codebase(ctx.llm.as_ref(), &map, requirement).await?;
+    Ok((survey, map))
+}
+
+/// Loads the cached codebase map for `root`, or generates it, for a survey.
+///
+/// The first half of [`broad_survey`]; with `no_cache`, the cache is never read.
+///
+/// # Errors
+///
+/// Returns an error if the commit cannot be read or map generation fails.
+pub async fn survey_map(
+    ctx: &ServiceContext,
+    root: &Path,
+    no_cache: bool,
+) -> Result<CodebaseMap, String> {
+    match cached_map(ctx, root, no_cache)? {
+        Some(map) => Ok(map),
+        None => generator::generate_async(ctx, root, None).await,
+    }
+}
 
-    let prompt = build_survey_prompt(&map, requirement);
+/// Asks `llm` to survey `map` for `requirement`.
+///
+/// The second half of [`broad_survey`], for callers that route the call
+/// through a caching client.
+///
+/// # Errors
+///
+/// Returns an error if the LLM call fails or its response cannot be parsed.
+pub async fn survey_codebase(
+    llm: &dyn LlmClient,
+    map: &CodebaseMap,
+    requirement: &str,
+) -> Result<SurveyResult, String> {
+    let prompt = build_survey_prompt(map, requirement);
     let request =
         CompletionRequest { model: "claude-sonnet-4-20250514".into(), prompt, max_tokens: 4096 };
 
     let response: CompletionResponse =
-        ctx.llm.complete(&request).await.map_err(|e| format!("LLM survey failed: {e}"))?;
+        llm.complete(&request).await.map_err(|e| format!("LLM survey failed: {e}"))?;
 
-    let survey = parse_survey_response(&response.text, &map)?;
-    Ok((survey, map))
+    parse_survey_response(&response.text, map)
 }
 
 /// Loads or generates the codebase map for the given project root.
@@ -83,11 +118,25 @@ pub async fn broad_survey(
 /// Returns an error if the git commit cannot be read, map generation fails, or
 /// the cache cannot be written.
 pub fn load_codebase_map(ctx: &ServiceContext, root: &Path) -> Result<CodebaseMap, String> {
-    load_or_generate_map(ctx, root)
+    match cached_map(ctx, root, false)? {
+        Some(map) => Ok(map),
+        None => generator::generate(ctx, root, None),
+    }
 }
 
-/// Loads a cached codebase map if the commit hash matches, otherwise generates a new one.
-fn load_or_generate_map(ctx: &ServiceContext, root: &Path) -> Result<CodebaseMap, String> {
+/// Loads the cached codebase map if its commit hash matches the current commit.
+///
+/// Returns `None` when the map must be regenerated; with `no_cache` the cache
+/// is never read.
+fn cached_map(
+    ctx: &ServiceContext,
+    root: &Path,
+    no_cache: bool,
+) -> Result<Option<CodebaseMap>, String> {
+    if no_cache {
+        return Ok(None);
+    }
+
     let current_commit =
         ctx.git.current_commit().map_err(|e| format!("failed to get current commit: {e}"))?;
 
@@ -96,13 +145,13 @@ fn load_or_generate_map(ctx: &ServiceContext, root: &Path) -> Result<CodebaseMap
         if let Ok(content) = ctx.fs.read_to_string(&cache_path) {
             if let Ok(cached_map) = serde_yaml::from_str::<CodebaseMap>(&content) {
                 if cached_map.commit_hash == current_commit {
-                    return Ok(cached_map);
+                    return Ok(Some(cached_map));
                 }
             }
         }
     }
 
-    generator::generate(ctx, root)
+    Ok(None)
 }
 
 /// Builds the LLM prompt for analyzing the codebase map against a requirement.
@@ -115,7 +164,11 @@ fn build_survey_prompt(map: &CodebaseMap, requirement: &str) -> String {
     for module in &map.modules {
         let _ = writeln!(prompt, "### {}", module.path);
         let _ = writeln!(prompt, "Public items: {}", module.public_items.join(", "));
-        let _ = writeln!(prompt, "Dependencies: {}\n", module.dependencies.join(", "));
+        let _ = writeln!(prompt, "Dependencies: {}", module.dependencies.join(", "));
+        if !module.external_deps.is_empty() {
+            let _ = writeln!(prompt, "External crates: {}", module.external_deps.join(", "));
+        }
+        prompt.push('\n');
     }
 
     let _ = write!(prompt, "## Requirement\n\n{requirement}\n\n");
@@ -251,6 +304,8 @@ fn keyword_prefix_match(keyword: &str, word: &str) -> bool {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
+
     use super::*;
     use crate::cassette::format::{Cassette, Interaction};
     use crate::context::ServiceContext;
@@ -355,17 +410,24 @@ mod tests {
                 input: json!({"path": "/project/src/map/generator.rs"}),
                 output: json!("use crate::map;\n\nfn helper() {}\n"),
             },
-            // fs.write — map output
             Interaction {
                 seq: 8,
                 port: "fs".into(),
+                method: "create_dir_all".into(),
+                input: json!({"path": "/project/.spec-cache"}),
+                output: json!(null),
+            },
+            // fs.write — map output
+            Interaction {
+                seq: 9,
+                port: "fs".into(),
                 method: "write".into(),
                 input: json!({"path": "/project/.spec-cache/codebase_map.yaml"}),
                 output: json!(null),
             },
             // --- LLM call for survey analysis ---
             Interaction {
-                seq: 9,
+                seq: 10,
                 port: "llm".into(),
                 method: "complete".into(),
                 input: json!({}),
@@ -390,7 +452,7 @@ mod tests {
         let ctx = ServiceContext::replaying(&cassette_path).unwrap();
 
         let (result, map) =
-            broad_survey(&ctx, Path::new("/project"), "Add authentication").await.unwrap();
+            broad_survey(&ctx, Path::new("/project"), "Add authentication", false).await.unwrap();
 
         assert_eq!(result.routing_table.len(), 2);
         assert!(result.routing_table.contains_key("src"));
@@ -421,9 +483,13 @@ mod tests {
                 path: "src/cached".into(),
                 public_items: vec!["fn cached_fn".into()],
                 dependencies: vec!["dep_a".into()],
+                external_deps: vec![],
+                locations: BTreeMap::new(),
+                crate_name: None,
             }],
             directory_tree: vec!["src/cached/mod.rs".into()],
             test_infrastructure: vec![],
+            unreadable: vec![],
         };
         let cached_yaml = serde_yaml::to_string(&cached_map).unwrap();
 
@@ -476,7 +542,7 @@ mod tests {
         let ctx = ServiceContext::replaying(&cassette_path).unwrap();
 
         let (result, map) =
-            broad_survey(&ctx, Path::new("/project"), "Some requirement").await.unwrap();
+            broad_survey(&ctx, Path::new("/project"), "Some requirement", false).await.unwrap();
 
         // Should use the cached map's module structure
         assert!(result.routing_table.contains_key("src/cached"));
@@ -489,6 +555,31 @@ mod tests {
         let _ = std::fs::remove_dir_all(&dir);
     }
 
+    #[tokio::test]
+    async fn broad_survey_no_cache_regenerates_map() {
+        let dir = std::env::temp_dir().join("speck_survey_test_no_cache");
+        std::fs::create_dir_all(&dir).unwrap();
+
+        // A valid cache exists for this commit, but with --no-cache neither the
+        // commit check nor the cache read happens: the cassette holds only the
+        // generator's interactions, so any cache access would fail the replay.
+        let interactions: Vec<Interaction> = make_survey_cassette_interactions()
+            .into_iter()
+            .filter(|i| !(i.seq == 0 || i.port == "fs" && i.method == "exists"))
+            .collect();
+        let cassette_path = write_cassette(&dir, "survey_no_cache", interactions);
+        let ctx = ServiceContext::replaying(&cassette_path).unwrap();
+
+        let (result, map) =
+            broad_survey(&ctx, Path::new("/project"), "Add authentication", true).await.unwrap();
+
+        assert_eq!(map.commit_hash, "abc123def");
+        assert!(map.modules.iter().any(|m| m.path == "src/map"));
+        assert!(result.routing_table.contains_key("src/map"));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
     #[test]
     fn parse_survey_response_parses_valid_json() {
         let map = CodebaseMap {
@@ -498,9 +589,13 @@ mod tests {
                 path: "src".into(),
                 public_items: vec![],
                 dependencies: vec!["ports".into()],
+                external_deps: vec![],
+                locations: BTreeMap::new(),
+                crate_name: None,
             }],
             directory_tree: vec![],
             test_infrastructure: vec![],
+            unreadable: vec![],
         };
 
         let response = serde_json::to_string(&json!({
@@ -525,6 +620,7 @@ mod tests {
             modules: vec![],
             directory_tree: vec![],
             test_infrastructure: vec![],
+            unreadable: vec![],
         };
         let result = parse_survey_response("not json", &map);
         assert!(result.is_err());
@@ -540,9 +636,13 @@ mod tests {
                 path: "src/auth".into(),
                 public_items: vec!["fn login".into()],
                 dependencies: vec!["db".into()],
+                external_deps: vec![],
+                locations: BTreeMap::new(),
+                crate_name: None,
             }],
             directory_tree: vec![],
             test_infrastructure: vec![],
+            unreadable: vec![],
         };
 
         let prompt = build_survey_prompt(&map, "Add OAuth support");
@@ -560,11 +660,17 @@ mod tests {
                 path: "src/db".into(),
                 public_items: vec!["fn query".into(), "fn migrate".into()],
                 dependencies: vec![],
+                external_deps: vec![],
+                locations: BTreeMap::new(),
+                crate_name: None,
             },
             crate::map::ModuleSummary {
                 path: "src/hooks".into(),
                 public_items: vec!["fn execute".into()],
                 dependencies: vec![],
+                external_deps: vec![],
+                locations: BTreeMap::new(),
+                crate_name: None,
             },
         ];
 
@@ -592,6 +698,9 @@ mod tests {
             path: "src/auth".into(),
             public_items: vec!["fn login".into()],
             dependencies: vec![],
+            external_deps: vec![],
+            locations: BTreeMap::new(),
+            crate_name: None,
         }];
 
         let gaps = vec!["Notification system".into(), "Caching layer".into()];
@@ -612,15 +721,22 @@ mod tests {
                     path: "src/db".into(),
                     public_items: vec!["fn query".into(), "fn migrate".into()],
                     dependencies: vec![],
+                    external_deps: vec![],
+                    locations: BTreeMap::new(),
+                    crate_name: None,
                 },
                 crate::map::ModuleSummary {
                     path: "src/notifications".into(),
                     public_items: vec!["fn send".into()],
                     dependencies: vec![],
+                    external_deps: vec![],
+                    locations: BTreeMap::new(),
+                    crate_name: None,
                 },
             ],
             directory_tree: vec![],
             test_infrastructure: vec![],
+            unreadable: vec![],
         };
 
         let response = serde_json::to_string(&json!({
@@ -1,5 +1,7 @@
 //! Clock port for obtaining the current time.
 
+use std::time::Duration;
+
 use chrono::{DateTime, Utc};
 
 /// Provides the current time.
@@ -9,4 +11,18 @@ use chrono::{DateTime, Utc};
 pub trait Clock: Send + Sync {
     /// Returns the current UTC time.
     fn now(&self) -> DateTime<Utc>;
+
+    /// Returns a monotonic reading: time elapsed since an arbitrary fixed origin.
+    ///
+    /// Unlike [`now`](Self::now), readings never go backwards, so the
+    /// difference between two of them measures elapsed wall-clock time.
+    fn monotonic(&self) -> Duration;
+
+    /// Blocks the current thread for `duration`.
+    ///
+    /// Replaying clocks return immediately so replays stay fast and
+    /// deterministic.
+    fn sleep(&self, duration: Duration) {
+        std::thread::sleep(duration);
+    }
 }
@@ -0,0 +1,11 @@
+//! Environment port for reading process environment variables.
+
+/// Reads environment variables.
+///
+/// Abstracting the environment allows deterministic replay of values that
+/// are only known at runtime (versions, hosts, credentials-free settings).
+pub trait Environment: Send + Sync {
+    /// Returns the value of the named variable, or `None` if it is unset
+    /// or not valid Unicode.
+    fn var(&self, name: &str) -> Option<String>;
+}
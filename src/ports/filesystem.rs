@@ -2,6 +2,20 @@
 
 use std::path::Path;
 
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Size and modification time of a filesystem entry.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileMetadata {
+    /// Size in bytes (0 for directories on some platforms).
+    pub len: u64,
+    /// Last modification time.
+    pub modified: DateTime<Utc>,
+    /// Whether the entry is a directory.
+    pub is_dir: bool,
+}
+
 /// Provides filesystem access for reading and writing files.
 ///
 /// Abstracting the filesystem allows deterministic replay and testing
@@ -19,6 +33,9 @@ pub trait FileSystem: Send + Sync {
 
     /// Writes the given contents to a file, creating or overwriting it.
     ///
+    /// Callers should create the parent directory with
+    /// [`create_dir_all`](Self::create_dir_all) first.
+    ///
     /// # Errors
     ///
     /// Returns an error if the write fails (permissions, disk full, etc.).
@@ -28,6 +45,55 @@ pub trait FileSystem: Send + Sync {
         contents: &str,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
 
+    /// Creates a directory and any missing parents.
+    ///
+    /// Succeeds without change if the directory already exists.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a component exists as a file or cannot be created.
+    fn create_dir_all(&self, path: &Path) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+
+    /// Moves a file from `from` to `to`, replacing `to` if it exists.
+    ///
+    /// On a single filesystem this is atomic: readers see either the old
+    /// or the new contents of `to`, never a partial write. The parent
+    /// directory of `to` must already exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `from` does not exist or the rename fails.
+    fn rename(
+        &self,
+        from: &Path,
+        to: &Path,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+
+    /// Removes a file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file does not exist or cannot be removed.
+    fn remove_file(&self, path: &Path) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+
+    /// Removes an empty directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory does not exist, is not empty, or
+    /// cannot be removed.
+    fn remove_dir(&self, path: &Path) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+
+    /// Returns the size and modification time of a file or directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the path does not exist or cannot be inspected.
+    fn metadata(
+        &self,
+        path: &Path,
+    ) -> Result<FileMetadata, Box<dyn std::error::Error + Send + Sync>>;
+
     /// Returns `true` if the path exists on the filesystem.
     fn exists(&self, path: &Path) -> bool;
 
@@ -41,3 +107,55 @@ pub trait FileSystem: Send + Sync {
         path: &Path,
     ) -> Result<Vec<String>, Box<dyn std::error::Error + Send + Sync>>;
 }
+
+/// Boxed future returned by [`AsyncFileSystem`] methods.
+pub type FsFuture<'a, T> = std::pin::Pin<
+    Box<
+        dyn std::future::Future<Output = Result<T, Box<dyn std::error::Error + Send + Sync>>>
+            + Send
+            + 'a,
+    >,
+>;
+
+/// Asynchronous counterpart of [`FileSystem`] for I/O-heavy async call sites.
+///
+/// Lets callers such as the async map generator issue many reads at once.
+/// Recordings use the same `fs` port and method names as [`FileSystem`].
+/// Replay matches each call to an interaction recorded for the same path, so
+/// a cassette recorded through [`FileSystem`] also replays through this trait.
+/// The reverse does not hold: concurrent reads are recorded as they complete,
+/// while [`FileSystem`] replays strictly in recorded order.
+pub trait AsyncFileSystem: Send + Sync {
+    /// Reads the entire contents of a file as a UTF-8 string.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file does not exist or is not valid UTF-8.
+    fn read_to_string<'a>(&'a self, path: &'a Path) -> FsFuture<'a, String>;
+
+    /// Writes the given contents to a file, creating or overwriting it.
+    ///
+    /// Callers should create the parent directory with
+    /// [`create_dir_all`](Self::create_dir_all) first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the write fails (permissions, disk full, etc.).
+    fn write<'a>(&'a self, path: &'a Path, contents: &'a str) -> FsFuture<'a, ()>;
+
+    /// Creates a directory and any missing parents.
+    ///
+    /// Succeeds without change if the directory already exists.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a component exists as a file or cannot be created.
+    fn create_dir_all<'a>(&'a self, path: &'a Path) -> FsFuture<'a, ()>;
+
+    /// Lists the entries in a directory, sorted by name.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the path is not a directory or cannot be read.
+    fn list_dir<'a>(&'a self, path: &'a Path) -> FsFuture<'a, Vec<String>>;
+}
@@ -2,6 +2,8 @@
 
 use std::path::Path;
 
+use chrono::{DateTime, Utc};
+
 /// Provides read access to a git repository.
 ///
 /// Abstracting git access allows deterministic replay and testing
@@ -21,6 +23,17 @@ pub trait GitRepo: Send + Sync {
     /// Returns an error if the diff cannot be computed.
     fn diff(&self) -> Result<String, Box<dyn std::error::Error + Send + Sync>>;
 
+    /// Returns when and by whom `path` was last changed, as
+    /// `(author timestamp, author name)` of the most recent commit touching it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the history cannot be read or no commit touches `path`.
+    fn last_modified(
+        &self,
+        path: &Path,
+    ) -> Result<(DateTime<Utc>, String), Box<dyn std::error::Error + Send + Sync>>;
+
     /// Lists all tracked files in the repository.
     ///
     /// # Errors
@@ -30,4 +43,36 @@ pub trait GitRepo: Send + Sync {
         &self,
         path: &Path,
     ) -> Result<Vec<String>, Box<dyn std::error::Error + Send + Sync>>;
+
+    /// Fetches `remote_ref` (such as `origin/main`) and returns the commit it points to.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the ref is not `<remote>/<branch>`, the fetch
+    /// fails, or the ref does not resolve to a commit.
+    fn fetch_remote_commit(
+        &self,
+        remote_ref: &str,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>>;
+
+    /// Lists every file in the tree of `commit`, relative to the repository root.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `commit` does not exist.
+    fn list_files_at(
+        &self,
+        commit: &str,
+    ) -> Result<Vec<String>, Box<dyn std::error::Error + Send + Sync>>;
+
+    /// Reads the contents of `path` as of `commit`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `commit` does not exist or has no file at `path`.
+    fn read_file_at(
+        &self,
+        commit: &str,
+        path: &Path,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>>;
 }
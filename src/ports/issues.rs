@@ -13,6 +13,24 @@ pub struct Issue {
     pub body: String,
     /// The current status (e.g. "open", "closed").
     pub status: String,
+    /// Labels (or components) attached to the issue, e.g. `area:auth`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<String>,
+}
+
+/// Tracker-independent issue status.
+///
+/// Adapters translate this into their backend's vocabulary (for example
+/// Beads' `in_progress`, or GitLab's `opened`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueStatus {
+    /// Not yet started.
+    Open,
+    /// Actively being worked on.
+    InProgress,
+    /// Done or abandoned.
+    Closed,
 }
 
 /// Manages issues in an external tracker.
@@ -20,7 +38,16 @@ pub struct Issue {
 /// Abstracting issue tracking allows deterministic replay and testing
 /// without touching a real issue tracker API.
 pub trait IssueTracker: Send + Sync {
-    /// Creates a new issue and returns it with its assigned ID.
+    /// Whether this tracker stores issue labels.
+    ///
+    /// Trackers without labels return `false` and ignore the `labels`
+    /// arguments below, so callers know not to expect them back.
+    fn supports_labels(&self) -> bool {
+        true
+    }
+
+    /// Creates a new issue with the given labels and returns it with its
+    /// assigned ID.
     ///
     /// # Errors
     ///
@@ -29,9 +56,28 @@ pub trait IssueTracker: Send + Sync {
         &self,
         title: &str,
         body: &str,
+        labels: &[String],
     ) -> Result<Issue, Box<dyn std::error::Error + Send + Sync>>;
 
-    /// Updates an existing issue's title, body, or status.
+    /// Creates several issues, returning them in the same order as `items`.
+    ///
+    /// Each item is a `(title, body, labels)` triple. Adapters backed by a
+    /// tracker with a bulk endpoint should override this; the default creates
+    /// issues one at a time via [`IssueTracker::create_issue`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the first creation error. Issues created before the failure
+    /// are not rolled back.
+    fn create_issues_batch(
+        &self,
+        items: &[(&str, &str, &[String])],
+    ) -> Result<Vec<Issue>, Box<dyn std::error::Error + Send + Sync>> {
+        items.iter().map(|(title, body, labels)| self.create_issue(title, body, labels)).collect()
+    }
+
+    /// Updates an existing issue's title, body, or status, and adds any of
+    /// `labels` it does not already have. Existing labels are never removed.
     ///
     /// # Errors
     ///
@@ -41,7 +87,8 @@ pub trait IssueTracker: Send + Sync {
         id: &str,
         title: Option<&str>,
         body: Option<&str>,
-        status: Option<&str>,
+        status: Option<IssueStatus>,
+        labels: &[String],
     ) -> Result<Issue, Box<dyn std::error::Error + Send + Sync>>;
 
     /// Lists issues, optionally filtered by status.
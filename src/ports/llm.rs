@@ -34,6 +34,16 @@ pub type CompletionFuture<'a> = std::pin::Pin<
     >,
 >;
 
+/// Boxed future type returned by [`LlmClient::embed`].
+pub type EmbeddingFuture<'a> = std::pin::Pin<
+    Box<
+        dyn std::future::Future<
+                Output = Result<Vec<Vec<f32>>, Box<dyn std::error::Error + Send + Sync>>,
+            > + Send
+            + 'a,
+    >,
+>;
+
 /// Sends completion requests to a language model.
 pub trait LlmClient: Send + Sync {
     /// Generates a completion for the given request.
@@ -42,4 +52,28 @@ pub trait LlmClient: Send + Sync {
     ///
     /// Returns an error if the request fails (network, auth, rate-limit, etc.).
     fn complete(&self, request: &CompletionRequest) -> CompletionFuture<'_>;
+
+    /// Embeds each text as a vector, returned in the same order as `texts`.
+    ///
+    /// The default reports embeddings as unsupported, so callers can fall
+    /// back to non-semantic strategies.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the backend has no embedding support or the
+    /// request fails.
+    fn embed(&self, _texts: &[String]) -> EmbeddingFuture<'_> {
+        Box::pin(async { Err("embeddings are not supported by this LLM client".into()) })
+    }
+}
+
+/// Lets middleware wrap a borrowed client, such as a context's `llm`.
+impl<T: LlmClient + ?Sized> LlmClient for &T {
+    fn complete(&self, request: &CompletionRequest) -> CompletionFuture<'_> {
+        (**self).complete(request)
+    }
+
+    fn embed(&self, texts: &[String]) -> EmbeddingFuture<'_> {
+        (**self).embed(texts)
+    }
 }
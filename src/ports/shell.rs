@@ -22,4 +22,20 @@ pub trait ShellExecutor: Send + Sync {
     ///
     /// Returns an error if the command cannot be spawned or fails to execute.
     fn run(&self, command: &str) -> Result<ShellOutput, Box<dyn std::error::Error + Send + Sync>>;
+
+    /// Runs a command whose output is read beyond its exit code.
+    ///
+    /// Behaves like [`run`](Self::run). Recorders keep these runs in full
+    /// even when recording minimally, since text elided from the middle of
+    /// the output could change a check's verdict on replay.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the command cannot be spawned or fails to execute.
+    fn run_inspected(
+        &self,
+        command: &str,
+    ) -> Result<ShellOutput, Box<dyn std::error::Error + Send + Sync>> {
+        self.run(command)
+    }
 }
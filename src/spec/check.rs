@@ -12,6 +12,15 @@ pub enum VerificationCheck {
         command: String,
         /// What to expect from the output.
         expected: String,
+        /// Extra attempts after a failure; the check passes if any attempt does.
+        #[serde(default, skip_serializing_if = "is_zero")]
+        retries: u8,
+        /// Wait before the first retry, doubling before each later one.
+        #[serde(default, skip_serializing_if = "is_zero")]
+        retry_backoff_ms: u64,
+        /// Stable name reported for this check instead of one derived from it.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        name: Option<String>,
     },
     /// Run a SQL query and assert on the result.
     SqlAssertion {
@@ -19,22 +28,98 @@ pub enum VerificationCheck {
         query: String,
         /// Expected result description.
         expected: String,
+        /// Stable name reported for this check instead of one derived from it.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        name: Option<String>,
     },
     /// Run a command and check its output.
+    ///
+    /// The check passes when the command exits with code 0. `expected`
+    /// describes the output for reports and is never matched against it,
+    /// whether written literally or with `${VAR}` placeholders; placeholders
+    /// are resolved from the environment, and an undefined variable fails
+    /// the check.
     CommandOutput {
         /// The command to run.
         command: String,
-        /// Expected output or assertion.
+        /// Description of the expected output, with optional `${VAR}` placeholders.
         expected: String,
+        /// Extra attempts after a failure; the check passes if any attempt does.
+        #[serde(default, skip_serializing_if = "is_zero")]
+        retries: u8,
+        /// Wait before the first retry, doubling before each later one.
+        #[serde(default, skip_serializing_if = "is_zero")]
+        retry_backoff_ms: u64,
+        /// Stable name reported for this check instead of one derived from it.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        name: Option<String>,
+    },
+    /// Run a coverage tool and assert a minimum reported percentage.
+    Coverage {
+        /// The coverage command to run.
+        command: String,
+        /// Minimum acceptable coverage percentage (0-100).
+        min_percent: f64,
+        /// Text preceding the percentage in the tool's output (e.g. `"TOTAL"`).
+        /// The percentage is searched for after it; defaults to all of stdout.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        marker: Option<String>,
+        /// Regex matching the percentage; its first capture group, or else the
+        /// whole match less any `%`, is the number. Defaults to `\d+(?:\.\d+)?%`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pattern: Option<String>,
+        /// Stable name reported for this check instead of one derived from it.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        name: Option<String>,
+    },
+    /// Run a command and compare its stdout with a golden file.
+    GoldenFile {
+        /// The command to run.
+        command: String,
+        /// Path of the file holding the expected stdout.
+        golden_path: String,
+        /// Stable name reported for this check instead of one derived from it.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        name: Option<String>,
     },
     /// Verify a migration can be rolled back.
     MigrationRollback {
         /// Description of the rollback check.
         description: String,
+        /// Stable name reported for this check instead of one derived from it.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        name: Option<String>,
     },
     /// A custom check with a freeform description.
     Custom {
         /// Description of the custom check.
         description: String,
+        /// Stable name reported for this check instead of one derived from it.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        name: Option<String>,
     },
 }
+
+impl VerificationCheck {
+    /// The stable name set on this check, if any.
+    ///
+    /// When present it replaces the name derived from the check's command or
+    /// description in results, so results correlate across command edits.
+    #[must_use]
+    pub fn name(&self) -> Option<&str> {
+        match self {
+            Self::TestSuite { name, .. }
+            | Self::SqlAssertion { name, .. }
+            | Self::CommandOutput { name, .. }
+            | Self::Coverage { name, .. }
+            | Self::GoldenFile { name, .. }
+            | Self::MigrationRollback { name, .. }
+            | Self::Custom { name, .. } => name.as_deref(),
+        }
+    }
+}
+
+/// Serde helper: omit retry settings left at their zero default.
+fn is_zero<T: Default + PartialEq>(value: &T) -> bool {
+    *value == T::default()
+}
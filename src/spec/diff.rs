@@ -0,0 +1,100 @@
+//! Structured differences between two versions of a task spec.
+
+use super::signal::SignalType;
+use super::task_spec::TaskSpec;
+
+/// What changed between two task specs.
+///
+/// Produced by [`TaskSpec::diff`]; `before` is the receiver and `after`
+/// the argument.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SpecDiff {
+    /// Old and new title, if the title changed.
+    pub title: Option<(String, String)>,
+    /// Acceptance criteria present only in the newer spec, in order.
+    pub criteria_added: Vec<String>,
+    /// Acceptance criteria present only in the older spec, in order.
+    pub criteria_removed: Vec<String>,
+    /// Old and new signal type, if the classification changed.
+    pub signal: Option<(SignalType, SignalType)>,
+    /// Whether the verification strategy differs in any way.
+    pub verification_changed: bool,
+}
+
+impl SpecDiff {
+    /// Returns `true` if none of the compared fields changed.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.title.is_none()
+            && self.criteria_added.is_empty()
+            && self.criteria_removed.is_empty()
+            && self.signal.is_none()
+            && !self.verification_changed
+    }
+}
+
+impl TaskSpec {
+    /// Compares this spec against a newer version of it.
+    ///
+    /// Covers the title, acceptance criteria, signal type, and verification
+    /// strategy. Identity and provenance fields are ignored.
+    #[must_use]
+    pub fn diff(&self, after: &TaskSpec) -> SpecDiff {
+        let title = (self.title != after.title).then(|| (self.title.clone(), after.title.clone()));
+        let criteria_added = after
+            .acceptance_criteria
+            .iter()
+            .filter(|c| !self.acceptance_criteria.contains(c))
+            .cloned()
+            .collect();
+        let criteria_removed = self
+            .acceptance_criteria
+            .iter()
+            .filter(|c| !after.acceptance_criteria.contains(c))
+            .cloned()
+            .collect();
+        let signal = (self.signal_type != after.signal_type)
+            .then(|| (self.signal_type.clone(), after.signal_type.clone()));
+
+        SpecDiff {
+            title,
+            criteria_added,
+            criteria_removed,
+            signal,
+            verification_changed: self.verification != after.verification,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::spec::{SignalType, TaskSpec};
+
+    fn spec(criteria: &[&str], signal: SignalType) -> TaskSpec {
+        TaskSpec {
+            acceptance_criteria: criteria.iter().map(|c| (*c).to_string()).collect(),
+            signal_type: signal,
+            ..TaskSpec::for_test("TASK-1", "Export CSV")
+        }
+    }
+
+    #[test]
+    fn identical_specs_have_empty_diff() {
+        let a = spec(&["works"], SignalType::Clear);
+        assert!(a.diff(&a.clone()).is_empty());
+    }
+
+    #[test]
+    fn diff_reports_criteria_and_signal_changes() {
+        let before = spec(&["exports rows", "feels fast"], SignalType::Fuzzy);
+        let mut after = spec(&["exports rows", "finishes under 2s"], SignalType::Clear);
+        after.title = "Export CSV quickly".to_string();
+
+        let diff = before.diff(&after);
+        assert_eq!(diff.title, Some(("Export CSV".to_string(), "Export CSV quickly".to_string())));
+        assert_eq!(diff.criteria_added, ["finishes under 2s"]);
+        assert_eq!(diff.criteria_removed, ["feels fast"]);
+        assert_eq!(diff.signal, Some((SignalType::Fuzzy, SignalType::Clear)));
+        assert!(!diff.verification_changed);
+    }
+}
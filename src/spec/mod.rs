@@ -4,11 +4,13 @@
 //! These are serialized/deserialized by the store and consumed by validate.
 
 mod check;
+mod diff;
 mod signal;
 mod task_spec;
 mod verification;
 
 pub use check::VerificationCheck;
+pub use diff::SpecDiff;
 pub use signal::SignalType;
 pub use task_spec::{TaskContext, TaskSpec};
-pub use verification::VerificationStrategy;
+pub use verification::{CheckGroup, VerificationStrategy};
@@ -1,5 +1,6 @@
 //! Core task spec type.
 
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 use super::signal::SignalType;
@@ -42,4 +43,73 @@ pub struct TaskSpec {
     /// `None` means globs have not been derived yet (distinct from empty vec which means "affects nothing").
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub affected_globs: Option<Vec<String>>,
+    /// When the spec was first created by `speck plan`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<DateTime<Utc>>,
+    /// The git commit the spec was first planned against.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_commit: Option<String>,
+    /// Shelved: left out of `validate --all`, drift, and sync unless
+    /// `--include-disabled` is given, and marked "(disabled)" in listings.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub disabled: bool,
+}
+
+impl TaskSpec {
+    /// The title, suffixed with "(disabled)" when the spec is shelved.
+    #[must_use]
+    pub fn display_title(&self) -> String {
+        if self.disabled {
+            format!("{} (disabled)", self.title)
+        } else {
+            self.title.clone()
+        }
+    }
+
+    /// Sorts and de-duplicates `context.dependencies`, dropping self-references.
+    ///
+    /// Returns `true` if a self-reference was removed.
+    pub fn normalize_dependencies(&mut self) -> bool {
+        let Some(context) = self.context.as_mut() else {
+            return false;
+        };
+        let before = context.dependencies.len();
+        context.dependencies.retain(|dep| *dep != self.id);
+        let had_self_reference = context.dependencies.len() != before;
+        context.dependencies.sort();
+        context.dependencies.dedup();
+        had_self_reference
+    }
+}
+
+#[cfg(test)]
+impl TaskSpec {
+    /// A spec with only `id` and `title` set, for tests to fill in with
+    /// struct update syntax: no context, criteria, or checks.
+    pub(crate) fn for_test(id: &str, title: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            title: title.to_string(),
+            requirement: None,
+            context: None,
+            acceptance_criteria: vec![],
+            signal_type: SignalType::Clear,
+            verification: VerificationStrategy::DirectAssertion {
+                checks: vec![],
+                groups: vec![],
+                setup: vec![],
+                teardown: vec![],
+            },
+            affected_globs: None,
+            created_at: None,
+            created_commit: None,
+            disabled: false,
+        }
+    }
+}
+
+/// Serde helper: omit `disabled` unless set.
+#[allow(clippy::trivially_copy_pass_by_ref)]
+fn is_false(value: &bool) -> bool {
+    !*value
 }
@@ -4,14 +4,38 @@ use serde::{Deserialize, Serialize};
 
 use super::check::VerificationCheck;
 
+/// A labelled set of related checks, such as "API behavior" or "migration safety".
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CheckGroup {
+    /// Heading shown above this group's results.
+    pub label: String,
+    /// The checks in this group.
+    pub checks: Vec<VerificationCheck>,
+}
+
 /// How to verify that a task's acceptance criteria are met.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(tag = "strategy", rename_all = "snake_case")]
 pub enum VerificationStrategy {
     /// Direct assertions via checks (tests, SQL, commands).
     DirectAssertion {
-        /// The list of checks to run.
+        /// Ungrouped checks to run.
+        #[serde(default)]
         checks: Vec<VerificationCheck>,
+        /// Labelled groups of checks, run after the ungrouped ones.
+        ///
+        /// Specs written before grouping existed have no `groups` key and
+        /// load with an empty list, so their flat `checks` keep working.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        groups: Vec<CheckGroup>,
+        /// Commands run before any check, e.g. to start a database or server.
+        ///
+        /// If one fails, the remaining setup and all checks are skipped.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        setup: Vec<String>,
+        /// Commands run after the checks, even when setup or checks failed.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        teardown: Vec<String>,
     },
     /// Refactor internal logic to expose a decision point for testing.
     RefactorToExpose {
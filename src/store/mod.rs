@@ -8,13 +8,20 @@
 //!   ├── requirements/
 //!   ├── tasks/
 //!   └── history/
+//!       └── <id>/<YYYYMMDDTHHMMSSZ>.yaml
 //! ```
 
 use std::path::{Path, PathBuf};
 
+use chrono::{DateTime, NaiveDateTime, Utc};
+
 use crate::context::ServiceContext;
+use crate::error::SpeckError;
 use crate::spec::TaskSpec;
 
+/// Filename timestamp format for history snapshots (UTC, second precision).
+const HISTORY_TIMESTAMP_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
 /// Persistence layer for task specs and requirements.
 ///
 /// All I/O goes through `ctx.fs` so that the store works with live,
@@ -33,32 +40,108 @@ impl<'a> SpecStore<'a> {
 
     /// Saves a task spec as YAML in `<root>/tasks/<id>.yaml`.
     ///
+    /// Dependencies are sorted and de-duplicated, and self-references dropped.
+    ///
     /// # Errors
     ///
     /// Returns an error if serialization or file writing fails.
-    pub fn save_task_spec(&self, spec: &TaskSpec) -> Result<(), String> {
-        let yaml = serde_yaml::to_string(spec)
-            .map_err(|e| format!("Failed to serialize task spec {}: {e}", spec.id))?;
+    pub fn save_task_spec(&self, spec: &TaskSpec) -> Result<(), SpeckError> {
+        let spec = &normalized(spec.clone());
+        let yaml = serde_yaml::to_string(spec).map_err(|e| {
+            SpeckError::Parse(format!("Failed to serialize task spec {}: {e}", spec.id))
+        })?;
         let path = self.task_path(&spec.id);
+        self.ensure_dir(&self.root.join("tasks"))?;
         self.ctx
             .fs
             .write(&path, &yaml)
-            .map_err(|e| format!("Failed to write task spec {}: {e}", spec.id))
+            .map_err(|e| SpeckError::Io(format!("Failed to write task spec {}: {e}", spec.id)))
+    }
+
+    /// Saves several task specs so that either all of them or none persist.
+    ///
+    /// Every spec is first written to `<root>/.staging/`, then promoted into
+    /// `<root>/tasks/` by rename. If staging fails, nothing in `tasks/` is
+    /// touched. If a promotion fails, specs already promoted are restored
+    /// to their previous contents (or removed if they were new).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error describing the first failure; the store is left as
+    /// it was before the call.
+    pub fn save_many(&self, specs: &[TaskSpec]) -> Result<(), SpeckError> {
+        let fs = &self.ctx.fs;
+        let staging_dir = self.root.join(".staging");
+        self.ensure_dir(&staging_dir)?;
+        self.ensure_dir(&self.root.join("tasks"))?;
+
+        let mut staged: Vec<(PathBuf, PathBuf)> = Vec::with_capacity(specs.len());
+        let mut stage = || -> Result<(), SpeckError> {
+            for spec in specs {
+                let spec = &normalized(spec.clone());
+                let yaml = serde_yaml::to_string(spec).map_err(|e| {
+                    SpeckError::Parse(format!("Failed to serialize task spec {}: {e}", spec.id))
+                })?;
+                let staged_path = staging_dir.join(format!("{}.yaml", spec.id));
+                fs.write(&staged_path, &yaml).map_err(|e| {
+                    SpeckError::Io(format!("Failed to stage task spec {}: {e}", spec.id))
+                })?;
+                staged.push((staged_path, self.task_path(&spec.id)));
+            }
+            Ok(())
+        };
+        if let Err(e) = stage() {
+            for (staged_path, _) in &staged {
+                let _ = fs.remove_file(staged_path);
+            }
+            return Err(e);
+        }
+
+        // Snapshot previous contents so promotion can be undone.
+        let previous: Vec<Option<String>> =
+            staged.iter().map(|(_, target)| fs.read_to_string(target).ok()).collect();
+
+        for (i, (staged_path, target)) in staged.iter().enumerate() {
+            if let Err(e) = fs.rename(staged_path, target) {
+                for ((_, done), prev) in staged[..i].iter().zip(&previous) {
+                    let _ = match prev {
+                        Some(contents) => fs.write(done, contents),
+                        None => fs.remove_file(done),
+                    };
+                }
+                for (pending, _) in &staged[i..] {
+                    let _ = fs.remove_file(pending);
+                }
+                return Err(SpeckError::Store(format!(
+                    "Failed to promote task spec {}: {e}",
+                    specs[i].id
+                )));
+            }
+        }
+
+        Ok(())
     }
 
     /// Loads a task spec by ID from `<root>/tasks/<id>.yaml`.
     ///
+    /// Dependencies are sorted and de-duplicated, and self-references dropped.
+    ///
     /// # Errors
     ///
-    /// Returns an error if the file cannot be read or parsed.
-    pub fn load_task_spec(&self, id: &str) -> Result<TaskSpec, String> {
+    /// Returns [`SpeckError::NotFound`] if no spec file exists for `id`, and
+    /// an I/O or parse error if the file cannot be read or parsed.
+    pub fn load_task_spec(&self, id: &str) -> Result<TaskSpec, SpeckError> {
         let path = self.task_path(id);
-        let contents = self
-            .ctx
-            .fs
-            .read_to_string(&path)
-            .map_err(|e| format!("Failed to read task spec {id}: {e}"))?;
-        serde_yaml::from_str(&contents).map_err(|e| format!("Failed to parse task spec {id}: {e}"))
+        let contents = self.ctx.fs.read_to_string(&path).map_err(|e| {
+            if self.ctx.fs.exists(&path) {
+                SpeckError::Io(format!("Failed to read task spec {id}: {e}"))
+            } else {
+                SpeckError::NotFound(format!("Spec '{id}' not found in {}", self.root.display()))
+            }
+        })?;
+        serde_yaml::from_str(&contents)
+            .map(normalized)
+            .map_err(|e| SpeckError::Parse(format!("Failed to parse task spec {id}: {e}")))
     }
 
     /// Lists all task spec IDs in the store.
@@ -68,7 +151,7 @@ impl<'a> SpecStore<'a> {
     /// # Errors
     ///
     /// Returns an error if the tasks directory cannot be listed.
-    pub fn list_task_specs(&self) -> Result<Vec<String>, String> {
+    pub fn list_task_specs(&self) -> Result<Vec<String>, SpeckError> {
         let tasks_dir = self.root.join("tasks");
         if !self.ctx.fs.exists(&tasks_dir) {
             return Ok(Vec::new());
@@ -77,7 +160,7 @@ impl<'a> SpecStore<'a> {
             .ctx
             .fs
             .list_dir(&tasks_dir)
-            .map_err(|e| format!("Failed to list tasks directory: {e}"))?;
+            .map_err(|e| SpeckError::Io(format!("Failed to list tasks directory: {e}")))?;
         Ok(entries
             .into_iter()
             .filter_map(|name| name.strip_suffix(".yaml").map(String::from))
@@ -89,107 +172,154 @@ impl<'a> SpecStore<'a> {
     /// # Errors
     ///
     /// Returns an error if file writing fails.
-    pub fn save_requirement(&self, id: &str, content: &str) -> Result<(), String> {
-        let path = self.root.join("requirements").join(format!("{id}.yaml"));
+    pub fn save_requirement(&self, id: &str, content: &str) -> Result<(), SpeckError> {
+        let dir = self.root.join("requirements");
+        self.ensure_dir(&dir)?;
+        let path = dir.join(format!("{id}.yaml"));
         self.ctx
             .fs
             .write(&path, content)
-            .map_err(|e| format!("Failed to write requirement {id}: {e}"))
+            .map_err(|e| SpeckError::Io(format!("Failed to write requirement {id}: {e}")))
     }
 
-    fn task_path(&self, id: &str) -> PathBuf {
-        self.root.join("tasks").join(format!("{id}.yaml"))
+    /// Whether a requirement document has been saved under `id`.
+    #[must_use]
+    pub fn has_requirement(&self, id: &str) -> bool {
+        self.ctx.fs.exists(&self.root.join("requirements").join(format!("{id}.yaml")))
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::spec::{SignalType, VerificationCheck, VerificationStrategy};
 
-    /// In-memory filesystem for testing the store without touching disk.
-    struct MemFs {
-        files: std::sync::Mutex<std::collections::HashMap<PathBuf, String>>,
+    /// Saves `spec` as a history snapshot taken at `at`.
+    ///
+    /// Snapshots live in `<root>/history/<id>/` and are named by timestamp,
+    /// so a second snapshot within the same second replaces the first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization or file writing fails.
+    pub fn save_history_snapshot(
+        &self,
+        spec: &TaskSpec,
+        at: DateTime<Utc>,
+    ) -> Result<(), SpeckError> {
+        let yaml = serde_yaml::to_string(spec).map_err(|e| {
+            SpeckError::Parse(format!("Failed to serialize task spec {}: {e}", spec.id))
+        })?;
+        self.ensure_dir(&self.root.join("history").join(&spec.id))?;
+        self.ctx.fs.write(&self.history_path(&spec.id, at), &yaml).map_err(|e| {
+            SpeckError::Io(format!("Failed to write history snapshot for {}: {e}", spec.id))
+        })
     }
 
-    impl MemFs {
-        fn new() -> Self {
-            Self { files: std::sync::Mutex::new(std::collections::HashMap::new()) }
+    /// Lists the timestamps of all history snapshots for a spec, oldest first.
+    ///
+    /// Files in the history directory whose names are not snapshot
+    /// timestamps are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the history directory cannot be listed.
+    pub fn list_history(&self, id: &str) -> Result<Vec<DateTime<Utc>>, SpeckError> {
+        let dir = self.root.join("history").join(id);
+        if !self.ctx.fs.exists(&dir) {
+            return Ok(Vec::new());
         }
+        let entries = self
+            .ctx
+            .fs
+            .list_dir(&dir)
+            .map_err(|e| SpeckError::Io(format!("Failed to list history for {id}: {e}")))?;
+        let mut stamps: Vec<DateTime<Utc>> = entries
+            .iter()
+            .filter_map(|name| name.strip_suffix(".yaml"))
+            .filter_map(|stem| NaiveDateTime::parse_from_str(stem, HISTORY_TIMESTAMP_FORMAT).ok())
+            .map(|naive| naive.and_utc())
+            .collect();
+        stamps.sort();
+        Ok(stamps)
     }
 
-    impl crate::ports::filesystem::FileSystem for MemFs {
-        fn read_to_string(
-            &self,
-            path: &Path,
-        ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
-            let files = self.files.lock().unwrap();
-            files
-                .get(path)
-                .cloned()
-                .ok_or_else(|| format!("File not found: {}", path.display()).into())
-        }
+    /// Loads the history snapshot of a spec taken at `at`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no snapshot exists at that time or it cannot be parsed.
+    pub fn load_history_snapshot(
+        &self,
+        id: &str,
+        at: DateTime<Utc>,
+    ) -> Result<TaskSpec, SpeckError> {
+        let stamp = at.format(HISTORY_TIMESTAMP_FORMAT);
+        let path = self.history_path(id, at);
+        let contents = self.ctx.fs.read_to_string(&path).map_err(|e| {
+            if self.ctx.fs.exists(&path) {
+                SpeckError::Io(format!("Failed to read history snapshot {id}@{stamp}: {e}"))
+            } else {
+                SpeckError::NotFound(format!("No history snapshot of {id} at {stamp}"))
+            }
+        })?;
+        serde_yaml::from_str(&contents).map_err(|e| {
+            SpeckError::Parse(format!("Failed to parse history snapshot {id}@{stamp}: {e}"))
+        })
+    }
 
-        fn write(
-            &self,
-            path: &Path,
-            contents: &str,
-        ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-            let mut files = self.files.lock().unwrap();
-            files.insert(path.to_path_buf(), contents.to_string());
-            Ok(())
-        }
+    fn history_path(&self, id: &str, at: DateTime<Utc>) -> PathBuf {
+        self.root
+            .join("history")
+            .join(id)
+            .join(format!("{}.yaml", at.format(HISTORY_TIMESTAMP_FORMAT)))
+    }
 
-        fn exists(&self, path: &Path) -> bool {
-            let files = self.files.lock().unwrap();
-            // Check exact path or if any file is "under" this directory.
-            files.contains_key(path) || files.keys().any(|k| k.starts_with(path) && k != path)
-        }
+    fn task_path(&self, id: &str) -> PathBuf {
+        self.root.join("tasks").join(format!("{id}.yaml"))
+    }
 
-        fn list_dir(
-            &self,
-            path: &Path,
-        ) -> Result<Vec<String>, Box<dyn std::error::Error + Send + Sync>> {
-            let files = self.files.lock().unwrap();
-            let mut names: Vec<String> = files
-                .keys()
-                .filter_map(|k| {
-                    if k.parent() == Some(path) {
-                        k.file_name().map(|n| n.to_string_lossy().into_owned())
-                    } else {
-                        None
-                    }
-                })
-                .collect();
-            names.sort();
-            Ok(names)
-        }
+    /// Creates `dir` (and its parents) before files are written into it.
+    fn ensure_dir(&self, dir: &Path) -> Result<(), SpeckError> {
+        self.ctx
+            .fs
+            .create_dir_all(dir)
+            .map_err(|e| SpeckError::Io(format!("Failed to create {}: {e}", dir.display())))
     }
+}
+
+/// Sorts and de-duplicates a spec's dependencies, dropping self-references.
+///
+/// A self-reference would otherwise read as a trivial dependency cycle, so
+/// one is reported on stderr when dropped.
+fn normalized(mut spec: TaskSpec) -> TaskSpec {
+    if spec.normalize_dependencies() {
+        eprintln!("Warning: spec {} listed itself as a dependency; ignoring it", spec.id);
+    }
+    spec
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::memory::filesystem::MemFileSystem as MemFs;
+    use crate::spec::{CheckGroup, VerificationCheck, VerificationStrategy};
 
     fn make_test_context(fs: MemFs) -> ServiceContext {
-        use crate::cassette::config::CassetteConfig;
-        // Start from panicking defaults, then replace fs.
-        let mut ctx = ServiceContext::replaying_from(&CassetteConfig::panic_on_unspecified())
-            .expect("panic config should always succeed");
-        ctx.fs = Box::new(fs);
-        ctx
+        ServiceContext::testing().with_fs(fs)
     }
 
     fn sample_spec(id: &str) -> TaskSpec {
         TaskSpec {
-            id: id.to_string(),
-            title: format!("Test task {id}"),
             requirement: Some("test-req".to_string()),
-            context: None,
             acceptance_criteria: vec!["it works".to_string()],
-            signal_type: SignalType::Clear,
             verification: VerificationStrategy::DirectAssertion {
                 checks: vec![VerificationCheck::TestSuite {
                     command: "cargo test".to_string(),
                     expected: "all pass".to_string(),
+                    retries: 0,
+                    retry_backoff_ms: 0,
+                    name: None,
                 }],
+                groups: vec![],
+                setup: vec![],
+                teardown: vec![],
             },
-            affected_globs: None,
+            ..TaskSpec::for_test(id, &format!("Test task {id}"))
         }
     }
 
@@ -206,6 +336,151 @@ mod tests {
         assert_eq!(spec, loaded);
     }
 
+    #[test]
+    fn load_errors_distinguish_missing_from_malformed() {
+        let fs = MemFs::new();
+        let files = fs.files();
+        let ctx = make_test_context(fs);
+        let store = SpecStore::new(&ctx, Path::new("/store"));
+
+        let missing = store.load_task_spec("NOPE").unwrap_err();
+        assert!(matches!(missing, SpeckError::NotFound(_)), "{missing:?}");
+
+        files.lock().unwrap().insert(PathBuf::from("/store/tasks/BAD.yaml"), "id: [".to_string());
+        let malformed = store.load_task_spec("BAD").unwrap_err();
+        assert!(matches!(malformed, SpeckError::Parse(_)), "{malformed:?}");
+        assert!(malformed.to_string().starts_with("Failed to parse task spec BAD:"));
+    }
+
+    #[test]
+    fn load_normalizes_dependencies() {
+        let fs = MemFs::new();
+        let files = fs.files();
+        let ctx = make_test_context(fs);
+        let store = SpecStore::new(&ctx, Path::new("/store"));
+
+        let mut spec = sample_spec("TASK-D");
+        spec.context = Some(crate::spec::TaskContext {
+            modules: vec![],
+            patterns: None,
+            dependencies: vec!["TASK-C".into(), "TASK-D".into(), "TASK-A".into(), "TASK-C".into()],
+        });
+        let yaml = serde_yaml::to_string(&spec).unwrap();
+        files.lock().unwrap().insert(PathBuf::from("/store/tasks/TASK-D.yaml"), yaml);
+
+        let loaded = store.load_task_spec("TASK-D").unwrap();
+        assert_eq!(loaded.context.as_ref().unwrap().dependencies, ["TASK-A", "TASK-C"]);
+        assert!(crate::plan::reconcile::detect_circular_dependencies(&[loaded]).is_empty());
+    }
+
+    #[test]
+    fn coverage_check_round_trips() {
+        let fs = MemFs::new();
+        let ctx = make_test_context(fs);
+        let store = SpecStore::new(&ctx, Path::new("/store"));
+
+        let mut spec = sample_spec("TASK-COV");
+        spec.verification = VerificationStrategy::DirectAssertion {
+            checks: vec![VerificationCheck::Coverage {
+                command: "cargo llvm-cov".to_string(),
+                min_percent: 80.5,
+                marker: Some("TOTAL".to_string()),
+                pattern: Some(r"(\d+)% total".to_string()),
+                name: None,
+            }],
+            groups: vec![],
+            setup: vec![],
+            teardown: vec![],
+        };
+        store.save_task_spec(&spec).unwrap();
+
+        let yaml = ctx.fs.read_to_string(Path::new("/store/tasks/TASK-COV.yaml")).unwrap();
+        assert!(yaml.contains("type: coverage"));
+        assert!(yaml.contains("min_percent: 80.5"));
+        assert_eq!(store.load_task_spec("TASK-COV").unwrap(), spec);
+    }
+
+    #[test]
+    fn flat_checks_spec_loads_with_no_groups() {
+        let fs = MemFs::new();
+        let ctx = make_test_context(fs);
+        let yaml = r"id: OLD-1
+title: Legacy
+context:
+  modules: []
+acceptance_criteria: []
+signal_type: clear
+verification:
+  strategy: direct_assertion
+  checks:
+  - type: test_suite
+    command: cargo test
+    expected: all pass
+";
+        ctx.fs.write(Path::new("/store/tasks/OLD-1.yaml"), yaml).unwrap();
+        let store = SpecStore::new(&ctx, Path::new("/store"));
+
+        let spec = store.load_task_spec("OLD-1").unwrap();
+        let VerificationStrategy::DirectAssertion { checks, groups, setup, .. } =
+            &spec.verification
+        else {
+            panic!("expected DirectAssertion, got {:?}", spec.verification);
+        };
+        assert_eq!(checks.len(), 1);
+        assert!(groups.is_empty());
+        assert!(setup.is_empty());
+
+        store.save_task_spec(&spec).unwrap();
+        let saved = ctx.fs.read_to_string(Path::new("/store/tasks/OLD-1.yaml")).unwrap();
+        assert!(!saved.contains("groups"));
+        assert!(!saved.contains("setup"));
+    }
+
+    #[test]
+    fn grouped_checks_round_trip() {
+        let fs = MemFs::new();
+        let ctx = make_test_context(fs);
+        let store = SpecStore::new(&ctx, Path::new("/store"));
+
+        let mut spec = sample_spec("TASK-GRP");
+        spec.verification = VerificationStrategy::DirectAssertion {
+            checks: vec![],
+            groups: vec![CheckGroup {
+                label: "API behavior".to_string(),
+                checks: vec![VerificationCheck::Custom {
+                    description: "returns 201".to_string(),
+                    name: None,
+                }],
+            }],
+            setup: vec![],
+            teardown: vec![],
+        };
+        store.save_task_spec(&spec).unwrap();
+
+        let yaml = ctx.fs.read_to_string(Path::new("/store/tasks/TASK-GRP.yaml")).unwrap();
+        assert!(yaml.contains("label: API behavior"));
+        assert_eq!(store.load_task_spec("TASK-GRP").unwrap(), spec);
+    }
+
+    #[test]
+    fn list_history_returns_snapshots_oldest_first() {
+        let fs = MemFs::new();
+        let ctx = make_test_context(fs);
+        let store = SpecStore::new(&ctx, Path::new("/store"));
+        let t1 = "2026-03-01T09:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let t2 = "2026-03-02T17:30:15Z".parse::<DateTime<Utc>>().unwrap();
+
+        let mut spec = sample_spec("TASK-H");
+        store.save_history_snapshot(&spec, t2).unwrap();
+        spec.title = "Original title".to_string();
+        store.save_history_snapshot(&spec, t1).unwrap();
+        ctx.fs.write(Path::new("/store/history/TASK-H/notes.txt"), "ignored").unwrap();
+
+        assert_eq!(store.list_history("TASK-H").unwrap(), [t1, t2]);
+        assert_eq!(store.load_history_snapshot("TASK-H", t1).unwrap().title, "Original title");
+        assert!(store.list_history("MISSING").unwrap().is_empty());
+    }
+
     #[test]
     fn list_task_specs_returns_all_saved() {
         let fs = MemFs::new();
@@ -231,6 +506,19 @@ mod tests {
         assert!(ids.is_empty());
     }
 
+    #[test]
+    fn save_into_fresh_store_creates_tasks_dir() {
+        let fs = MemFs::new();
+        let dirs = fs.dirs();
+        let ctx = make_test_context(fs);
+        let store = SpecStore::new(&ctx, Path::new("/fresh"));
+
+        store.save_task_spec(&sample_spec("TASK-D")).unwrap();
+
+        assert!(dirs.lock().unwrap().contains(Path::new("/fresh/tasks")));
+        assert_eq!(store.list_task_specs().unwrap(), vec!["TASK-D"]);
+    }
+
     #[test]
     fn save_requirement() {
         let fs = MemFs::new();
@@ -243,4 +531,42 @@ mod tests {
         let content = ctx.fs.read_to_string(Path::new("/store/requirements/req-1.yaml")).unwrap();
         assert!(content.contains("My Requirement"));
     }
+
+    #[test]
+    fn save_many_persists_all_specs() {
+        let fs = MemFs::new();
+        let ctx = make_test_context(fs);
+        let store = SpecStore::new(&ctx, Path::new("/store"));
+
+        let specs = vec![sample_spec("A"), sample_spec("B"), sample_spec("C")];
+        store.save_many(&specs).unwrap();
+
+        let mut ids = store.list_task_specs().unwrap();
+        ids.sort();
+        assert_eq!(ids, vec!["A", "B", "C"]);
+        assert!(!ctx.fs.exists(Path::new("/store/.staging/A.yaml")));
+    }
+
+    #[test]
+    fn save_many_rolls_back_on_third_write_failure() {
+        let fs = MemFs::failing_writes_to("THIRD.yaml");
+        let files = fs.files();
+        let ctx = make_test_context(fs);
+        let store = SpecStore::new(&ctx, Path::new("/store"));
+
+        let mut original = sample_spec("FIRST");
+        original.title = "Original".to_string();
+        store.save_task_spec(&original).unwrap();
+
+        let mut updated = sample_spec("FIRST");
+        updated.title = "Updated".to_string();
+        let batch = vec![updated, sample_spec("SECOND"), sample_spec("THIRD")];
+
+        let err = store.save_many(&batch).unwrap_err();
+
+        assert!(matches!(err, SpeckError::Io(ref msg) if msg.contains("THIRD")), "{err}");
+        assert_eq!(store.list_task_specs().unwrap(), vec!["FIRST"]);
+        assert_eq!(store.load_task_spec("FIRST").unwrap().title, "Original");
+        assert_eq!(files.lock().unwrap().len(), 1, "staging files should be cleaned up");
+    }
 }
@@ -3,6 +3,7 @@
 //! Idempotent: re-running does not create duplicates.  Issues are matched
 //! to specs by looking for the spec ID prefix (`[SPEC-ID]`) in the title.
 
+use std::collections::BTreeSet;
 use std::fmt::Write;
 
 use crate::context::ServiceContext;
@@ -98,6 +99,42 @@ fn issue_body(spec: &TaskSpec) -> String {
     body
 }
 
+/// Derives `area:<name>` tracker labels from a spec's modules and globs.
+///
+/// Path-like references use their first segment below `src/` (so
+/// `src/auth/**` becomes `area:auth`); abstract module names such as
+/// `AuthService` become `area:auth-service`. Files directly under `src/`
+/// yield no label. Labels are sorted and de-duplicated.
+fn issue_labels(spec: &TaskSpec) -> Vec<String> {
+    let modules = spec.context.iter().flat_map(|c| &c.modules);
+    let globs = spec.affected_globs.iter().flatten();
+    modules
+        .chain(globs)
+        .filter_map(|reference| area(reference))
+        .map(|area| format!("area:{area}"))
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+/// The area a module reference belongs to, if any.
+fn area(reference: &str) -> Option<String> {
+    if !reference.contains('/') {
+        let mut kebab = String::new();
+        for (i, c) in reference.chars().enumerate() {
+            if c.is_uppercase() && i > 0 {
+                kebab.push('-');
+            }
+            kebab.extend(c.to_lowercase());
+        }
+        return Some(kebab).filter(|k| !k.is_empty() && !k.contains(['*', '.']));
+    }
+    let mut segments = reference.split('/').filter(|s| !s.is_empty());
+    let first = segments.next()?;
+    let segment = if first == "src" { segments.next()? } else { first };
+    Some(segment.to_string()).filter(|s| !s.contains(['*', '.']))
+}
+
 /// Parses a [`TaskSpec`] from a bead issue body.
 ///
 /// The body must contain a `## Verification` section with a YAML fenced block
@@ -139,6 +176,9 @@ pub fn parse_spec_from_body(
         signal_type: SignalType::Clear,
         verification,
         affected_globs: None,
+        created_at: None,
+        created_commit: None,
+        disabled: false,
     }))
 }
 
@@ -185,15 +225,25 @@ fn find_matching_issue<'a>(spec_id: &str, issues: &'a [Issue]) -> Option<&'a Iss
 }
 
 /// Plans sync actions for a list of task specs against existing issues.
+///
+/// With `compare_labels` (the tracker supports labels), an issue missing any
+/// of its spec's derived area labels also needs an update. Extra labels on
+/// the issue are left alone.
 #[must_use]
-pub fn plan_sync(specs: &[TaskSpec], existing_issues: &[Issue]) -> Vec<SyncAction> {
+pub fn plan_sync(
+    specs: &[TaskSpec],
+    existing_issues: &[Issue],
+    compare_labels: bool,
+) -> Vec<SyncAction> {
     specs
         .iter()
         .map(|spec| {
             if let Some(existing) = find_matching_issue(&spec.id, existing_issues) {
                 let new_title = issue_title(spec);
                 let new_body = issue_body(spec);
-                if existing.title == new_title && existing.body == new_body {
+                let labels_present = !compare_labels
+                    || issue_labels(spec).iter().all(|label| existing.labels.contains(label));
+                if existing.title == new_title && existing.body == new_body && labels_present {
                     SyncAction::Unchanged {
                         spec_id: spec.id.clone(),
                         issue_id: existing.id.clone(),
@@ -217,6 +267,11 @@ pub fn plan_sync(specs: &[TaskSpec], existing_issues: &[Issue]) -> Vec<SyncActio
 /// Actions reference specs by ID; every action's `spec_id` **must** appear
 /// in `specs` (this is guaranteed when actions come from [`plan_sync`]).
 ///
+/// All `Create` actions are sent in a single
+/// [`create_issues_batch`](crate::ports::IssueTracker::create_issues_batch)
+/// call, after which updates are applied one by one. Each issue is given
+/// its spec's derived area labels, unless the tracker does not support labels.
+///
 /// # Errors
 ///
 /// Returns an error if any issue creation or update fails.
@@ -229,31 +284,40 @@ pub fn execute_sync(
     specs: &[TaskSpec],
     actions: &[SyncAction],
 ) -> Result<(), String> {
-    for action in actions {
-        match action {
+    let find_spec = |spec_id: &str| {
+        specs.iter().find(|s| s.id == spec_id).expect("action references unknown spec")
+    };
+    let labels_for =
+        |spec: &TaskSpec| if ctx.issues.supports_labels() { issue_labels(spec) } else { vec![] };
+
+    let creates: Vec<(String, String, Vec<String>)> = actions
+        .iter()
+        .filter_map(|action| match action {
             SyncAction::Create { spec_id, .. } => {
-                let spec = specs
-                    .iter()
-                    .find(|s| s.id == *spec_id)
-                    .expect("action references unknown spec");
-                let title = issue_title(spec);
-                let body = issue_body(spec);
-                ctx.issues
-                    .create_issue(&title, &body)
-                    .map_err(|e| format!("Failed to create issue for {spec_id}: {e}"))?;
-            }
-            SyncAction::Update { spec_id, issue_id, .. } => {
-                let spec = specs
-                    .iter()
-                    .find(|s| s.id == *spec_id)
-                    .expect("action references unknown spec");
-                let title = issue_title(spec);
-                let body = issue_body(spec);
-                ctx.issues
-                    .update_issue(issue_id, Some(&title), Some(&body), None)
-                    .map_err(|e| format!("Failed to update issue for {spec_id}: {e}"))?;
+                let spec = find_spec(spec_id);
+                Some((issue_title(spec), issue_body(spec), labels_for(spec)))
             }
-            SyncAction::Unchanged { .. } => {}
+            _ => None,
+        })
+        .collect();
+    if !creates.is_empty() {
+        let items: Vec<(&str, &str, &[String])> = creates
+            .iter()
+            .map(|(title, body, labels)| (title.as_str(), body.as_str(), labels.as_slice()))
+            .collect();
+        ctx.issues
+            .create_issues_batch(&items)
+            .map_err(|e| format!("Failed to create {} issue(s): {e}", items.len()))?;
+    }
+
+    for action in actions {
+        if let SyncAction::Update { spec_id, issue_id, .. } = action {
+            let spec = find_spec(spec_id);
+            let title = issue_title(spec);
+            let body = issue_body(spec);
+            ctx.issues
+                .update_issue(issue_id, Some(&title), Some(&body), None, &labels_for(spec))
+                .map_err(|e| format!("Failed to update issue for {spec_id}: {e}"))?;
         }
     }
     Ok(())
@@ -286,23 +350,24 @@ pub fn format_actions(actions: &[SyncAction]) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::spec::{SignalType, TaskContext, VerificationCheck, VerificationStrategy};
+    use crate::spec::{TaskContext, VerificationCheck, VerificationStrategy};
 
     fn sample_spec(id: &str, title: &str) -> TaskSpec {
         TaskSpec {
-            id: id.to_string(),
-            title: title.to_string(),
-            requirement: None,
-            context: None,
             acceptance_criteria: vec!["it works".to_string()],
-            signal_type: SignalType::Clear,
             verification: VerificationStrategy::DirectAssertion {
                 checks: vec![VerificationCheck::TestSuite {
                     command: "cargo test".to_string(),
                     expected: "pass".to_string(),
+                    retries: 0,
+                    retry_backoff_ms: 0,
+                    name: None,
                 }],
+                groups: vec![],
+                setup: vec![],
+                teardown: vec![],
             },
-            affected_globs: None,
+            ..TaskSpec::for_test(id, title)
         }
     }
 
@@ -319,33 +384,36 @@ mod tests {
 
     fn sample_spec_full() -> TaskSpec {
         TaskSpec {
-            id: "T-99".to_string(),
-            title: "Full spec".to_string(),
-            requirement: None,
             context: Some(TaskContext {
                 modules: vec!["MetricsService".to_string(), "AuthService".to_string()],
                 patterns: Some("Follow existing migration conventions".to_string()),
                 dependencies: vec!["T-1".to_string()],
             }),
             acceptance_criteria: vec!["it works".to_string()],
-            signal_type: SignalType::Clear,
             verification: VerificationStrategy::DirectAssertion {
                 checks: vec![VerificationCheck::TestSuite {
                     command: "cargo test".to_string(),
                     expected: "pass".to_string(),
+                    retries: 0,
+                    retry_backoff_ms: 0,
+                    name: None,
                 }],
+                groups: vec![],
+                setup: vec![],
+                teardown: vec![],
             },
             affected_globs: Some(vec![
                 "src/services/metrics/**".to_string(),
                 "src/lib.rs".to_string(),
             ]),
+            ..TaskSpec::for_test("T-99", "Full spec")
         }
     }
 
     #[test]
     fn plan_creates_for_new_specs() {
         let specs = vec![sample_spec("T-1", "First task")];
-        let actions = plan_sync(&specs, &[]);
+        let actions = plan_sync(&specs, &[], true);
         assert_eq!(actions.len(), 1);
         assert!(matches!(&actions[0], SyncAction::Create { spec_id, .. } if spec_id == "T-1"));
     }
@@ -358,8 +426,9 @@ mod tests {
             title: "[T-1] First task".to_string(),
             body: issue_body(&specs[0]),
             status: "open".to_string(),
+            labels: vec![],
         }];
-        let actions = plan_sync(&specs, &existing);
+        let actions = plan_sync(&specs, &existing, true);
         assert_eq!(actions.len(), 1);
         assert!(matches!(&actions[0], SyncAction::Unchanged { spec_id, .. } if spec_id == "T-1"));
     }
@@ -372,8 +441,9 @@ mod tests {
             title: "[T-1] Old title".to_string(),
             body: issue_body(&specs[0]),
             status: "open".to_string(),
+            labels: vec![],
         }];
-        let actions = plan_sync(&specs, &existing);
+        let actions = plan_sync(&specs, &existing, true);
         assert_eq!(actions.len(), 1);
         assert!(matches!(&actions[0], SyncAction::Update { spec_id, .. } if spec_id == "T-1"));
     }
@@ -386,8 +456,9 @@ mod tests {
             title: "[T-1] First task".to_string(),
             body: "old body".to_string(),
             status: "open".to_string(),
+            labels: vec![],
         }];
-        let actions = plan_sync(&specs, &existing);
+        let actions = plan_sync(&specs, &existing, true);
         assert_eq!(actions.len(), 1);
         assert!(matches!(&actions[0], SyncAction::Update { spec_id, .. } if spec_id == "T-1"));
     }
@@ -547,9 +618,105 @@ mod tests {
         assert!(output.contains("UNCHANGED T-3"));
     }
 
+    #[test]
+    fn execute_sync_batches_creates_from_recorded_cassette() {
+        use crate::cassette::format::{Cassette, Interaction};
+        use serde_json::json;
+
+        let interactions = ["A", "B", "C"]
+            .iter()
+            .enumerate()
+            .map(|(i, id)| Interaction {
+                seq: i as u64,
+                port: "issues".into(),
+                method: "create_issue".into(),
+                input: json!({}),
+                output: json!({"Ok": {
+                    "id": format!("bd-{id}"),
+                    "title": format!("[T-{id}] Task {id}"),
+                    "body": "",
+                    "status": "open"
+                }}),
+            })
+            .collect();
+        let cassette = Cassette {
+            name: "sync-batch".into(),
+            recorded_at: chrono::Utc::now(),
+            commit: "abc".into(),
+            interactions,
+        };
+        let dir = std::env::temp_dir().join("speck_sync_batch_create");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("sync_batch.cassette.yaml");
+        std::fs::write(&path, serde_yaml::to_string(&cassette).unwrap()).unwrap();
+
+        let ctx = ServiceContext::replaying(&path).unwrap();
+        let issues = ctx
+            .issues
+            .create_issues_batch(&[("[T-A] Task A", "", &[]), ("[T-B] Task B", "", &[])])
+            .unwrap();
+        let ids: Vec<&str> = issues.iter().map(|i| i.id.as_str()).collect();
+        assert_eq!(ids, ["bd-A", "bd-B"]);
+
+        let specs = vec![sample_spec("T-C", "Task C")];
+        let actions = plan_sync(&specs, &[], true);
+        let result = execute_sync(&ctx, &specs, &actions);
+
+        let _ = std::fs::remove_dir_all(&dir);
+        assert!(result.is_ok(), "{result:?}");
+    }
+
     #[test]
     fn format_actions_empty() {
         let output = format_actions(&[]);
         assert_eq!(output, "No specs to sync.");
     }
+
+    #[test]
+    fn execute_sync_sends_area_labels_and_resyncs_idempotently() {
+        let ctx = ServiceContext::testing();
+        let specs = vec![sample_spec_full()];
+        let expected = ["area:auth-service", "area:metrics-service", "area:services"];
+
+        let actions = plan_sync(&specs, &[], ctx.issues.supports_labels());
+        execute_sync(&ctx, &specs, &actions).unwrap();
+        let issues = ctx.issues.list_issues(None).unwrap();
+        assert_eq!(issues[0].labels, expected);
+
+        let actions = plan_sync(&specs, &issues, true);
+        assert!(matches!(&actions[0], SyncAction::Unchanged { .. }), "{actions:?}");
+
+        // An issue synced before labels existed gets them on the next sync.
+        let mut unlabeled = issues[0].clone();
+        unlabeled.labels.clear();
+        let actions = plan_sync(&specs, &[unlabeled.clone()], true);
+        assert!(matches!(&actions[0], SyncAction::Update { .. }), "{actions:?}");
+        let actions = plan_sync(&specs, &[unlabeled], false);
+        assert!(matches!(&actions[0], SyncAction::Unchanged { .. }), "{actions:?}");
+    }
+
+    #[test]
+    fn execute_sync_skips_labels_for_trackers_without_them() {
+        use crate::adapters::memory::issues::MemIssueTracker;
+
+        let ctx = ServiceContext::testing().with_issues(MemIssueTracker::without_labels());
+        let mut specs = vec![sample_spec_full()];
+        let compare_labels = ctx.issues.supports_labels();
+        assert!(!compare_labels);
+
+        // The tracker rejects any labels, so both creating and updating
+        // succeed only if sync sends none.
+        let actions = plan_sync(&specs, &[], compare_labels);
+        execute_sync(&ctx, &specs, &actions).unwrap();
+        let issues = ctx.issues.list_issues(None).unwrap();
+        assert!(issues[0].labels.is_empty());
+
+        let actions = plan_sync(&specs, &issues, compare_labels);
+        assert!(matches!(&actions[0], SyncAction::Unchanged { .. }), "{actions:?}");
+
+        specs[0].title = "Renamed".to_string();
+        let actions = plan_sync(&specs, &issues, compare_labels);
+        assert!(matches!(&actions[0], SyncAction::Update { .. }), "{actions:?}");
+        execute_sync(&ctx, &specs, &actions).unwrap();
+    }
 }
@@ -0,0 +1,185 @@
+//! Spec-level verification cache.
+//!
+//! After a fully green run, each store-backed spec's result is saved to
+//! `<store>/verification-cache/<id>.yaml` together with a fingerprint of the
+//! spec, the files of the modules it resolves to, the files under its
+//! `affected_globs`, and the golden files its checks compare against. A later
+//! run whose fingerprint matches reuses that result instead of re-running the
+//! checks.
+//!
+//! Nothing else a check may depend on is fingerprinted: environment
+//! variables, installed tools, and other files outside those modules and
+//! globs can change without invalidating a cached pass. `validate --force` re-runs the
+//! checks regardless.
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::context::ServiceContext;
+use crate::error::SpeckError;
+use crate::linkage;
+use crate::map::CodebaseMap;
+use crate::spec::{TaskSpec, VerificationCheck, VerificationStrategy};
+
+use super::ValidationResult;
+
+/// Directory of cached results, relative to the spec store root.
+pub const VERIFICATION_CACHE_DIR: &str = "verification-cache";
+
+/// A cached green result and the fingerprint it was recorded under.
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheEntry {
+    fingerprint: String,
+    result: ValidationResult,
+}
+
+/// Reads and writes cached results under a spec store.
+pub struct VerificationCache<'a> {
+    ctx: &'a ServiceContext,
+    dir: PathBuf,
+}
+
+impl<'a> VerificationCache<'a> {
+    /// Creates a cache stored under `<store_root>/verification-cache/`.
+    #[must_use]
+    pub fn new(ctx: &'a ServiceContext, store_root: &Path) -> Self {
+        Self { ctx, dir: store_root.join(VERIFICATION_CACHE_DIR) }
+    }
+
+    /// Fingerprints `spec` together with the files it is checked against.
+    ///
+    /// Each resolved module directory is hashed recursively, so an edit
+    /// anywhere beneath it changes the fingerprint. So is the fixed part of
+    /// each of `affected_globs` (the path up to its first wildcard), so files
+    /// a glob could match are covered, along with some it may not. So is each
+    /// golden file the spec's checks compare against. Module directories are
+    /// resolved against `map` and everything is read relative to
+    /// `project_root`. Returns `None` when the spec names no modules, any
+    /// module is unresolved, a glob starts with a wildcard, or a file cannot
+    /// be read: without every file the spec depends on, a change could go
+    /// unnoticed, so such specs are never cached.
+    ///
+    /// The fingerprint is a SHA-256 digest, stable across toolchains and
+    /// platforms.
+    #[must_use]
+    pub fn fingerprint(
+        &self,
+        spec: &TaskSpec,
+        map: &CodebaseMap,
+        project_root: &Path,
+    ) -> Option<String> {
+        let linkage = linkage::resolve(spec, map);
+        if linkage.links.is_empty() || !linkage.fully_resolved() {
+            return None;
+        }
+        let mut paths: BTreeSet<&str> =
+            linkage.links.iter().filter_map(|l| l.resolved_path.as_deref()).collect();
+        for glob in spec.affected_globs.iter().flatten() {
+            paths.insert(glob_base(glob)?);
+        }
+        paths.extend(golden_paths(spec));
+
+        let mut hasher = Sha256::new();
+        feed(&mut hasher, serde_yaml::to_string(spec).ok()?.as_bytes());
+        for path in paths {
+            self.hash_path(&project_root.join(path), Path::new(path), &mut hasher)?;
+        }
+        Some(format!("{:x}", hasher.finalize()))
+    }
+
+    /// Hashes `path` (as `rel`): a file's contents, every file beneath a
+    /// directory in name order, or a marker if nothing exists there yet.
+    fn hash_path(&self, path: &Path, rel: &Path, hasher: &mut Sha256) -> Option<()> {
+        feed(hasher, rel.to_string_lossy().as_bytes());
+        if !self.ctx.fs.exists(path) {
+            feed(hasher, b"<missing>");
+            return Some(());
+        }
+        if !self.ctx.fs.metadata(path).ok()?.is_dir {
+            feed(hasher, self.ctx.fs.read_to_string(path).ok()?.as_bytes());
+            return Some(());
+        }
+        let mut names = self.ctx.fs.list_dir(path).ok()?;
+        names.sort();
+        for name in names {
+            self.hash_path(&path.join(&name), &rel.join(&name), hasher)?;
+        }
+        Some(())
+    }
+
+    /// Returns the cached result for `spec_id` if it was recorded under `fingerprint`.
+    ///
+    /// A missing or unreadable entry is a cache miss.
+    #[must_use]
+    pub fn lookup(&self, spec_id: &str, fingerprint: &str) -> Option<ValidationResult> {
+        let path = self.entry_path(spec_id);
+        if !self.ctx.fs.exists(&path) {
+            return None;
+        }
+        let yaml = self.ctx.fs.read_to_string(&path).ok()?;
+        let entry: CacheEntry = serde_yaml::from_str(&yaml).ok()?;
+        (entry.fingerprint == fingerprint).then_some(entry.result)
+    }
+
+    /// Records `result` under `fingerprint` if every check passed outright.
+    ///
+    /// Results with failures, including allowlisted known failures, are not
+    /// cached, so only green runs can be skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the entry cannot be serialized or written.
+    pub fn save(&self, fingerprint: &str, result: &ValidationResult) -> Result<(), SpeckError> {
+        if !result.checks.iter().all(|c| c.passed) {
+            return Ok(());
+        }
+        let entry = CacheEntry { fingerprint: fingerprint.to_string(), result: result.clone() };
+        let yaml = serde_yaml::to_string(&entry)
+            .map_err(|e| SpeckError::Parse(format!("Failed to serialize cached result: {e}")))?;
+        self.ctx
+            .fs
+            .create_dir_all(&self.dir)
+            .and_then(|()| self.ctx.fs.write(&self.entry_path(&result.spec_id), &yaml))
+            .map_err(|e| {
+                SpeckError::Io(format!("Failed to cache result for {}: {e}", result.spec_id))
+            })
+    }
+
+    fn entry_path(&self, spec_id: &str) -> PathBuf {
+        self.dir.join(format!("{spec_id}.yaml"))
+    }
+}
+
+/// The `golden_path` of every golden-file check in `spec`, grouped or not.
+fn golden_paths(spec: &TaskSpec) -> impl Iterator<Item = &str> {
+    let checks: Vec<&VerificationCheck> = match &spec.verification {
+        VerificationStrategy::DirectAssertion { checks, groups, .. } => {
+            checks.iter().chain(groups.iter().flat_map(|g| &g.checks)).collect()
+        }
+        _ => vec![],
+    };
+    checks.into_iter().filter_map(|check| match check {
+        VerificationCheck::GoldenFile { golden_path, .. } => Some(golden_path.as_str()),
+        _ => None,
+    })
+}
+
+/// Adds `bytes` to `hasher`, length-prefixed so adjacent inputs cannot run together.
+fn feed(hasher: &mut Sha256, bytes: &[u8]) {
+    hasher.update((bytes.len() as u64).to_le_bytes());
+    hasher.update(bytes);
+}
+
+/// The fixed leading part of `glob`: the directory holding its first wildcard,
+/// or the whole pattern if it has none. `None` if the wildcard is in the first
+/// path segment, since that would cover the whole project.
+fn glob_base(glob: &str) -> Option<&str> {
+    let base = match glob.find(['*', '?', '[', '{']) {
+        Some(i) => &glob[..glob[..i].rfind('/')?],
+        None => glob.trim_end_matches('/'),
+    };
+    (!base.is_empty()).then_some(base)
+}
@@ -0,0 +1,95 @@
+//! Archives the output of passing checks as evidence that they passed.
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use serde::Serialize;
+
+use super::ValidationResult;
+use crate::context::ServiceContext;
+
+/// Name of the per-spec file linking each captured check to its log.
+pub const CAPTURE_MANIFEST: &str = "manifest.json";
+
+#[derive(Serialize)]
+struct Manifest<'a> {
+    spec_id: &'a str,
+    checks: Vec<ManifestEntry<'a>>,
+}
+
+#[derive(Serialize)]
+struct ManifestEntry<'a> {
+    check: &'a str,
+    log: String,
+    exit_code: i32,
+}
+
+/// Writes the output of each passing check in `result` under `dir/<spec_id>/`.
+///
+/// Each check that ran a command and passed gets a `<check>.log` holding its
+/// exit code, stdout, and stderr, with the check name reduced to a safe file
+/// name. A [`CAPTURE_MANIFEST`] maps check names to their log files. Checks
+/// that failed, ran nothing, or were served from the cache are not captured.
+///
+/// Returns the number of logs written.
+///
+/// # Errors
+///
+/// Returns an error if a log or the manifest cannot be written.
+pub fn capture_passing(
+    ctx: &ServiceContext,
+    dir: &Path,
+    result: &ValidationResult,
+) -> Result<usize, String> {
+    let spec_dir = dir.join(&result.spec_id);
+    let mut used = HashSet::new();
+    let mut entries = Vec::new();
+    for check in result.checks.iter().filter(|c| c.passed) {
+        let Some(output) = &check.output else {
+            continue;
+        };
+        if entries.is_empty() {
+            ctx.fs
+                .create_dir_all(&spec_dir)
+                .map_err(|e| format!("failed to create {}: {e}", spec_dir.display()))?;
+        }
+        let log = log_file_name(&check.name, &mut used);
+        let contents = format!(
+            "exit code: {}\n--- stdout ---\n{}\n--- stderr ---\n{}\n",
+            output.exit_code, output.stdout, output.stderr
+        );
+        let path = spec_dir.join(&log);
+        ctx.fs
+            .write(&path, &contents)
+            .map_err(|e| format!("failed to write {}: {e}", path.display()))?;
+        entries.push(ManifestEntry { check: &check.name, log, exit_code: output.exit_code });
+    }
+    if entries.is_empty() {
+        return Ok(0);
+    }
+
+    let count = entries.len();
+    let manifest = Manifest { spec_id: &result.spec_id, checks: entries };
+    let json = serde_json::to_string_pretty(&manifest)
+        .map_err(|e| format!("failed to serialize capture manifest: {e}"))?;
+    let path = spec_dir.join(CAPTURE_MANIFEST);
+    ctx.fs.write(&path, &json).map_err(|e| format!("failed to write {}: {e}", path.display()))?;
+    Ok(count)
+}
+
+/// A `.log` file name for `check`, distinct from every name already in `used`.
+fn log_file_name(check: &str, used: &mut HashSet<String>) -> String {
+    let stem: String = check
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '.' { c } else { '_' })
+        .collect();
+    let stem = stem.trim_matches(['_', '.']);
+    let stem = if stem.is_empty() { "check" } else { stem };
+    let mut name = format!("{stem}.log");
+    let mut n = 2;
+    while !used.insert(name.clone()) {
+        name = format!("{stem}-{n}.log");
+        n += 1;
+    }
+    name
+}
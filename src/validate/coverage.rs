@@ -0,0 +1,246 @@
+//! Coverage verification: runs a coverage tool and parses its reported percentage.
+
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use super::{CheckCategory, CheckResult};
+use crate::context::ServiceContext;
+
+/// Matches a percentage such as `84%` or `84.62%` when a check gives no pattern.
+static DEFAULT_PATTERN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\d+(?:\.\d+)?%").expect("default coverage pattern is valid"));
+
+/// Runs a coverage command and passes when the reported percentage meets `min_percent`.
+///
+/// The percentage is the first match of `pattern` (or [`DEFAULT_PATTERN`])
+/// after `marker`. An invalid `pattern` fails the check without running it.
+pub(super) fn run_coverage_check(
+    ctx: &ServiceContext,
+    command: &str,
+    min_percent: f64,
+    marker: Option<&str>,
+    pattern: Option<&str>,
+) -> CheckResult {
+    let name = format!("coverage: {command}");
+    let expected = format!(">= {min_percent}%");
+
+    let custom = match pattern.map(Regex::new).transpose() {
+        Ok(custom) => custom,
+        Err(e) => {
+            return CheckResult {
+                name,
+                passed: false,
+                detail: format!("invalid coverage pattern: {e}"),
+                expected,
+                actual: "invalid pattern".to_string(),
+                category: CheckCategory::Executable,
+                group: None,
+                output: None,
+            };
+        }
+    };
+    let pattern = custom.as_ref().unwrap_or(&DEFAULT_PATTERN);
+
+    let output = match ctx.shell.run_inspected(command) {
+        Ok(output) => output,
+        Err(e) => {
+            return CheckResult {
+                name,
+                passed: false,
+                detail: format!("failed to run command: {e}"),
+                expected,
+                actual: format!("error: {e}"),
+                category: CheckCategory::Executable,
+                group: None,
+                output: None,
+            };
+        }
+    };
+
+    if output.exit_code != 0 {
+        return CheckResult {
+            name,
+            passed: false,
+            detail: format!(
+                "coverage command exited with code {}\nstderr: {}",
+                output.exit_code, output.stderr
+            ),
+            expected,
+            actual: format!("exit code {}", output.exit_code),
+            category: CheckCategory::Executable,
+            group: None,
+            output: None,
+        };
+    }
+
+    match parse_percent(&output.stdout, marker, pattern) {
+        Some(actual) => {
+            let passed = actual >= min_percent;
+            let detail = if passed {
+                format!("coverage {actual}% meets required {min_percent}%")
+            } else {
+                format!("coverage {actual}% is below required {min_percent}%")
+            };
+            CheckResult {
+                name,
+                passed,
+                detail,
+                expected,
+                actual: format!("{actual}%"),
+                category: CheckCategory::Executable,
+                group: None,
+                output: Some(output.clone()),
+            }
+        }
+        None => CheckResult {
+            name,
+            passed: false,
+            detail: match marker {
+                Some(m) => format!("no match for /{pattern}/ after '{m}' in output"),
+                None => format!("no match for /{pattern}/ in output"),
+            },
+            expected,
+            actual: "no percentage reported".to_string(),
+            category: CheckCategory::Executable,
+            group: None,
+            output: None,
+        },
+    }
+}
+
+/// Reads the first match of `pattern` in `text`, starting after `marker` when given.
+///
+/// The number is the match's first capture group, or the whole match with any
+/// `%` removed; a match that is not a number counts as no match.
+pub(super) fn parse_percent(text: &str, marker: Option<&str>, pattern: &Regex) -> Option<f64> {
+    let haystack = match marker {
+        Some(m) => &text[text.find(m)? + m.len()..],
+        None => text,
+    };
+    let captures = pattern.captures(haystack)?;
+    let number = captures.get(1).or_else(|| captures.get(0))?.as_str();
+    number.trim().trim_end_matches('%').trim().parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cassette::config::CassetteConfig;
+    use crate::ports::shell::{ShellExecutor, ShellOutput};
+
+    struct FakeShellExecutor {
+        stdout: String,
+    }
+
+    impl ShellExecutor for FakeShellExecutor {
+        fn run(
+            &self,
+            _command: &str,
+        ) -> Result<ShellOutput, Box<dyn std::error::Error + Send + Sync>> {
+            Ok(ShellOutput { exit_code: 0, stdout: self.stdout.clone(), stderr: String::new() })
+        }
+    }
+
+    fn context_with_stdout(stdout: &str) -> ServiceContext {
+        let mut ctx = ServiceContext::replaying_from(&CassetteConfig::panic_on_unspecified())
+            .expect("panic config should always succeed");
+        ctx.shell = Box::new(FakeShellExecutor { stdout: stdout.to_string() });
+        ctx
+    }
+
+    #[test]
+    fn parses_first_percentage() {
+        let out = "running 12 tests\n84.62% coverage, 110/130 lines covered\n";
+        assert_eq!(parse_percent(out, None, &DEFAULT_PATTERN), Some(84.62));
+    }
+
+    #[test]
+    fn parses_integer_percentage_after_marker() {
+        let out = "src/a.rs  50%\nsrc/b.rs  70%\nTOTAL     91%\n";
+        assert_eq!(parse_percent(out, Some("TOTAL"), &DEFAULT_PATTERN), Some(91.0));
+    }
+
+    #[test]
+    fn parse_returns_none_without_percentage() {
+        assert_eq!(parse_percent("no numbers here 42", None, &DEFAULT_PATTERN), None);
+        assert_eq!(parse_percent("50%", Some("TOTAL"), &DEFAULT_PATTERN), None);
+    }
+
+    #[test]
+    fn coverage_meeting_threshold_passes() {
+        let ctx = context_with_stdout("84.62% coverage");
+        let result = run_coverage_check(&ctx, "cargo tarpaulin", 80.0, None, None);
+        assert!(result.passed);
+        assert_eq!(result.actual, "84.62%");
+    }
+
+    #[test]
+    fn coverage_below_threshold_fails_with_actual_and_required() {
+        let ctx = context_with_stdout("61.5% coverage");
+        let result = run_coverage_check(&ctx, "cargo tarpaulin", 80.0, None, None);
+        assert!(!result.passed);
+        assert_eq!(result.actual, "61.5%");
+        assert_eq!(result.expected, ">= 80%");
+        assert!(result.detail.contains("61.5% is below required 80%"));
+    }
+
+    #[test]
+    fn custom_pattern_reads_its_capture_group() {
+        let ctx = context_with_stdout(
+            "Filename  Regions  Cover  Lines  Cover\nTOTAL  1200  72.10%  900  88.40%\n",
+        );
+        let lines = r"TOTAL(?:\s+\S+){3}\s+(\d+\.\d+)%";
+        let result = run_coverage_check(&ctx, "cargo llvm-cov", 85.0, None, Some(lines));
+        assert!(result.passed, "{}", result.detail);
+        assert_eq!(result.actual, "88.4%");
+    }
+
+    #[test]
+    fn invalid_pattern_fails_without_running_the_command() {
+        let ctx = ServiceContext::replaying_from(&CassetteConfig::panic_on_unspecified())
+            .expect("panic config should always succeed");
+        let result = run_coverage_check(&ctx, "cargo tarpaulin", 80.0, None, Some("(\\d+%"));
+        assert!(!result.passed);
+        assert!(result.detail.starts_with("invalid coverage pattern"), "{}", result.detail);
+    }
+
+    #[test]
+    fn minimal_recording_keeps_the_coverage_report_for_replay() {
+        use std::sync::{Arc, Mutex};
+
+        use crate::adapters::recording::shell::RecordingShellExecutor;
+        use crate::adapters::replaying::shell::ReplayingShellExecutor;
+        use crate::cassette::recorder::CassetteRecorder;
+        use crate::cassette::shell_output::ShellRecordMode;
+
+        let dir = std::env::temp_dir().join("speck_coverage_minimal_replay");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("coverage.cassette.yaml");
+
+        // The report sits between long logs, where a minimal excerpt elides it.
+        let log = "compiling crate\n".repeat(100);
+        let stdout = format!("{log}TOTAL 87.5%\n{log}");
+        let recorder = Arc::new(Mutex::new(CassetteRecorder::new(&path, "test", "abc")));
+        let shell = RecordingShellExecutor::new(
+            Box::new(FakeShellExecutor { stdout }),
+            Arc::clone(&recorder),
+        )
+        .with_mode(ShellRecordMode::Minimal);
+        let ctx = ServiceContext::testing().with_shell(shell);
+        let on_record = run_coverage_check(&ctx, "cargo tarpaulin", 80.0, Some("TOTAL"), None);
+        drop(ctx);
+        Arc::try_unwrap(recorder).ok().unwrap().into_inner().unwrap().finish().unwrap();
+
+        let replayer = CassetteConfig::load_monolithic(&path).unwrap();
+        let ctx = ServiceContext::testing()
+            .with_shell(ReplayingShellExecutor::new(Arc::new(Mutex::new(replayer))));
+        let on_replay = run_coverage_check(&ctx, "cargo tarpaulin", 80.0, Some("TOTAL"), None);
+
+        let _ = std::fs::remove_dir_all(&dir);
+        assert!(on_record.passed, "{}", on_record.detail);
+        assert!(on_replay.passed, "{}", on_replay.detail);
+        assert_eq!(on_replay.actual, on_record.actual);
+    }
+}
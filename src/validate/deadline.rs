@@ -0,0 +1,53 @@
+//! Wall-clock budget shared by every check in a validation run.
+
+use std::time::Duration;
+
+use crate::context::ServiceContext;
+use crate::spec::VerificationCheck;
+
+use super::{dry_run, CheckCategory, CheckResult};
+
+/// Detail recorded for checks skipped because the budget ran out.
+pub const DEADLINE_EXCEEDED: &str = "not run (deadline exceeded)";
+
+/// A point on the monotonic clock after which no further checks start.
+///
+/// Checks already running are not interrupted; per-check timeouts bound those.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Deadline {
+    expires_at: Duration,
+}
+
+impl Deadline {
+    /// A deadline `budget` from now on `ctx.clock`.
+    #[must_use]
+    pub fn after(ctx: &ServiceContext, budget: Duration) -> Self {
+        Self { expires_at: ctx.clock.monotonic().saturating_add(budget) }
+    }
+
+    /// Returns `true` once the budget has been used up.
+    #[must_use]
+    pub fn exceeded(&self, ctx: &ServiceContext) -> bool {
+        ctx.clock.monotonic() >= self.expires_at
+    }
+}
+
+/// The result reported for `check` when it is skipped at the deadline.
+pub(super) fn not_run(ctx: &ServiceContext, check: &VerificationCheck) -> CheckResult {
+    let planned = dry_run::plan_check(ctx, check);
+    let category = if planned.would_run.is_some() {
+        CheckCategory::Executable
+    } else {
+        CheckCategory::ManualReview
+    };
+    CheckResult {
+        name: planned.name,
+        passed: false,
+        detail: DEADLINE_EXCEEDED.to_string(),
+        expected: planned.expected,
+        actual: "not run".to_string(),
+        category,
+        group: None,
+        output: None,
+    }
+}
@@ -0,0 +1,194 @@
+//! Dry-run planning: describe what validation would execute without running it.
+
+use serde::Serialize;
+
+use crate::context::ServiceContext;
+use crate::spec::{TaskSpec, VerificationCheck, VerificationStrategy};
+
+use super::interpolate;
+
+/// One check as it would be run by [`validate`](super::validate).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PlannedCheck {
+    /// Check name, matching [`CheckResult::name`](super::CheckResult::name).
+    pub name: String,
+    /// The command or query that would be executed, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub would_run: Option<String>,
+    /// Resolved expectation, after `${VAR}` interpolation.
+    pub expected: String,
+    /// Why the check would fail before running, e.g. an undefined variable.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Label of the check group this check belongs to, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+}
+
+/// Resolves every check in `spec` without invoking `ctx.shell`.
+///
+/// Expected values are interpolated through `ctx.env` exactly as a real run
+/// would; checks that need manual review have no `would_run`.
+#[must_use]
+pub fn plan_checks(ctx: &ServiceContext, spec: &TaskSpec) -> Vec<PlannedCheck> {
+    match &spec.verification {
+        VerificationStrategy::DirectAssertion { checks, groups, setup, teardown } => {
+            let mut planned: Vec<PlannedCheck> =
+                setup.iter().map(|command| lifecycle("setup", command)).collect();
+            planned.extend(checks.iter().map(|check| plan_check(ctx, check)));
+            for group in groups {
+                planned.extend(group.checks.iter().map(|check| PlannedCheck {
+                    group: Some(group.label.clone()),
+                    ..plan_check(ctx, check)
+                }));
+            }
+            planned.extend(teardown.iter().map(|command| lifecycle("teardown", command)));
+            planned
+        }
+        VerificationStrategy::RefactorToExpose { decision_point, .. } => {
+            vec![manual(
+                format!("refactor-to-expose: {decision_point}"),
+                "manual refactoring completed",
+            )]
+        }
+        VerificationStrategy::TraceAssertion { trace_point, .. } => {
+            vec![manual(format!("trace-assertion: {trace_point}"), "trace matches expected output")]
+        }
+    }
+}
+
+/// Formats planned checks as a human-readable report.
+#[must_use]
+pub fn format_plan(spec_id: &str, planned: &[PlannedCheck]) -> String {
+    let mut lines = vec![format!("Spec: {spec_id} (dry run)"), String::new()];
+    let mut current_group = None;
+    for check in planned {
+        if check.group.is_some() && check.group != current_group {
+            current_group.clone_from(&check.group);
+            lines.push(format!("  {}:", check.group.as_deref().unwrap_or_default()));
+        }
+        let indent = if check.group.is_some() { "    " } else { "  " };
+        let status = match (&check.would_run, &check.error) {
+            (_, Some(_)) => "ERROR",
+            (Some(_), None) => "WOULD RUN",
+            (None, None) => "MANUAL",
+        };
+        lines.push(format!("{indent}[{status}] {}", check.name));
+        if let Some(command) = &check.would_run {
+            lines.push(format!("{indent}       run:      {command}"));
+        }
+        lines.push(format!("{indent}       expected: {}", check.expected));
+        if let Some(error) = &check.error {
+            lines.push(format!("{indent}       error:    {error}"));
+        }
+    }
+    lines.join("\n")
+}
+
+pub(super) fn plan_check(ctx: &ServiceContext, check: &VerificationCheck) -> PlannedCheck {
+    let planned = match check {
+        VerificationCheck::TestSuite { command, expected, .. } => {
+            plan_shell_check(ctx, format!("test-suite: {command}"), command, expected)
+        }
+        VerificationCheck::CommandOutput { command, expected, .. } => {
+            plan_shell_check(ctx, format!("command-output: {command}"), command, expected)
+        }
+        VerificationCheck::Coverage { command, min_percent, .. } => PlannedCheck {
+            name: format!("coverage: {command}"),
+            would_run: Some(command.clone()),
+            expected: format!("coverage >= {min_percent}%"),
+            error: None,
+            group: None,
+        },
+        VerificationCheck::GoldenFile { command, golden_path, .. } => PlannedCheck {
+            name: format!("golden-file: {command}"),
+            would_run: Some(command.clone()),
+            expected: format!("stdout matches {golden_path}"),
+            error: None,
+            group: None,
+        },
+        VerificationCheck::SqlAssertion { query, expected, .. } => {
+            manual(format!("sql-assertion: {query}"), expected)
+        }
+        VerificationCheck::MigrationRollback { description, .. } => {
+            manual(format!("migration-rollback: {description}"), "rollback succeeds")
+        }
+        VerificationCheck::Custom { description, .. } => {
+            manual(format!("custom: {description}"), description)
+        }
+    };
+    match check.name() {
+        Some(name) => PlannedCheck { name: name.to_string(), ..planned },
+        None => planned,
+    }
+}
+
+fn plan_shell_check(
+    ctx: &ServiceContext,
+    name: String,
+    command: &str,
+    expected: &str,
+) -> PlannedCheck {
+    let (expected, error) = match interpolate::interpolate(ctx.env.as_ref(), expected) {
+        Ok(resolved) => (resolved, None),
+        Err(e) => (expected.to_string(), Some(format!("could not resolve expected value: {e}"))),
+    };
+    PlannedCheck { name, would_run: Some(command.to_string()), expected, error, group: None }
+}
+
+fn manual(name: String, expected: &str) -> PlannedCheck {
+    PlannedCheck { name, would_run: None, expected: expected.to_string(), error: None, group: None }
+}
+
+/// A setup or teardown command, named `"{phase}: {command}"`.
+fn lifecycle(phase: &str, command: &str) -> PlannedCheck {
+    PlannedCheck {
+        name: format!("{phase}: {command}"),
+        would_run: Some(command.to_string()),
+        expected: "exit code 0".to_string(),
+        error: None,
+        group: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cassette::config::CassetteConfig;
+    use crate::spec::CheckGroup;
+
+    #[test]
+    fn plan_lists_commands_and_manual_checks() {
+        let ctx = ServiceContext::replaying_from(&CassetteConfig::panic_on_unspecified()).unwrap();
+        let spec = TaskSpec {
+            verification: VerificationStrategy::DirectAssertion {
+                checks: vec![VerificationCheck::TestSuite {
+                    command: "cargo test export".to_string(),
+                    expected: "pass".to_string(),
+                    retries: 0,
+                    retry_backoff_ms: 0,
+                    name: None,
+                }],
+                groups: vec![CheckGroup {
+                    label: "Review".to_string(),
+                    checks: vec![VerificationCheck::Custom {
+                        description: "UX ok".to_string(),
+                        name: None,
+                    }],
+                }],
+                setup: vec![],
+                teardown: vec![],
+            },
+            ..TaskSpec::for_test("DRY-1", "Dry run")
+        };
+
+        let planned = plan_checks(&ctx, &spec);
+        assert_eq!(planned[0].would_run.as_deref(), Some("cargo test export"));
+        assert_eq!(planned[1].group.as_deref(), Some("Review"));
+        assert!(planned[1].would_run.is_none());
+
+        let report = format_plan(&spec.id, &planned);
+        assert!(report.contains("[WOULD RUN] test-suite: cargo test export"), "{report}");
+        assert!(report.contains("  Review:\n    [MANUAL] custom: UX ok"), "{report}");
+    }
+}
@@ -0,0 +1,227 @@
+//! Golden-file verification: compares a command's stdout with a stored file.
+
+use std::path::Path;
+
+use super::{CheckCategory, CheckResult};
+use crate::context::ServiceContext;
+
+/// Unchanged lines shown on each side of a mismatch.
+const CONTEXT_LINES: usize = 3;
+
+/// Diff lines shown before the excerpt is cut short.
+const MAX_DIFF_LINES: usize = 40;
+
+/// Runs `command` and passes when its stdout equals the contents of `golden_path`.
+///
+/// With `update`, a missing or mismatched golden file is rewritten with the
+/// command's stdout and the check passes. A command that exits non-zero fails
+/// in either mode and never updates the file.
+pub(super) fn run_golden_check(
+    ctx: &ServiceContext,
+    command: &str,
+    golden_path: &str,
+    update: bool,
+) -> CheckResult {
+    let name = format!("golden-file: {command}");
+    let expected = format!("stdout matches {golden_path}");
+    let fail = |detail: String, actual: String| CheckResult {
+        name: name.clone(),
+        passed: false,
+        detail,
+        expected: expected.clone(),
+        actual,
+        category: CheckCategory::Executable,
+        group: None,
+        output: None,
+    };
+
+    let output = match ctx.shell.run_inspected(command) {
+        Ok(output) => output,
+        Err(e) => return fail(format!("failed to run command: {e}"), format!("error: {e}")),
+    };
+    if output.exit_code != 0 {
+        return fail(
+            format!("command exited with code {}\nstderr: {}", output.exit_code, output.stderr),
+            format!("exit code {}", output.exit_code),
+        );
+    }
+
+    let path = Path::new(golden_path);
+    let golden = if ctx.fs.exists(path) {
+        match ctx.fs.read_to_string(path) {
+            Ok(content) => Some(content),
+            Err(e) => {
+                return fail(format!("failed to read golden file: {e}"), format!("error: {e}"))
+            }
+        }
+    } else {
+        None
+    };
+
+    let (passed, detail, actual) = match golden {
+        Some(golden) if golden == output.stdout => {
+            (true, format!("stdout matches {golden_path}"), "stdout matches".to_string())
+        }
+        _ if update => match write_golden(ctx, path, &output.stdout) {
+            Ok(()) => (true, format!("updated {golden_path}"), "golden file updated".to_string()),
+            Err(e) => (false, format!("failed to update golden file: {e}"), format!("error: {e}")),
+        },
+        Some(golden) => (
+            false,
+            format!(
+                "stdout differs from {golden_path}\n{}",
+                unified_diff_excerpt(&golden, &output.stdout)
+            ),
+            "stdout differs".to_string(),
+        ),
+        None => (
+            false,
+            format!("golden file {golden_path} not found; rerun with --update-golden to create it"),
+            "golden file missing".to_string(),
+        ),
+    };
+    CheckResult {
+        passed,
+        detail,
+        actual,
+        output: Some(output),
+        ..fail(String::new(), String::new())
+    }
+}
+
+fn write_golden(ctx: &ServiceContext, path: &Path, content: &str) -> Result<(), String> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        ctx.fs.create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    ctx.fs.write(path, content).map_err(|e| e.to_string())
+}
+
+/// A single-hunk unified diff from `golden` to `actual`, cut to [`MAX_DIFF_LINES`].
+///
+/// The hunk spans from the first to the last differing line, with
+/// [`CONTEXT_LINES`] of unchanged context on each side.
+fn unified_diff_excerpt(golden: &str, actual: &str) -> String {
+    let old: Vec<&str> = golden.lines().collect();
+    let new: Vec<&str> = actual.lines().collect();
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let start = prefix.saturating_sub(CONTEXT_LINES);
+    let old_end = (old.len() - suffix + CONTEXT_LINES).min(old.len());
+    let new_end = (new.len() - suffix + CONTEXT_LINES).min(new.len());
+
+    let mut body: Vec<String> = old[start..prefix].iter().map(|l| format!(" {l}")).collect();
+    body.extend(old[prefix..old.len() - suffix].iter().map(|l| format!("-{l}")));
+    body.extend(new[prefix..new.len() - suffix].iter().map(|l| format!("+{l}")));
+    body.extend(old[old.len() - suffix..old_end].iter().map(|l| format!(" {l}")));
+    if body.iter().all(|l| l.starts_with(' ')) {
+        // Line content is identical; only trailing newlines differ.
+        body.push("\\ trailing newline differs".to_string());
+    }
+
+    let mut lines = vec![
+        "--- golden".to_string(),
+        "+++ stdout".to_string(),
+        format!("@@ -{},{} +{},{} @@", start + 1, old_end - start, start + 1, new_end - start),
+    ];
+    let hidden = body.len().saturating_sub(MAX_DIFF_LINES);
+    lines.extend(body.into_iter().take(MAX_DIFF_LINES));
+    if hidden > 0 {
+        lines.push(format!("... {hidden} more diff lines"));
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::memory::filesystem::MemFileSystem;
+    use crate::ports::shell::{ShellExecutor, ShellOutput};
+
+    /// Shell whose every command prints `report` and succeeds.
+    struct ReportShell;
+
+    impl ShellExecutor for ReportShell {
+        fn run(
+            &self,
+            _command: &str,
+        ) -> Result<ShellOutput, Box<dyn std::error::Error + Send + Sync>> {
+            Ok(ShellOutput {
+                exit_code: 0,
+                stdout: "total: 3\nfailed: 1\n".to_string(),
+                stderr: String::new(),
+            })
+        }
+    }
+
+    fn golden_context(golden: Option<&str>) -> (ServiceContext, MemFileSystem) {
+        let fs = MemFileSystem::new();
+        if let Some(golden) = golden {
+            fs.files().lock().unwrap().insert("golden/report.txt".into(), golden.to_string());
+        }
+        let ctx = ServiceContext::testing().with_fs(fs.clone()).with_shell(ReportShell);
+        (ctx, fs)
+    }
+
+    fn golden_file(fs: &MemFileSystem) -> Option<String> {
+        fs.files().lock().unwrap().get(Path::new("golden/report.txt")).cloned()
+    }
+
+    #[test]
+    fn matching_stdout_passes() {
+        let (ctx, _) = golden_context(Some("total: 3\nfailed: 1\n"));
+        let result = run_golden_check(&ctx, "./report", "golden/report.txt", false);
+        assert!(result.passed, "{}", result.detail);
+        assert_eq!(result.name, "golden-file: ./report");
+    }
+
+    #[test]
+    fn mismatched_stdout_fails_with_diff() {
+        let (ctx, fs) = golden_context(Some("total: 3\nfailed: 0\n"));
+        let result = run_golden_check(&ctx, "./report", "golden/report.txt", false);
+        assert!(!result.passed);
+        assert_eq!(
+            result.detail,
+            "stdout differs from golden/report.txt\n--- golden\n+++ stdout\n\
+             @@ -1,2 +1,2 @@\n total: 3\n-failed: 0\n+failed: 1"
+        );
+        assert_eq!(golden_file(&fs).as_deref(), Some("total: 3\nfailed: 0\n"));
+    }
+
+    #[test]
+    fn update_mode_rewrites_mismatched_and_missing_golden_files() {
+        let (ctx, fs) = golden_context(Some("stale\n"));
+        let result = run_golden_check(&ctx, "./report", "golden/report.txt", true);
+        assert!(result.passed, "{}", result.detail);
+        assert_eq!(result.detail, "updated golden/report.txt");
+        assert_eq!(golden_file(&fs).as_deref(), Some("total: 3\nfailed: 1\n"));
+
+        let (ctx, fs) = golden_context(None);
+        let missing = run_golden_check(&ctx, "./report", "golden/report.txt", false);
+        assert!(!missing.passed);
+        assert!(missing.detail.contains("--update-golden"), "{}", missing.detail);
+        assert!(run_golden_check(&ctx, "./report", "golden/report.txt", true).passed);
+        assert_eq!(golden_file(&fs).as_deref(), Some("total: 3\nfailed: 1\n"));
+    }
+
+    #[test]
+    fn diff_excerpt_shows_changed_lines_with_context() {
+        let golden = "a\nb\nc\nd\ne\nf\ng\nh\n";
+        let actual = "a\nb\nc\nd\nE\nf\ng\nh\n";
+        let diff = unified_diff_excerpt(golden, actual);
+        assert_eq!(diff, "--- golden\n+++ stdout\n@@ -2,7 +2,7 @@\n b\n c\n d\n-e\n+E\n f\n g\n h");
+    }
+
+    #[test]
+    fn diff_excerpt_is_truncated() {
+        let golden = "x\n".repeat(100);
+        let actual = "y\n".repeat(100);
+        let diff = unified_diff_excerpt(&golden, &actual);
+        assert!(diff.ends_with("... 160 more diff lines"));
+    }
+}
@@ -0,0 +1,146 @@
+//! `${VAR}` interpolation of expected values from the environment port.
+
+use crate::ports::Environment;
+
+/// Replaces each `${VAR}` in `text` with its value from `env`.
+///
+/// Text without placeholders is returned unchanged. An unterminated `${`
+/// is kept literally.
+///
+/// # Errors
+///
+/// Returns an error naming the first variable that is not defined.
+pub(super) fn interpolate(env: &dyn Environment, text: &str) -> Result<String, String> {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find("${") {
+        let Some(len) = rest[start + 2..].find('}') else {
+            break;
+        };
+        let name = &rest[start + 2..start + 2 + len];
+        let value = env
+            .var(name)
+            .ok_or_else(|| format!("undefined environment variable '{name}' in expected value"))?;
+        out.push_str(&rest[..start]);
+        out.push_str(&value);
+        rest = &rest[start + 2 + len + 1..];
+    }
+    out.push_str(rest);
+
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cassette::config::CassetteConfig;
+    use crate::context::ServiceContext;
+    use crate::ports::shell::{ShellExecutor, ShellOutput};
+    use crate::spec::VerificationCheck;
+    use std::collections::HashMap;
+
+    struct FakeEnvironment(HashMap<&'static str, &'static str>);
+
+    impl Environment for FakeEnvironment {
+        fn var(&self, name: &str) -> Option<String> {
+            self.0.get(name).map(|v| (*v).to_string())
+        }
+    }
+
+    struct FakeShellExecutor;
+
+    impl ShellExecutor for FakeShellExecutor {
+        fn run(
+            &self,
+            _command: &str,
+        ) -> Result<ShellOutput, Box<dyn std::error::Error + Send + Sync>> {
+            Ok(ShellOutput {
+                exit_code: 0,
+                stdout: "speck version 1.4.2\n".to_string(),
+                stderr: String::new(),
+            })
+        }
+    }
+
+    fn env() -> FakeEnvironment {
+        FakeEnvironment(HashMap::from([("EXPECTED_VERSION", "1.4.2"), ("NAME", "speck")]))
+    }
+
+    fn check_context() -> ServiceContext {
+        let mut ctx = ServiceContext::replaying_from(&CassetteConfig::panic_on_unspecified())
+            .expect("panic config should always succeed");
+        ctx.shell = Box::new(FakeShellExecutor);
+        ctx.env = Box::new(env());
+        ctx
+    }
+
+    #[test]
+    fn literal_without_placeholders_unchanged() {
+        assert_eq!(interpolate(&env(), "all tests pass").unwrap(), "all tests pass");
+    }
+
+    #[test]
+    fn interpolates_multiple_variables() {
+        let text = "${NAME} version ${EXPECTED_VERSION}";
+        assert_eq!(interpolate(&env(), text).unwrap(), "speck version 1.4.2");
+    }
+
+    #[test]
+    fn undefined_variable_is_an_error() {
+        let err = interpolate(&env(), "version ${MISSING}").unwrap_err();
+        assert!(err.contains("'MISSING'"));
+    }
+
+    #[test]
+    fn unterminated_placeholder_kept_literally() {
+        assert_eq!(interpolate(&env(), "cost ${NAME").unwrap(), "cost ${NAME");
+    }
+
+    #[test]
+    fn command_output_check_reports_interpolated_expected() {
+        let ctx = check_context();
+        let check = VerificationCheck::CommandOutput {
+            command: "speck --version".to_string(),
+            expected: "version ${EXPECTED_VERSION}".to_string(),
+            retries: 0,
+            retry_backoff_ms: 0,
+            name: None,
+        };
+        let result =
+            crate::validate::run_check(&ctx, &check, &crate::validate::RunOptions::default());
+        assert!(result.passed, "{}", result.detail);
+        assert_eq!(result.expected, "version 1.4.2");
+
+        // Like a literal, the resolved value is descriptive: output that
+        // does not contain it still passes on exit code 0.
+        let unmatched = VerificationCheck::CommandOutput {
+            command: "speck --version".to_string(),
+            expected: "version ${NAME}".to_string(),
+            retries: 0,
+            retry_backoff_ms: 0,
+            name: None,
+        };
+        let result =
+            crate::validate::run_check(&ctx, &unmatched, &crate::validate::RunOptions::default());
+        assert!(result.passed, "{}", result.detail);
+        assert_eq!(result.detail, "exit code 0 (expected: version speck)");
+    }
+
+    #[test]
+    fn check_with_undefined_variable_fails_clearly() {
+        let ctx = check_context();
+        let check = VerificationCheck::TestSuite {
+            command: "cargo test".to_string(),
+            expected: "${MISSING_VAR} passed".to_string(),
+            retries: 0,
+            retry_backoff_ms: 0,
+            name: None,
+        };
+        let result =
+            crate::validate::run_check(&ctx, &check, &crate::validate::RunOptions::default());
+        assert!(!result.passed);
+        assert!(result.detail.contains("undefined environment variable 'MISSING_VAR'"));
+        assert_eq!(result.actual, "not executed");
+    }
+}
@@ -0,0 +1,89 @@
+//! Allowlist of checks that are known to fail.
+//!
+//! Read from `<store>/known-failures.yaml`, which maps spec IDs to the
+//! names of checks whose failures should be downgraded to warnings:
+//!
+//! ```yaml
+//! LEGACY-7:
+//!   - "test-suite: cargo test legacy::"
+//! ```
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::context::ServiceContext;
+use crate::error::SpeckError;
+
+use super::ValidationResult;
+
+/// Filename of the allowlist, relative to the spec store root.
+pub const KNOWN_FAILURES_FILE: &str = "known-failures.yaml";
+
+/// Check names, per spec ID, whose failures are expected.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KnownFailures {
+    entries: BTreeMap<String, Vec<String>>,
+}
+
+impl KnownFailures {
+    /// Parses an allowlist from YAML.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the YAML is not a map of spec IDs to check names.
+    pub fn parse(yaml: &str) -> Result<Self, SpeckError> {
+        let entries: Option<BTreeMap<String, Vec<String>>> =
+            serde_yaml::from_str(yaml).map_err(|e| {
+                SpeckError::Parse(format!("Failed to parse {KNOWN_FAILURES_FILE}: {e}"))
+            })?;
+        Ok(Self { entries: entries.unwrap_or_default() })
+    }
+
+    /// Loads the allowlist from `<store_root>/known-failures.yaml`.
+    ///
+    /// A missing file yields an empty allowlist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but cannot be read or parsed.
+    pub fn load(ctx: &ServiceContext, store_root: &Path) -> Result<Self, SpeckError> {
+        let path = store_root.join(KNOWN_FAILURES_FILE);
+        if !ctx.fs.exists(&path) {
+            return Ok(Self::default());
+        }
+        let yaml = ctx
+            .fs
+            .read_to_string(&path)
+            .map_err(|e| SpeckError::Io(format!("Failed to read {}: {e}", path.display())))?;
+        Self::parse(&yaml)
+    }
+
+    /// Marks the allowlisted checks of `result` as known failures.
+    pub fn apply(&self, result: &mut ValidationResult) {
+        if let Some(names) = self.entries.get(&result.spec_id) {
+            result.known_failures.clone_from(names);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_spec_to_check_names() {
+        let known = KnownFailures::parse("LEGACY-1:\n  - \"custom: flaky\"\n").unwrap();
+        assert_eq!(known.entries["LEGACY-1"], ["custom: flaky"]);
+    }
+
+    #[test]
+    fn malformed_allowlist_is_a_parse_error() {
+        let err = KnownFailures::parse("LEGACY-1: [").unwrap_err();
+        assert!(matches!(err, SpeckError::Parse(_)), "{err:?}");
+    }
+
+    #[test]
+    fn empty_file_is_empty_allowlist() {
+        assert_eq!(KnownFailures::parse("").unwrap(), KnownFailures::default());
+    }
+}
@@ -0,0 +1,69 @@
+//! Lint rule: module references that resolve to nothing in the codebase map.
+
+use std::path::Path;
+
+use crate::context::ServiceContext;
+use crate::linkage;
+use crate::map::CodebaseMap;
+use crate::spec::TaskSpec;
+
+/// Path of the cached codebase map, relative to the project root.
+const CACHED_MAP_PATH: &str = ".spec-cache/codebase_map.yaml";
+
+/// Loads the cached codebase map under `project_root`, if one exists and parses.
+///
+/// The map is not regenerated; without a cached map there is nothing to lint
+/// against.
+#[must_use]
+pub fn load_cached_map(ctx: &ServiceContext, project_root: &Path) -> Option<CodebaseMap> {
+    let path = project_root.join(CACHED_MAP_PATH);
+    if !ctx.fs.exists(&path) {
+        return None;
+    }
+    let yaml = ctx.fs.read_to_string(&path).ok()?;
+    serde_yaml::from_str(&yaml).ok()
+}
+
+/// Returns the spec's `context.modules` entries that match no module in `map`.
+#[must_use]
+pub fn unresolved_references(spec: &TaskSpec, map: &CodebaseMap) -> Vec<String> {
+    linkage::resolve(spec, map).unresolved().into_iter().map(String::from).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+    use crate::map::ModuleSummary;
+    use crate::spec::TaskContext;
+
+    #[test]
+    fn nonexistent_module_is_reported_unresolved() {
+        let map = CodebaseMap {
+            commit_hash: "abc".to_string(),
+            generated_at: chrono::Utc::now(),
+            modules: vec![ModuleSummary {
+                path: "src/auth".to_string(),
+                public_items: vec!["fn login".to_string()],
+                dependencies: vec![],
+                external_deps: vec![],
+                locations: BTreeMap::new(),
+                crate_name: None,
+            }],
+            directory_tree: vec![],
+            test_infrastructure: vec![],
+            unreadable: vec![],
+        };
+        let spec = TaskSpec {
+            context: Some(TaskContext {
+                modules: vec!["auth".to_string(), "FooService".to_string()],
+                patterns: None,
+                dependencies: vec![],
+            }),
+            ..TaskSpec::for_test("TASK-LINT", "Lint")
+        };
+
+        assert_eq!(unresolved_references(&spec, &map), ["FooService"]);
+    }
+}
@@ -88,6 +88,60 @@ fn invalid_subcommand_exits_with_error() {
     assert!(stderr.contains("unrecognized subcommand"));
 }
 
+#[test]
+fn parse_error_exits_with_usage_code() {
+    let output = run_speck(&["nonsense"]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn failing_validation_exits_with_validation_code() {
+    use std::io::Write as _;
+
+    let spec = "id: EXIT-1\ntitle: Always fails\nacceptance_criteria: [fails]\n\
+                signal_type: clear\nverification:\n  strategy: direct_assertion\n  \
+                checks:\n    - type: test_suite\n      command: \"false\"\n      \
+                expected: pass\n";
+    let mut child = Command::new(env!("CARGO_BIN_EXE_speck"))
+        .args(["validate", "-"])
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .expect("failed to run speck binary");
+    child.stdin.take().unwrap().write_all(spec.as_bytes()).unwrap();
+    let output = child.wait_with_output().unwrap();
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(1), "stderr: {stderr}");
+}
+
+#[test]
+fn unknown_spec_id_exits_with_usage_code() {
+    for args in [&["show", "NO-SUCH-SPEC"][..], &["diff-specs", "NO-SUCH-A", "NO-SUCH-B"]] {
+        let output = run_speck(args);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert_eq!(output.status.code(), Some(2), "{args:?} stderr: {stderr}");
+        assert!(stderr.contains("not found"), "{args:?} stderr: {stderr}");
+    }
+}
+
+#[test]
+fn unknown_sync_target_exits_with_usage_code() {
+    let output = run_speck(&["sync", "jira"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(2), "stderr: {stderr}");
+    assert!(stderr.contains("Unknown sync target"), "stderr: {stderr}");
+}
+
+#[test]
+fn missing_config_path_exits_with_clear_error() {
+    let output = run_speck(&["--config", "does/not/exist.toml", "status"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success());
+    assert!(stderr.contains("config file not found: does/not/exist.toml"), "stderr: {stderr}");
+}
+
 #[test]
 fn plan_with_cassette_produces_specs() {
     let cassette_path =